@@ -0,0 +1,37 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! The synthetic VHDL library used by the type checking benchmark.
+
+use std::fmt::Write;
+
+/// The problem sizes, as the number of entities and the number of signal
+/// assignments in each of their architectures.
+pub const SIZES: &[(usize, usize)] = &[(8, 16), (32, 64)];
+
+/// Generate the source code of a library.
+///
+/// The library contains `entities` entities, each with an architecture that
+/// declares and assigns `assigns` signals.
+pub fn generate_library(entities: usize, assigns: usize) -> String {
+    let mut src = String::new();
+    for i in 0..entities {
+        writeln!(src, "entity e{} is", i).unwrap();
+        writeln!(src, "    port (a : in BIT; y : out BIT);").unwrap();
+        writeln!(src, "end;").unwrap();
+        writeln!(src, "architecture rtl of e{} is", i).unwrap();
+        for j in 0..assigns {
+            writeln!(src, "    signal s{} : BIT_VECTOR(0 to 3);", j).unwrap();
+        }
+        writeln!(src, "begin").unwrap();
+        writeln!(src, "    y <= a;").unwrap();
+        for j in 0..assigns {
+            if j % 2 == 0 {
+                writeln!(src, "    s{} <= \"01\" & \"10\";", j).unwrap();
+            } else {
+                writeln!(src, "    s{} <= s{};", j, j - 1).unwrap();
+            }
+        }
+        writeln!(src, "end;").unwrap();
+    }
+    src
+}
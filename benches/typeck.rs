@@ -0,0 +1,82 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Benchmarks for type checking VHDL libraries.
+//!
+//! A synthetic library of entities with a configurable number of signal
+//! assignments each is generated and parsed once. Every iteration then builds
+//! a fresh scoreboard and type checks the entire library, such that no results
+//! are memoized across iterations. Use criterion's baselines to compare two
+//! versions of the compiler:
+//!
+//! ```text
+//! cargo bench --bench typeck -- --save-baseline before
+//! cargo bench --bench typeck -- --baseline before
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use moore::common::name::get_name_table;
+use moore::common::source::get_source_manager;
+use moore::common::Session;
+use moore::score::{Arenas, Ast, ScoreBoard, ScoreContext};
+use moore::svlog;
+use moore::vhdl;
+use moore::vhdl::typeck::{Typeck, TypeckContext};
+
+mod fixture;
+
+use fixture::{generate_library, SIZES};
+
+/// Parse a generated library.
+fn parse_library(entities: usize, assigns: usize) -> Vec<Ast<'static>> {
+    let source = get_source_manager().add(
+        &format!("bench_{}x{}.vhd", entities, assigns),
+        &generate_library(entities, assigns),
+    );
+    let units = vhdl::syntax::parse(source).expect("generated library does not parse");
+    vec![Ast::Vhdl(units)]
+}
+
+/// Type check all units of a library with a fresh scoreboard.
+///
+/// Returns `true` if no errors were found.
+fn typeck_library(sess: &Session, asts: &[Ast]) -> bool {
+    let svlog_arenas = svlog::GlobalArenas::default();
+    let arenas = Arenas::new();
+    let sb = ScoreBoard::new(&arenas);
+    let vhdl_sb = vhdl::score::ScoreBoard::new(&arenas.vhdl);
+    let svlog_sb = svlog::GlobalContext::new(sess, &svlog_arenas);
+    let vhdl_phases = vhdl::lazy::LazyPhaseTable::new(&vhdl_sb);
+    let ctx = ScoreContext {
+        sess: sess,
+        sb: &sb,
+        vhdl: &vhdl_sb,
+        vhdl_phases: &vhdl_phases,
+        svlog: &svlog_sb,
+    };
+    let lib = ctx.add_library(get_name_table().intern("work", true), asts);
+    let sbc = ctx.vhdl();
+    let tyc = TypeckContext::new(&sbc);
+    tyc.typeck(vhdl::score::LibRef::new(lib.into()));
+    tyc.finish()
+}
+
+fn typeck(c: &mut Criterion) {
+    let sess = Session::new();
+    let mut group = c.benchmark_group("typeck");
+    for &(entities, assigns) in SIZES {
+        let asts = parse_library(entities, assigns);
+        assert!(
+            typeck_library(&sess, &asts),
+            "generated library fails to type check"
+        );
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", entities, assigns)),
+            &asts,
+            |b, asts| b.iter(|| typeck_library(&sess, asts)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, typeck);
+criterion_main!(benches);
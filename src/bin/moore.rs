@@ -112,6 +112,39 @@ fn main() {
                 .takes_value(true)
                 .number_of_values(1),
         )
+        .arg(
+            Arg::with_name("warnings-as-errors")
+                .long("warnings-as-errors")
+                .help("Treat warnings as errors"),
+        )
+        .arg(
+            Arg::with_name("lint-signals")
+                .long("lint-signals")
+                .help("Warn about signals which are never read or never assigned"),
+        )
+        .arg(
+            Arg::with_name("sort-diagnostics")
+                .long("sort-diagnostics")
+                .help("Print diagnostics in source order at the end of each pass"),
+        )
+        .arg(
+            Arg::with_name("max-type-nesting")
+                .long("max-type-nesting")
+                .value_name("N")
+                .help("Maximum number of type names followed when resolving a type")
+                .takes_value(true)
+                .number_of_values(1)
+                .validator(validate_positive),
+        )
+        .arg(
+            Arg::with_name("max-loop-iterations")
+                .long("max-loop-iterations")
+                .value_name("N")
+                .help("Warn about loops whose static range exceeds N iterations")
+                .takes_value(true)
+                .number_of_values(1)
+                .validator(validate_positive),
+        )
         .arg(
             Arg::with_name("lib")
                 .short("l")
@@ -131,6 +164,15 @@ fn main() {
                 .takes_value(true)
                 .number_of_values(1),
         )
+        .arg(
+            Arg::with_name("typeck")
+                .long("typeck")
+                .value_name("UNIT")
+                .help("Type check a VHDL architecture and its entity, or a package and its body")
+                .multiple(true)
+                .takes_value(true)
+                .number_of_values(1),
+        )
         .arg(
             Arg::with_name("INPUT")
                 .help("The input files to compile")
@@ -161,9 +203,28 @@ fn main() {
         };
     }
     session.opts.opt_level = matches.value_of("opt-level").unwrap().parse().unwrap();
+    session.opts.warnings_as_errors = matches.is_present("warnings-as-errors");
+    session.opts.lint_signals = matches.is_present("lint-signals");
+    session.opts.sort_diagnostics = matches.is_present("sort-diagnostics");
+    session.opts.color = atty::is(atty::Stream::Stderr) && std::env::var_os("NO_COLOR").is_none();
+    if let Some(n) = matches.value_of("max-type-nesting") {
+        session.opts.max_type_nesting = n.parse().unwrap();
+    }
+    if let Some(n) = matches.value_of("max-loop-iterations") {
+        session.opts.max_loop_iterations = n.parse().unwrap();
+    }
 
     // Invoke the compiler.
     score(&session, &matches);
+    session.flush_diagnostics();
+}
+
+/// Check that the value of a command line option is a positive integer.
+fn validate_positive(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(()),
+        _ => Err(format!("`{}` is not a positive integer", value)),
+    }
 }
 
 fn score(sess: &Session, matches: &ArgMatches) {
@@ -258,6 +319,7 @@ fn score(sess: &Session, matches: &ArgMatches) {
             },
         }
     }
+    sess.flush_diagnostics();
     if failed || sess.failed() {
         std::process::exit(1);
     }
@@ -283,6 +345,7 @@ fn score(sess: &Session, matches: &ArgMatches) {
     }
 
     // Stop processing if requested.
+    sess.flush_diagnostics();
     if matches.is_present("check-syntax") {
         std::process::exit(0);
     }
@@ -304,6 +367,14 @@ fn score(sess: &Session, matches: &ArgMatches) {
             svlog: &svlog_sb,
         };
         let lib_id = ctx.add_library(lib, &asts);
+        if let Some(names) = matches.values_of("typeck") {
+            let vhdl_lib = vhdl::score::LibRef::new(lib_id.into());
+            for name in names {
+                if !ctx.vhdl().typeck_unit(vhdl_lib, name) {
+                    failed = true;
+                }
+            }
+        }
         if let Some(names) = matches.values_of("elaborate") {
             debug!("lib_id = {:?}", lib_id);
             debug!("{:?}", sb);
@@ -318,6 +389,7 @@ fn score(sess: &Session, matches: &ArgMatches) {
             }
         }
     }
+    sess.flush_diagnostics();
     if failed || sess.failed() {
         std::process::exit(1);
     }
@@ -376,6 +448,7 @@ fn elaborate_name(ctx: &ScoreContext, lib_id: score::LibRef, input_name: &str) -
     enum Elaborate {
         VhdlEntity(vhdl::score::EntityRef, vhdl::score::ArchRef),
         VhdlPkg(vhdl::score::PkgDeclRef),
+        VhdlPkgInst(vhdl::score::PkgInstRef),
         Svlog(NodeId), // TODO: handle svlog case
     };
     let defs = ctx.defs(lib.into())?;
@@ -413,6 +486,7 @@ fn elaborate_name(ctx: &ScoreContext, lib_id: score::LibRef, input_name: &str) -
             Elaborate::VhdlEntity(entity, arch_ref)
         }
         Some(&score::Def::Vhdl(vhdl::score::Def::Pkg(p))) => Elaborate::VhdlPkg(p),
+        Some(&score::Def::Vhdl(vhdl::score::Def::PkgInst(p))) => Elaborate::VhdlPkgInst(p),
         Some(&score::Def::Svlog(e)) => Elaborate::Svlog(e),
         _ => {
             let mut d = DiagBuilder2::error(format!("Item `{}` does not exist", name))
@@ -444,6 +518,12 @@ fn elaborate_name(ctx: &ScoreContext, lib_id: score::LibRef, input_name: &str) -
             // use moore::vhdl::codegen::Codegen;
             // ctx.vhdl().codegen(pkg, &mut ())?;
         }
+        Elaborate::VhdlPkgInst(inst) => {
+            use moore::vhdl::typeck::{Typeck, TypeckContext};
+            let sbc = ctx.vhdl();
+            let tyc = TypeckContext::new(&sbc);
+            tyc.typeck(inst);
+        }
         Elaborate::Svlog(m) => {
             // Emit the detailed type analysis if requested.
             if ctx.sess.has_verbosity(Verbosity::TYPES) {
@@ -75,12 +75,17 @@ pub struct DiagBuilder2 {
     pub severity: Severity,
     pub message: String,
     pub segments: Vec<DiagSegment>,
+    /// Whether the diagnostic is rendered with ANSI color codes.
+    pub color: bool,
 }
 
 #[derive(Clone, Debug)]
 pub enum DiagSegment {
     Span(Span),
     Note(String),
+    /// A span with a message explaining its relation to the diagnostic, e.g.
+    /// "expected type declared here".
+    Label(Span, String),
 }
 
 /// A diagnostic result type. Either carries the result `T` in the Ok variant,
@@ -93,6 +98,7 @@ impl DiagBuilder2 {
             severity: severity,
             message: message.into(),
             segments: Vec::new(),
+            color: true,
         }
     }
 
@@ -133,6 +139,32 @@ impl DiagBuilder2 {
         self.segment(DiagSegment::Note(message.into()))
     }
 
+    /// Attach a span together with a message that describes it.
+    pub fn label<S: Into<Span>, M: Into<String>>(self, span: S, message: M) -> DiagBuilder2 {
+        self.segment(DiagSegment::Label(span.into(), message.into()))
+    }
+
+    /// Remove all color from the diagnostic.
+    ///
+    /// Strips ANSI escape codes from the message, notes, and labels, and
+    /// renders the diagnostic as plain text.
+    pub fn strip_color(self) -> DiagBuilder2 {
+        DiagBuilder2 {
+            message: strip_ansi(&self.message),
+            segments: self
+                .segments
+                .into_iter()
+                .map(|segment| match segment {
+                    DiagSegment::Span(sp) => DiagSegment::Span(sp),
+                    DiagSegment::Note(msg) => DiagSegment::Note(strip_ansi(&msg)),
+                    DiagSegment::Label(sp, msg) => DiagSegment::Label(sp, strip_ansi(&msg)),
+                })
+                .collect(),
+            color: false,
+            ..self
+        }
+    }
+
     pub fn get_severity(&self) -> Severity {
         self.severity
     }
@@ -144,6 +176,14 @@ impl DiagBuilder2 {
     pub fn get_segments(&self) -> &[DiagSegment] {
         &self.segments
     }
+
+    /// Get the first span attached to the diagnostic, if any.
+    pub fn get_primary_span(&self) -> Option<Span> {
+        self.segments.iter().find_map(|seg| match *seg {
+            DiagSegment::Span(sp) | DiagSegment::Label(sp, _) => Some(sp),
+            DiagSegment::Note(_) => None,
+        })
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -173,9 +213,43 @@ impl fmt::Display for Severity {
     }
 }
 
+/// Remove ANSI escape sequences from a string.
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // Skip the `[`, parameters, and the final byte of the sequence.
+            chars.next();
+            while let Some(c) = chars.next() {
+                if c >= '@' && c <= '~' {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 impl fmt::Display for DiagBuilder2 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut colorcode = match self.get_severity() {
+        if self.color {
+            write!(f, "{}", ColoredDiag(self))
+        } else {
+            write!(f, "{}", strip_ansi(&format!("{}", ColoredDiag(self))))
+        }
+    }
+}
+
+/// A diagnostic rendered with ANSI color codes.
+struct ColoredDiag<'a>(&'a DiagBuilder2);
+
+impl<'a> fmt::Display for ColoredDiag<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let diag = self.0;
+        let mut colorcode = match diag.get_severity() {
             Severity::Bug | Severity::Fatal | Severity::Error => "\x1B[31;1m",
             Severity::Warning => "\x1B[33;1m",
             Severity::Note => "\x1B[36;1m",
@@ -184,73 +258,18 @@ impl fmt::Display for DiagBuilder2 {
             f,
             "{}{}:\x1B[m\x1B[1m {}\x1B[m\n",
             colorcode,
-            self.get_severity(),
-            self.get_message()
+            diag.get_severity(),
+            diag.get_message()
         )?;
 
-        for segment in &self.segments {
+        for segment in &diag.segments {
             match *segment {
                 DiagSegment::Span(sp) => {
-                    let c = sp.source.get_content();
-
-                    // Look for the start of the line.
-                    let (line, col, line_offset) = sp.begin().human();
-
-                    // Print the line in question.
-                    let text: String = c
-                        .iter_from(line_offset)
-                        .map(|x| x.1)
-                        .take_while(|c| *c != '\n' && *c != '\r')
-                        .collect();
-                    write!(
-                        f,
-                        "  --> {}:{}:{}-{}:\n",
-                        sp.source.get_path(),
-                        line,
-                        col,
-                        col + sp.extract().len()
-                    )?;
-                    write!(f, "   | \n")?;
-                    write!(f, "   | ")?;
-                    for (mut i, c) in text.char_indices() {
-                        i += line_offset;
-                        if sp.begin != sp.end {
-                            if i == sp.begin {
-                                write!(f, "{}", colorcode)?;
-                            }
-                            if i == sp.end {
-                                write!(f, "\x1B[m")?;
-                            }
-                        }
-                        match c {
-                            '\t' => write!(f, "    ")?,
-                            c => write!(f, "{}", c)?,
-                        }
-                    }
-                    write!(f, "\x1B[m\n")?;
-                    write!(f, "   | ")?;
-
-                    // Print the caret markers for the line in question.
-                    let mut pd = ' ';
-                    for (mut i, c) in text.char_indices() {
-                        i += line_offset;
-                        let d = if (i >= sp.begin && i < sp.end)
-                            || (i == sp.begin && sp.begin == sp.end)
-                        {
-                            '^'
-                        } else {
-                            ' '
-                        };
-                        if d != pd {
-                            write!(f, "{}", if d == ' ' { "\x1B[m" } else { colorcode })?;
-                        }
-                        pd = d;
-                        match c {
-                            '\t' => write!(f, "{}{}{}{}", d, d, d, d)?,
-                            _ => write!(f, "{}", d)?,
-                        }
-                    }
-                    write!(f, "\x1B[m\n")?;
+                    write_span(f, sp, None, colorcode)?;
+                    colorcode = "\x1B[1m";
+                }
+                DiagSegment::Label(sp, ref message) => {
+                    write_span(f, sp, Some(message.as_str()), colorcode)?;
                     colorcode = "\x1B[1m";
                 }
                 DiagSegment::Note(ref message) => {
@@ -259,7 +278,7 @@ impl fmt::Display for DiagBuilder2 {
             }
         }
 
-        if self.get_severity() == Severity::Bug {
+        if diag.get_severity() == Severity::Bug {
             write!(
                 f,
                 "\nYou have encountered a compiler bug. Sorry about that! We would appreciate if \
@@ -272,3 +291,78 @@ impl fmt::Display for DiagBuilder2 {
         Ok(())
     }
 }
+
+/// Print a span of source code with caret markers underneath it, optionally
+/// followed by a label.
+fn write_span(
+    f: &mut fmt::Formatter,
+    sp: Span,
+    label: Option<&str>,
+    colorcode: &str,
+) -> fmt::Result {
+    let c = sp.source.get_content();
+
+    // Look for the start of the line.
+    let (line, col, line_offset) = sp.begin().human();
+
+    // Print the line in question.
+    let text: String = c
+        .iter_from(line_offset)
+        .map(|x| x.1)
+        .take_while(|c| *c != '\n' && *c != '\r')
+        .collect();
+    write!(
+        f,
+        "  --> {}:{}:{}-{}:\n",
+        sp.source.get_path(),
+        line,
+        col,
+        col + sp.extract().len()
+    )?;
+    write!(f, "   | \n")?;
+    write!(f, "   | ")?;
+    for (mut i, c) in text.char_indices() {
+        i += line_offset;
+        if sp.begin != sp.end {
+            if i == sp.begin {
+                write!(f, "{}", colorcode)?;
+            }
+            if i == sp.end {
+                write!(f, "\x1B[m")?;
+            }
+        }
+        match c {
+            '\t' => write!(f, "    ")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\x1B[m\n")?;
+    write!(f, "   | ")?;
+
+    // Print the caret markers for the line in question.
+    let mut pd = ' ';
+    for (mut i, c) in text.char_indices() {
+        i += line_offset;
+        if label.is_some() && i >= sp.end.max(sp.begin + 1) {
+            break;
+        }
+        let d = if (i >= sp.begin && i < sp.end) || (i == sp.begin && sp.begin == sp.end) {
+            '^'
+        } else {
+            ' '
+        };
+        if d != pd {
+            write!(f, "{}", if d == ' ' { "\x1B[m" } else { colorcode })?;
+        }
+        pd = d;
+        match c {
+            '\t' => write!(f, "{}{}{}{}", d, d, d, d)?,
+            _ => write!(f, "{}", d)?,
+        }
+    }
+    match label {
+        Some(label) => write!(f, " {}{}\x1B[m\n", colorcode, label)?,
+        None => write!(f, "\x1B[m\n")?,
+    }
+    Ok(())
+}
@@ -19,37 +19,219 @@ pub mod util;
 
 pub use self::id::NodeId;
 use crate::errors::{DiagBuilder2, DiagEmitter, Severity};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 pub struct Session {
     pub opts: SessionOptions,
     /// Whether any error diagnostics were produced.
     pub failed: Cell<bool>,
+    /// The sink that receives all emitted diagnostics.
+    pub sink: Box<dyn DiagnosticSink>,
+    /// How often each unimplemented compiler path was hit, by node kind.
+    unimplemented: RefCell<HashMap<String, usize>>,
+    /// How often types were interned, and how often that required allocation.
+    intern_stats: Cell<InternStats>,
+    /// Diagnostics held back until the next flush, if they are to be sorted.
+    buffered: RefCell<Vec<DiagBuilder2>>,
+    /// Whether diagnostics are being captured rather than passed to the sink.
+    capturing: Cell<bool>,
+    /// Set to abort long-running passes such as type checking.
+    cancelled: Arc<AtomicBool>,
 }
 
 impl Session {
-    /// Create a new session.
+    /// Create a new session that prints diagnostics to stderr.
     pub fn new() -> Session {
+        Session::with_sink(StderrSink)
+    }
+
+    /// Create a new session that passes diagnostics to a custom sink.
+    pub fn with_sink<S: DiagnosticSink + 'static>(sink: S) -> Session {
         Session {
             opts: Default::default(),
             failed: Cell::new(false),
+            sink: Box::new(sink),
+            unimplemented: Default::default(),
+            intern_stats: Default::default(),
+            buffered: Default::default(),
+            capturing: Default::default(),
+            cancelled: Default::default(),
         }
     }
 
     pub fn failed(&self) -> bool {
         self.failed.get()
     }
+
+    /// Record that an unimplemented compiler path was hit for a kind of node.
+    pub fn record_unimplemented<S: Into<String>>(&self, kind: S) {
+        *self
+            .unimplemented
+            .borrow_mut()
+            .entry(kind.into())
+            .or_insert(0) += 1;
+    }
+
+    /// Report how often each unimplemented compiler path was hit.
+    ///
+    /// Returns the node kinds together with their number of hits, the most
+    /// frequent first. Kinds with the same number of hits are sorted by name.
+    pub fn coverage_report(&self) -> Vec<(String, usize)> {
+        let mut report: Vec<_> = self
+            .unimplemented
+            .borrow()
+            .iter()
+            .map(|(kind, &count)| (kind.clone(), count))
+            .collect();
+        report.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        report
+    }
+
+    /// Record that a type was interned.
+    ///
+    /// `hit` indicates whether an equal type had already been interned, such
+    /// that no new allocation was necessary.
+    pub fn record_intern(&self, hit: bool) {
+        let mut stats = self.intern_stats.get();
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.allocations += 1;
+        }
+        self.intern_stats.set(stats);
+    }
+
+    /// Report how often types were interned so far.
+    pub fn intern_stats(&self) -> InternStats {
+        self.intern_stats.get()
+    }
+
+    /// Get a handle to the cancellation flag of the session.
+    ///
+    /// Front-ends may keep the handle and set it, possibly from another
+    /// thread, to abort a stale analysis. Passes check the flag periodically
+    /// and return early once it is set.
+    pub fn cancel_token(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    /// Request that running passes be aborted.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether running passes have been asked to abort.
+    ///
+    /// A cancelled pass is not considered failed, since it may simply not
+    /// have reached the erroneous parts of the input.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Pass all buffered diagnostics on to the sink.
+    ///
+    /// Diagnostics are only buffered if `sort_diagnostics` is set. They are
+    /// sorted by their primary span, such that the output does not depend on
+    /// the order in which they were emitted. Diagnostics without a span come
+    /// last, in the order they were emitted.
+    pub fn flush_diagnostics(&self) {
+        for diag in self.take_diagnostics() {
+            self.sink.emit(diag);
+        }
+    }
+
+    /// Run a function and collect the diagnostics it emits.
+    ///
+    /// The diagnostics are returned sorted by their primary span, as for
+    /// `flush_diagnostics`, rather than being passed on to the sink.
+    /// Diagnostics buffered before the call are left untouched.
+    pub fn capture_diagnostics<R, F: FnOnce() -> R>(&self, f: F) -> (R, Vec<DiagBuilder2>) {
+        let outer = std::mem::replace(&mut *self.buffered.borrow_mut(), Vec::new());
+        let was_capturing = self.capturing.replace(true);
+        let result = f();
+        self.capturing.set(was_capturing);
+        let diags = self.take_diagnostics();
+        *self.buffered.borrow_mut() = outer;
+        (result, diags)
+    }
+
+    /// Remove all buffered diagnostics, sorted by their primary span.
+    fn take_diagnostics(&self) -> Vec<DiagBuilder2> {
+        let mut diags = std::mem::replace(&mut *self.buffered.borrow_mut(), Vec::new());
+        diags.sort_by_key(|diag| {
+            let span = diag.get_primary_span();
+            (span.is_none(), span)
+        });
+        diags
+    }
+}
+
+/// Statistics on the interning of types.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InternStats {
+    /// The number of types that were already interned.
+    pub hits: usize,
+    /// The number of types that had to be allocated.
+    pub allocations: usize,
 }
 
 impl DiagEmitter for Session {
     fn emit(&self, diag: DiagBuilder2) {
-        if diag.severity >= Severity::Error {
+        if diag.severity >= Severity::Error
+            || (diag.severity >= Severity::Warning && self.opts.warnings_as_errors)
+        {
             self.failed.set(true);
         }
+        let diag = if self.opts.color {
+            diag
+        } else {
+            diag.strip_color()
+        };
+        if self.opts.sort_diagnostics || self.capturing.get() {
+            self.buffered.borrow_mut().push(diag);
+        } else {
+            self.sink.emit(diag);
+        }
+    }
+}
+
+/// A receiver of the diagnostics emitted during a session.
+///
+/// Embedders may provide their own sink to capture diagnostics rather than
+/// printing them, e.g. to forward them to an API or language server.
+pub trait DiagnosticSink {
+    /// Receive a diagnostic message.
+    fn emit(&self, diag: DiagBuilder2);
+}
+
+/// A diagnostic sink that prints diagnostics to stderr.
+pub struct StderrSink;
+
+impl DiagnosticSink for StderrSink {
+    fn emit(&self, diag: DiagBuilder2) {
         eprintln!("{}", diag);
     }
 }
 
+/// Collects diagnostics into a vector.
+impl DiagnosticSink for RefCell<Vec<DiagBuilder2>> {
+    fn emit(&self, diag: DiagBuilder2) {
+        self.borrow_mut().push(diag);
+    }
+}
+
+/// Allows a sink to be shared with the session, such that it can still be
+/// inspected after the diagnostics have been emitted.
+impl<T: DiagnosticSink + ?Sized> DiagnosticSink for Rc<T> {
+    fn emit(&self, diag: DiagBuilder2) {
+        (**self).emit(diag)
+    }
+}
+
 impl SessionContext for Session {
     fn has_verbosity(&self, verb: Verbosity) -> bool {
         self.opts.verbosity.contains(verb)
@@ -66,7 +248,7 @@ pub trait SessionContext: DiagEmitter {
 ///
 /// The arguments passed on the command line are intended to modify these values
 /// in order to configure the execution of the program.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct SessionOptions {
     pub ignore_duplicate_defs: bool,
     /// Print a trace of scoreboard invocations for debugging purposes.
@@ -75,8 +257,46 @@ pub struct SessionOptions {
     pub verbosity: Verbosity,
     /// The optimization level.
     pub opt_level: usize,
+    /// Treat warnings as errors, such that they cause compilation to fail.
+    pub warnings_as_errors: bool,
+    /// The maximum number of type names followed when resolving a type.
+    pub max_type_nesting: usize,
+    /// The number of iterations above which a loop or generate statement with
+    /// a static range is reported as suspicious.
+    pub max_loop_iterations: usize,
+    /// Render diagnostics with ANSI color codes. If disabled, diagnostics are
+    /// passed to the sink as plain text.
+    pub color: bool,
+    /// Warn about signals which are never read or never assigned.
+    pub lint_signals: bool,
+    /// Hold back diagnostics until `Session::flush_diagnostics` is called,
+    /// and emit them sorted by their location in the source code.
+    pub sort_diagnostics: bool,
 }
 
+impl Default for SessionOptions {
+    fn default() -> SessionOptions {
+        SessionOptions {
+            ignore_duplicate_defs: false,
+            trace_scoreboard: false,
+            verbosity: Verbosity::empty(),
+            opt_level: 0,
+            warnings_as_errors: false,
+            max_type_nesting: DEFAULT_MAX_TYPE_NESTING,
+            max_loop_iterations: DEFAULT_MAX_LOOP_ITERATIONS,
+            color: false,
+            lint_signals: false,
+            sort_diagnostics: false,
+        }
+    }
+}
+
+/// The default value of `SessionOptions::max_type_nesting`.
+pub const DEFAULT_MAX_TYPE_NESTING: usize = 256;
+
+/// The default value of `SessionOptions::max_loop_iterations`.
+pub const DEFAULT_MAX_LOOP_ITERATIONS: usize = 1 << 24;
+
 bitflags! {
     /// A set of verbosity options for a session.
     ///
@@ -94,3 +314,80 @@ bitflags! {
         const INSTS         = 1 << 8;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_diagnostics() {
+        let sink = Rc::new(RefCell::new(Vec::new()));
+        let sess = Session::with_sink(sink.clone());
+        sess.emit(DiagBuilder2::warning("null range"));
+        assert!(!sess.failed());
+        sess.emit(DiagBuilder2::error("expected type BIT, but got INTEGER"));
+        assert!(sess.failed());
+        let diags = sink.borrow();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(diags[1].severity, Severity::Error);
+        assert_eq!(diags[1].message, "expected type BIT, but got INTEGER");
+    }
+
+    #[test]
+    fn default_limits() {
+        let sess = Session::new();
+        assert_eq!(sess.opts.max_type_nesting, DEFAULT_MAX_TYPE_NESTING);
+        assert_eq!(sess.opts.max_loop_iterations, DEFAULT_MAX_LOOP_ITERATIONS);
+    }
+
+    #[test]
+    fn strip_color_without_tty() {
+        let sink = Rc::new(RefCell::new(Vec::new()));
+        let sess = Session::with_sink(sink.clone());
+        sess.emit(DiagBuilder2::error("expected type \x1B[32mBIT\x1B[39m"));
+        let diags = sink.borrow();
+        assert_eq!(diags[0].message, "expected type BIT");
+        assert_eq!(format!("{}", diags[0]), "error: expected type BIT\n");
+    }
+
+    #[test]
+    fn sort_diagnostics() {
+        use crate::source::{get_source_manager, Span};
+        let source = get_source_manager().add("sort_diagnostics.vhd", "a := 1;\nb := 2;\n");
+        let sink = Rc::new(RefCell::new(Vec::new()));
+        let mut sess = Session::with_sink(sink.clone());
+        sess.opts.sort_diagnostics = true;
+        sess.emit(DiagBuilder2::error("unknown name `b`").span(Span::new(source, 8, 9)));
+        sess.emit(DiagBuilder2::note("compiling"));
+        sess.emit(DiagBuilder2::error("unknown name `a`").span(Span::new(source, 0, 1)));
+        assert!(sess.failed());
+        assert!(sink.borrow().is_empty());
+        sess.flush_diagnostics();
+        let messages: Vec<_> = sink.borrow().iter().map(|d| d.message.clone()).collect();
+        assert_eq!(
+            messages,
+            vec!["unknown name `a`", "unknown name `b`", "compiling"]
+        );
+    }
+
+    #[test]
+    fn capture_diagnostics() {
+        use crate::source::{get_source_manager, Span};
+        let source = get_source_manager().add("capture_diagnostics.vhd", "a := 1;\nb := 2;\n");
+        let sink = Rc::new(RefCell::new(Vec::new()));
+        let sess = Session::with_sink(sink.clone());
+        let (result, diags) = sess.capture_diagnostics(|| {
+            sess.emit(DiagBuilder2::error("unknown name `b`").span(Span::new(source, 8, 9)));
+            sess.emit(DiagBuilder2::error("unknown name `a`").span(Span::new(source, 0, 1)));
+            42
+        });
+        assert_eq!(result, 42);
+        assert!(sess.failed());
+        assert!(sink.borrow().is_empty());
+        let messages: Vec<_> = diags.iter().map(|d| d.message.clone()).collect();
+        assert_eq!(messages, vec!["unknown name `a`", "unknown name `b`"]);
+        sess.emit(DiagBuilder2::note("compiling"));
+        assert_eq!(sink.borrow().len(), 1);
+    }
+}
@@ -460,6 +460,9 @@ pub struct Span {
     pub end: usize,
 }
 
+/// The maximum number of characters reproduced by `Span::snippet`.
+pub const MAX_SNIPPET_LEN: usize = 40;
+
 impl Span {
     /// Create a new span from two byte offsets.
     pub fn new(source: Source, begin: usize, end: usize) -> Span {
@@ -515,6 +518,34 @@ impl Span {
         self.source.get_content().extract(self.begin, self.end)
     }
 
+    /// Copy a short excerpt of this span into an owned string.
+    ///
+    /// Meant for quoting source text in diagnostic messages. Only the first
+    /// line of the span is reproduced, truncated to `MAX_SNIPPET_LEN`
+    /// characters. An ellipsis marks that text was omitted.
+    pub fn snippet(&self) -> String {
+        let text = self.extract();
+        let text = text.trim_end();
+        let first = text.lines().next().unwrap_or("").trim_end();
+        let mut snippet: String = first.chars().take(MAX_SNIPPET_LEN).collect();
+        if snippet.len() < text.len() {
+            snippet.push_str(" ...");
+        }
+        snippet
+    }
+
+    /// Shrink this span to the part that lies on its first line.
+    ///
+    /// Diagnostics only underline a single line of source text, so spans that
+    /// cover multiple lines are best cut down to their first line.
+    pub fn first_line(&self) -> Span {
+        let text = self.extract();
+        match text.find(|c| c == '\n' || c == '\r') {
+            Some(offset) => Span::new(self.source, self.begin, self.begin + offset),
+            None => *self,
+        }
+    }
+
     /// Obtain an iterator over the extract of the source file describe by this
     /// span.
     pub fn iter<'a>(self, content: &'a Rc<dyn SourceContent>) -> Box<CharIter<'a>> {
@@ -635,6 +666,27 @@ mod tests {
         assert_eq!(sm.open("/this/path/points/nowhere"), None);
     }
 
+    #[test]
+    fn snippet() {
+        let sm = get_source_manager();
+        let source = sm.add(
+            "snippet.vhd",
+            "x <= a + b;\ny <= (c,\n  d);\nz <= 0123456789012345678901234567890123456789012345;\n",
+        );
+        let single = Span::new(source, 5, 10);
+        assert_eq!(single.snippet(), "a + b");
+        assert_eq!(single.first_line(), single);
+        let multi = Span::new(source, 17, 26);
+        assert_eq!(multi.extract(), "(c,\n  d);");
+        assert_eq!(multi.snippet(), "(c, ...");
+        assert_eq!(multi.first_line(), Span::new(source, 17, 20));
+        let long = Span::new(source, 32, 78);
+        assert_eq!(
+            long.snippet(),
+            "0123456789012345678901234567890123456789 ..."
+        );
+    }
+
     #[test]
     fn chars() {
         let sm = get_source_manager();
@@ -38,6 +38,8 @@ lazy_static! {
     pub static ref BOOLEAN_TYPE: BuiltinType = BuiltinType::new_enum("BOOLEAN");
     /// The builtin `BIT` type.
     pub static ref BIT_TYPE: BuiltinType = BuiltinType::new_enum("BIT");
+    /// The builtin `CHARACTER` type.
+    pub static ref CHARACTER_TYPE: BuiltinType = BuiltinType::new_enum("CHARACTER");
     /// The builtin `SEVERITY_LEVEL` type.
     pub static ref SEVERITY_LEVEL_TYPE: BuiltinType = BuiltinType::new_enum("SEVERITY_LEVEL");
     /// A reference to the type `INTEGER`.
@@ -82,23 +84,28 @@ lazy_static! {
         1.into(),
         i32::max_value().into()
     ));
+    /// The builtin `STRING` type.
+    pub static ref STRING_TYPE: BuiltinType = BuiltinType::new_array("STRING", ArrayTy::new(
+        vec![ArrayIndex::Unbounded(Box::new(POSITIVE_TYPE.named_ty()))],
+        Box::new(CHARACTER_TYPE.named_ty())
+    ));
     /// The builtin `BOOLEAN_VECTOR` type.
-    pub static ref BOOLEAN_VECTOR_TYPE: BuiltinType = BuiltinType::new("BOOLEAN_VECTOR", ArrayTy::new(
+    pub static ref BOOLEAN_VECTOR_TYPE: BuiltinType = BuiltinType::new_array("BOOLEAN_VECTOR", ArrayTy::new(
         vec![ArrayIndex::Unbounded(Box::new(NATURAL_TYPE.named_ty()))],
         Box::new(BOOLEAN_TYPE.named_ty())
     ));
     /// The builtin `BIT_VECTOR` type.
-    pub static ref BIT_VECTOR_TYPE: BuiltinType = BuiltinType::new("BIT_VECTOR", ArrayTy::new(
+    pub static ref BIT_VECTOR_TYPE: BuiltinType = BuiltinType::new_array("BIT_VECTOR", ArrayTy::new(
         vec![ArrayIndex::Unbounded(Box::new(NATURAL_TYPE.named_ty()))],
         Box::new(BIT_TYPE.named_ty())
     ));
     /// The builtin `INTEGER_VECTOR` type.
-    pub static ref INTEGER_VECTOR_TYPE: BuiltinType = BuiltinType::new("INTEGER_VECTOR", ArrayTy::new(
+    pub static ref INTEGER_VECTOR_TYPE: BuiltinType = BuiltinType::new_array("INTEGER_VECTOR", ArrayTy::new(
         vec![ArrayIndex::Unbounded(Box::new(NATURAL_TYPE.named_ty()))],
         Box::new(INTEGER_TYPE.named_ty())
     ));
     /// The builtin `TIME_VECTOR` type.
-    pub static ref TIME_VECTOR_TYPE: BuiltinType = BuiltinType::new("TIME_VECTOR", ArrayTy::new(
+    pub static ref TIME_VECTOR_TYPE: BuiltinType = BuiltinType::new_array("TIME_VECTOR", ArrayTy::new(
         vec![ArrayIndex::Unbounded(Box::new(NATURAL_TYPE.named_ty()))],
         Box::new(TIME_TYPE.named_ty())
     ));
@@ -163,12 +170,14 @@ lazy_static! {
         let mut bi = Vec::new();
         bi.push(wrapup_type_builtin(&BOOLEAN_TYPE));
         bi.push(wrapup_type_builtin(&BIT_TYPE));
+        bi.push(wrapup_type_builtin(&CHARACTER_TYPE));
         bi.push(wrapup_type_builtin(&SEVERITY_LEVEL_TYPE));
         bi.push(wrapup_type_builtin(&INTEGER_TYPE));
         bi.push(wrapup_type_builtin(&TIME_TYPE));
         bi.push(wrapup_type_builtin(&DELAY_LENGTH_TYPE));
         bi.push(wrapup_type_builtin(&NATURAL_TYPE));
         bi.push(wrapup_type_builtin(&POSITIVE_TYPE));
+        bi.push(wrapup_type_builtin(&STRING_TYPE));
         bi.push(wrapup_type_builtin(&BOOLEAN_VECTOR_TYPE));
         bi.push(wrapup_type_builtin(&BIT_VECTOR_TYPE));
         bi.push(wrapup_type_builtin(&INTEGER_VECTOR_TYPE));
@@ -230,6 +239,11 @@ fn wrapup_type_builtin(bt: &BuiltinType) -> (Builtin, Vec<Builtin>) {
     let bi = Builtin::new(Def::Type(bt.id), bt.name).ty(bt.ty.clone());
     let mut aux = Vec::new();
 
+    // Builtin subtypes share the predefined operators of their base type.
+    if builtin_base_type(bt.id) != bt.id {
+        return (bi, aux);
+    }
+
     // Add the usual predefined operators that all types get.
     match bt.ty {
         Ty::Enum(_) => enum_type_builtins(&bt.named_ty(), &mut aux),
@@ -328,6 +342,12 @@ lazy_static! {
         define_builtin_bit(&mut scope, '0', Def::Enum(EnumRef(BIT_TYPE.id, 0)));
         define_builtin_bit(&mut scope, '1', Def::Enum(EnumRef(BIT_TYPE.id, 1)));
 
+        // `type CHARACTER is (NUL, SOH, ..., 'ÿ')`
+        // define_builtin_ident(&mut scope, "CHARACTER", Def::Type(CHARACTER_TYPE.id));
+        for (index, lit) in character_literals().into_iter().enumerate() {
+            define_builtin(&mut scope, lit, Def::Enum(EnumRef(CHARACTER_TYPE.id, index)));
+        }
+
         // `type SEVERITY_LEVEL is (NOTE, WARNING, ERROR, FAILURE)`
         // define_builtin_ident(&mut scope, "SEVERITY_LEVEL", Def::Type(SEVERITY_LEVEL_TYPE.id));
         define_builtin_ident(&mut scope, "NOTE", Def::Enum(EnumRef(SEVERITY_LEVEL_TYPE.id, 0)));
@@ -358,6 +378,9 @@ lazy_static! {
         // `subtype POSITIVE is INTEGER range 1 to INTEGER'HIGH`
         // define_builtin_ident(&mut scope, "POSITIVE", Def::Type(POSITIVE_TYPE.id));
 
+        // `type STRING is array (POSITIVE range <>) of CHARACTER`
+        // define_builtin_ident(&mut scope, "STRING", Def::Type(STRING_TYPE.id));
+
         // `type BOOLEAN_VECTOR is array (NATURAL range <>) of BOOLEAN`
         // define_builtin_ident(&mut scope, "BOOLEAN_VECTOR", Def::Type(BOOLEAN_VECTOR_TYPE.id));
 
@@ -423,6 +446,77 @@ pub fn register_builtins<'ast, 'ctx>(sb: &ScoreBoard<'ast, 'ctx>) {
                 None => None,
             }),
     );
+
+    // Register the canonical named types of the builtin types, such that they
+    // are referred to by their declared name.
+    for &(ref bi, _) in (*STANDARD_BUILTINS).iter() {
+        if let (Def::Type(id), ResolvableName::Ident(name)) = (bi.def, bi.name) {
+            sb.intern_named_ty(name, id.into());
+        }
+    }
+}
+
+/// Check whether a type declaration is one of the builtin types.
+///
+/// Builtin types have no AST or HIR to consult.
+pub fn is_builtin_type(id: TypeDeclRef) -> bool {
+    (*STANDARD_BUILTINS)
+        .iter()
+        .any(|&(ref bi, _)| match bi.def {
+            Def::Type(t) => t == id,
+            _ => false,
+        })
+}
+
+/// Determine the base type of a builtin type.
+///
+/// The builtin subtypes are declared as types, since they have no subtype
+/// declaration to consult. This maps them back to the type they constrain.
+pub fn builtin_base_type(id: TypeDeclRef) -> TypeDeclRef {
+    if id == NATURAL_TYPE.id || id == POSITIVE_TYPE.id {
+        INTEGER_TYPE.id
+    } else if id == DELAY_LENGTH_TYPE.id {
+        TIME_TYPE.id
+    } else {
+        id
+    }
+}
+
+/// Determine the literals of a builtin enumeration type, in order.
+pub fn builtin_enum_literals(id: TypeDeclRef) -> Vec<ResolvableName> {
+    let mut lits: Vec<_> = STANDARD_PKG_SCOPE
+        .defs
+        .iter()
+        .flat_map(|(&name, defs)| {
+            defs.iter().filter_map(move |def| match def.value {
+                Def::Enum(EnumRef(ty, index)) if ty == id => Some((index, name)),
+                _ => None,
+            })
+        })
+        .collect();
+    lits.sort_by_key(|&(index, _)| index);
+    lits.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Determine the literals of the builtin `CHARACTER` type, in order.
+///
+/// These are the 256 characters of ISO/IEC 8859-1, where the control
+/// characters are named by identifiers. See IEEE 1076-2008 section 16.3.
+fn character_literals() -> Vec<ResolvableName> {
+    const CONTROL: [&str; 32] = [
+        "NUL", "SOH", "STX", "ETX", "EOT", "ENQ", "ACK", "BEL", "BS", "HT", "LF", "VT", "FF", "CR",
+        "SO", "SI", "DLE", "DC1", "DC2", "DC3", "DC4", "NAK", "SYN", "ETB", "CAN", "EM", "SUB",
+        "ESC", "FSP", "GSP", "RSP", "USP",
+    ];
+    let ident = |name: &str| get_name_table().intern(name, false).into();
+    (0u32..256)
+        .map(|code| match code {
+            0..=31 => ident(CONTROL[code as usize]),
+            127 => ident("DEL"),
+            128..=159 => ident(&format!("C{}", code)),
+            _ => std::char::from_u32(code).unwrap().into(),
+        })
+        .collect()
 }
 
 /// Create a physical type with time units.
@@ -692,6 +786,10 @@ fn array_type_builtins(ty: &Ty, aty: &ArrayTy, into: &mut Vec<Builtin>) {
         into.push(Builtin::operator(UnaryOp::Logical(LogicalOp::Xor)).ty(reduce_ty.clone()));
         into.push(Builtin::operator(UnaryOp::Logical(LogicalOp::Xnor)).ty(reduce_ty.clone()));
 
+        // The type `(A) return A`.
+        let unary_ty = SubprogTy::new(vec![SubprogTyArg::positional(ty.clone())], Some(ty.clone()));
+        into.push(Builtin::operator(UnaryOp::Not).ty(unary_ty.clone()));
+
         // The type `(A,T) return A` and `(T,A) return A`.
         let scalar_right_ty = SubprogTy::new(
             vec![
@@ -839,6 +937,12 @@ impl BuiltinType {
         }
     }
 
+    /// Create a new builtin array type.
+    pub fn new_array(name: &str, ty: ArrayTy) -> BuiltinType {
+        let id = TypeDeclRef::alloc();
+        BuiltinType::with_id(id, name, ty.with_decl(Some(id)))
+    }
+
     /// Create a new builtin enum type.
     pub fn new_enum(name: &str) -> BuiltinType {
         let id = TypeDeclRef::alloc();
@@ -136,7 +136,24 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     .collect::<Result<_>>()?;
                 llhd::struct_ty(fields)
             }
+            Ty::Protected(ref ty) => {
+                self.emit(DiagBuilder2::error(format!(
+                    "cannot generate code for protected type `{}`",
+                    ty
+                )));
+                return Err(());
+            }
+            Ty::Float(ref ty) => {
+                self.emit(DiagBuilder2::error(format!(
+                    "cannot generate code for floating-point type `{}`",
+                    ty
+                )));
+                return Err(());
+            }
             Ty::Subprog(..) => unimplemented!(),
+            // Ranges only exist during type checking and have no
+            // representation in LLHD.
+            Ty::Range(..) => unreachable!(),
             // Unbounded integers cannot be mapped to LLHD. All cases where
             // such an int can leak through to codegen should actually be caught
             // beforehand in the type check.
@@ -150,6 +167,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             // TODO: Map this to llhd::const_void once available.
             Const::Null => llhd::const_int(0, 0.into()),
             Const::Int(ref k) => llhd::const_int(999, k.value.clone()),
+            Const::Physical(ref k) => llhd::const_int(999, k.normalized()),
             Const::Enum(ref k) => {
                 let size = match self.lazy_hir(k.decl)?.data.as_ref().unwrap().value {
                     hir::TypeData::Enum(ref lits) => lits.len(),
@@ -2,10 +2,12 @@
 
 //! A compiler pass that gathers definitions.
 
+use crate::add_ctx::AddContext;
 use crate::hir;
 use crate::score::*;
 use crate::syntax::ast;
 use moore_common::errors::*;
+use moore_common::name::Name;
 use moore_common::score::Result;
 use moore_common::source::*;
 #[deny(missing_docs)]
@@ -64,7 +66,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }
         match def {
             // Handle overloadable cases.
-            Def::Enum(_) => {
+            _ if self.is_overloadable(def) => {
                 self.defs
                     .entry(name.value)
                     .or_insert_with(|| Vec::new())
@@ -88,6 +90,15 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
+    /// Check whether a definition may share its name with others.
+    fn is_overloadable(&self, def: Def) -> bool {
+        match def {
+            Def::Enum(_) | Def::Subprog(_) => true,
+            Def::Alias(id) => self.ctx.is_subprog_alias(id),
+            _ => false,
+        }
+    }
+
     /// Declare a primary name in the scope.
     ///
     /// This converts the name to a `ResolvableName` and calls `declare()`.
@@ -111,6 +122,15 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     /// Handle type declarations.
     pub fn declare_type(&mut self, id: TypeDeclRef) {
         let ast = self.ctx.ast(id).1;
+        // Protected type bodies complete an earlier declaration and introduce
+        // no name of their own.
+        if let Some(Spanned {
+            value: ast::ProtectedBody(..),
+            ..
+        }) = ast.data
+        {
+            return;
+        }
         self.declare(ast.name.map_into(), Def::Type(id));
         // This is a rather hacky way of declaring the variant names for enum
         // literals, but it does not require the HIR to be constructed, which is
@@ -158,7 +178,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         match id {
             DeclInBlockRef::Subprog(id) => self.declare_subprog(id),
             DeclInBlockRef::SubprogInst(id) => self.declare_subprog_inst(id),
-            DeclInBlockRef::SubprogBody(_id) => (),
+            DeclInBlockRef::SubprogBody(id) => self.declare_subprog_body(id),
             DeclInBlockRef::Pkg(id) => self.declare_pkg(id),
             DeclInBlockRef::PkgInst(id) => self.declare_pkg_inst(id),
             DeclInBlockRef::PkgBody(_id) => (),
@@ -228,7 +248,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     pub fn declare_any_in_subprog(&mut self, id: DeclInSubprogRef) {
         match id {
             DeclInSubprogRef::Subprog(id) => self.declare_subprog(id),
-            DeclInSubprogRef::SubprogBody(_id) => (),
+            DeclInSubprogRef::SubprogBody(id) => self.declare_subprog_body(id),
             DeclInSubprogRef::SubprogInst(id) => self.declare_subprog_inst(id),
             DeclInSubprogRef::Pkg(id) => self.declare_pkg(id),
             DeclInSubprogRef::PkgBody(_id) => (),
@@ -251,7 +271,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         match id {
             DeclInProcRef::Subprog(id) => self.declare_subprog(id),
             DeclInProcRef::SubprogInst(id) => self.declare_subprog_inst(id),
-            DeclInProcRef::SubprogBody(_id) => (),
+            DeclInProcRef::SubprogBody(id) => self.declare_subprog_body(id),
             DeclInProcRef::Pkg(id) => self.declare_pkg(id),
             DeclInProcRef::PkgInst(id) => self.declare_pkg_inst(id),
             DeclInProcRef::PkgBody(_id) => (),
@@ -346,6 +366,34 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         self.declare_primary_name(&self.ctx.ast(id).1.spec.name, Def::Subprog(id))
     }
 
+    /// Handle subprogram bodies.
+    ///
+    /// A body which does not complete an earlier declaration in the same
+    /// region also declares the subprogram. See IEEE 1076-2008 section 4.2.1.
+    pub fn declare_subprog_body(&mut self, id: SubprogBodyRef) {
+        let (scope, ast) = self.ctx.ast(id);
+        let name = match self.ctx.resolvable_from_primary_name(&ast.spec.name) {
+            Ok(n) => n,
+            Err(()) => {
+                self.failed = true;
+                return;
+            }
+        };
+        let completes = self.defs.get(&name.value).map_or(false, |defs| {
+            defs.iter().any(|def| match def.value {
+                Def::Subprog(decl) => specs_conform(&self.ctx.ast(decl).1.spec, &ast.spec),
+                _ => false,
+            })
+        });
+        if completes {
+            return;
+        }
+        match AddContext::new(self.ctx, scope).add_subprog_decl(ast) {
+            Ok(decl) => self.declare(name, Def::Subprog(decl)),
+            Err(()) => self.failed = true,
+        }
+    }
+
     /// Handle subprogram instantiations.
     pub fn declare_subprog_inst(&mut self, id: SubprogInstRef) {
         self.declare_primary_name(&self.ctx.ast(id).1.spec.name, Def::SubprogInst(id))
@@ -364,8 +412,14 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     ///
     /// These are mainly subprogram parameters and entity ports.
     pub fn declare_intf_objs(&mut self, ids: &[IntfObjRef]) {
-        if !ids.is_empty() {
-            unimplemented!();
+        for &id in ids {
+            let (name, def) = match id {
+                IntfObjRef::Const(id) => (self.ctx.ast(id).3, Def::Param(id.into())),
+                IntfObjRef::Var(id) => (self.ctx.ast(id).3, Def::Param(id.into())),
+                IntfObjRef::Signal(id) => (self.ctx.ast(id).3, Def::Signal(id.into())),
+                IntfObjRef::File(id) => (self.ctx.ast(id).3, Def::Param(id.into())),
+            };
+            self.declare(Spanned::new(name.name, name.span).map_into(), def);
         }
     }
 
@@ -376,3 +430,32 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> DefsContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 }
+
+/// Check whether two subprogram specifications conform.
+///
+/// The specifications must consist of the same sequence of lexical elements,
+/// except that the mode and class of a parameter may be omitted. See IEEE
+/// 1076-2008 section 4.10.
+fn specs_conform(a: &ast::SubprogSpec, b: &ast::SubprogSpec) -> bool {
+    fn lexical(span: Span) -> String {
+        span.extract()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+    fn params(spec: &ast::SubprogSpec) -> Vec<(Name, String)> {
+        let mut params = Vec::new();
+        for decl in spec.params.iter().flat_map(|p| p.iter()) {
+            if let ast::IntfDecl::ObjDecl(ref decl) = *decl {
+                for name in &decl.names {
+                    params.push((name.name, lexical(decl.ty.span)));
+                }
+            }
+        }
+        params
+    }
+    a.kind == b.kind
+        && params(a) == params(b)
+        && a.retty.as_ref().map(|n| lexical(n.span)) == b.retty.as_ref().map(|n| lexical(n.span))
+}
@@ -59,7 +59,14 @@ make_arenas!(
         variable_decl: Decl<VarDecl>,
         file_decl: Decl<FileDecl>,
         type_decl2: Decl<Option<TypeData>>,
+        block_stmt: BlockStmt,
         process_stmt: ProcessStmt,
+        for_gen_stmt: ForGenStmt,
+        if_gen_stmt: IfGenStmt,
+        case_gen_stmt: CaseGenStmt,
+        gen_body: GenBody,
+        component: Component,
+        comp_inst_stmt: CompInstStmt,
         sig_assign_stmt: SigAssignStmt,
         array_type_index: Spanned<ArrayTypeIndex>,
         subprog: Subprog,
@@ -120,6 +127,19 @@ pub struct Entity {
     pub ports: Vec<IntfSignalRef>,
 }
 
+/// A component declaration.
+///
+/// See IEEE 1076-2008 section 6.8.
+#[derive(Debug)]
+pub struct Component {
+    /// The component name.
+    pub name: Spanned<Name>,
+    /// The list of generics that the component declares.
+    pub generics: Vec<GenericRef>,
+    /// The list of ports that the component declares.
+    pub ports: Vec<IntfSignalRef>,
+}
+
 #[derive(Debug)]
 pub struct Arch {
     /// The context items associated with the entity.
@@ -163,6 +183,8 @@ pub struct SubtypeInd {
     pub span: Span,
     /// The type mark.
     pub type_mark: Spanned<TypeMarkRef>,
+    /// The optional resolution function.
+    pub resolution: Option<Spanned<Def>>,
     /// The optional constraint.
     pub constraint: Option<Spanned<Constraint>>,
 }
@@ -292,7 +314,8 @@ impl From<Range> for DiscreteRange {
 /// ```
 #[derive(Debug)]
 pub enum Range {
-    // Attr(AttrRef),
+    /// A range attribute, e.g. `A'range` or `T'reverse_range`.
+    Attr(ExprRef),
     Immediate(Dir, ExprRef, ExprRef),
 }
 
@@ -353,7 +376,20 @@ pub struct PackageInst {
     /// The package to be instantiated.
     pub pkg: Spanned<LatentPkgRef>,
     /// The generic map.
-    pub generic_map: Vec<()>,
+    pub generic_map: Vec<GenericMapElem>,
+}
+
+/// An element of a generic map aspect.
+///
+/// See IEEE 1076-2008 section 6.5.7.2.
+#[derive(Clone, Debug)]
+pub struct GenericMapElem {
+    /// The span of the entire element.
+    pub span: Span,
+    /// The formal generic, if the element is associated by name.
+    pub formal: Option<Spanned<Name>>,
+    /// The actual associated with the generic.
+    pub actual: GenericMapRef,
 }
 
 #[derive(Debug)]
@@ -388,6 +424,11 @@ pub enum TypeData {
     File(Spanned<TypeMarkRef>),
     /// A record type.
     Record(Vec<(Spanned<Name>, SubtypeIndRef)>),
+    /// A protected type, with the declarations of its methods.
+    Protected(Vec<SubprogDeclRef>),
+    /// A protected type body, with the specifications of the subprograms it
+    /// implements.
+    ProtectedBody(Vec<SubprogDeclRef>),
 }
 
 /// An enumeration literal as listed in a type declaration.
@@ -454,21 +495,37 @@ pub enum ExprData {
     VarName(VarDeclRef),
     /// A resolved file name.
     FileName(FileDeclRef),
+    /// A resolved generate parameter name.
+    GenParamName(ForGenStmtRef),
+    /// A resolved loop parameter name.
+    LoopParamName(LoopStmtRef),
+    /// A resolved subprogram parameter name.
+    ParamName(IntfObjRef),
     /// An overloaded enum name.
     EnumName(Vec<Spanned<EnumRef>>),
-    /// An overloaded resolved name.
-    #[deprecated]
+    /// An overloaded subprogram name. Resolved to one of the definitions
+    /// when the call it appears in is type checked.
     OverloadedName(Vec<Spanned<Def>>),
     /// A selection, e.g. `a.b`.
     Select(ExprRef, Spanned<ResolvableName>),
-    /// An attribute selection, e.g. `a'b`.
-    Attr(ExprRef, Spanned<ResolvableName>),
+    /// An attribute selection with an optional argument, e.g. `a'b` or
+    /// `s'delayed(1 ns)`.
+    Attr(ExprRef, Spanned<ResolvableName>, Option<ExprRef>),
+    /// A predefined attribute of a type with an optional argument, e.g.
+    /// `T'image(x)`.
+    TypeAttr(
+        Spanned<TypeMarkRef>,
+        Spanned<ResolvableName>,
+        Option<ExprRef>,
+    ),
     /// A bit string literal.
     StringLiteral(Vec<(TypeDeclRef, Vec<usize>)>),
     /// An integer literal.
     IntegerLiteral(ConstInt),
     /// A float literal.
     FloatLiteral(ConstFloat),
+    /// A physical literal, e.g. `10 ns`.
+    PhysLiteral(BigInt, Spanned<UnitRef>),
     /// A unary operator expression.
     Unary(Spanned<UnaryOp>, Vec<Spanned<Def>>, ExprRef),
     /// A binary operator expression.
@@ -560,6 +617,33 @@ pub struct FileDecl {
     pub mode: Option<ExprRef>,
 }
 
+/// A block statement.
+///
+/// See IEEE 1076-2008 section 11.2.
+#[derive(Debug)]
+pub struct BlockStmt {
+    /// The scope within which the statement is declared.
+    pub parent: ScopeRef,
+    /// The span of the entire statement.
+    pub span: Span,
+    /// The optional statement label.
+    pub label: Option<Spanned<Name>>,
+    /// The optional guard condition.
+    pub guard: Option<ExprRef>,
+    /// The list of generics that the block declares.
+    pub generics: Vec<GenericRef>,
+    /// The generic map.
+    pub generic_map: Vec<GenericMapElem>,
+    /// The list of ports that the block declares.
+    pub ports: Vec<IntfSignalRef>,
+    /// The port map.
+    pub port_map: Spanned<AssocList>,
+    /// The declarations made before the `begin` keyword.
+    pub decls: Vec<DeclInBlockRef>,
+    /// The statements inside the block.
+    pub stmts: Vec<ConcStmtRef>,
+}
+
 /// A process statement.
 ///
 /// See IEEE 1076-2008 section 11.3.
@@ -579,6 +663,91 @@ pub struct ProcessStmt {
     pub stmts: Vec<SeqStmtRef>,
 }
 
+/// A component instantiation statement.
+///
+/// See IEEE 1076-2008 section 11.7.
+#[derive(Debug)]
+pub struct CompInstStmt {
+    /// The scope within which the statement is declared.
+    pub parent: ScopeRef,
+    /// The span of the entire statement.
+    pub span: Span,
+    /// The optional statement label.
+    pub label: Option<Spanned<Name>>,
+    /// The instantiated component.
+    pub comp: Spanned<CompDeclRef>,
+    /// The generic map.
+    pub generic_map: Vec<GenericMapElem>,
+    /// The port map.
+    pub port_map: Spanned<AssocList>,
+}
+
+/// A for-generate statement.
+///
+/// See IEEE 1076-2008 section 11.8.
+#[derive(Debug)]
+pub struct ForGenStmt {
+    /// The scope within which the statement is declared.
+    pub parent: ScopeRef,
+    /// The optional statement label.
+    pub label: Option<Spanned<Name>>,
+    /// The name of the generate parameter.
+    pub param: Spanned<Name>,
+    /// The discrete range the generate parameter iterates over.
+    pub range: Spanned<DiscreteRange>,
+    /// The declarations made before the `begin` keyword.
+    pub decls: Vec<DeclInBlockRef>,
+    /// The statements inside the generated region.
+    pub stmts: Vec<ConcStmtRef>,
+}
+
+/// An if-generate statement.
+///
+/// See IEEE 1076-2008 section 11.8.
+#[derive(Debug)]
+pub struct IfGenStmt {
+    /// The scope within which the statement is declared.
+    pub parent: ScopeRef,
+    /// The optional statement label.
+    pub label: Option<Spanned<Name>>,
+    /// The conditional bodies, represented as (cond, body) tuples.
+    pub conds: Vec<(ExprRef, GenBodyRef)>,
+    /// The optional `else` body.
+    pub alt: Option<GenBodyRef>,
+}
+
+/// A case-generate statement.
+///
+/// See IEEE 1076-2008 section 11.8.
+#[derive(Debug)]
+pub struct CaseGenStmt {
+    /// The scope within which the statement is declared.
+    pub parent: ScopeRef,
+    /// The span of the entire statement.
+    pub span: Span,
+    /// The optional statement label.
+    pub label: Option<Spanned<Name>>,
+    /// The expression being switched over.
+    pub switch: ExprRef,
+    /// The alternatives, represented as (choices, body) tuples.
+    pub cases: Vec<(Spanned<Choices>, GenBodyRef)>,
+}
+
+/// A generate statement body of an if-generate or case-generate statement.
+///
+/// See IEEE 1076-2008 section 11.8.
+#[derive(Debug)]
+pub struct GenBody {
+    /// The scope within which the generate statement is declared.
+    pub parent: ScopeRef,
+    /// The optional alternative label.
+    pub label: Option<Spanned<Name>>,
+    /// The declarations made before the `begin` keyword.
+    pub decls: Vec<DeclInBlockRef>,
+    /// The statements inside the generated region.
+    pub stmts: Vec<ConcStmtRef>,
+}
+
 /// A process sensitivity specification.
 ///
 /// See IEEE 1076-2008 section 11.3.
@@ -607,6 +776,8 @@ pub struct SigAssignStmt {
     pub target: SigAssignTarget,
     /// The location of the right hand side in the source file.
     pub target_span: Span,
+    /// Whether the assignment is guarded.
+    pub guarded: bool,
     /// The kind of the assignment.
     pub kind: SigAssignKind,
     /// The location of the right hand side in the source file.
@@ -616,8 +787,37 @@ pub struct SigAssignStmt {
 /// A signal assignment target.
 #[derive(Debug)]
 pub enum SigAssignTarget {
+    /// An entire signal.
     Name(SignalRef),
-    Aggregate,
+    /// An element of an array signal, e.g. `a(i)`.
+    Index(SignalRef, Vec<ExprRef>),
+    /// A slice of an array signal, e.g. `a(3 downto 0)`.
+    Slice(SignalRef, Spanned<DiscreteRange>),
+    /// An aggregate of targets, e.g. `(a, b)`. Each target receives one
+    /// element of the assigned value.
+    Aggregate(Vec<Spanned<SigAssignTarget>>),
+}
+
+impl SigAssignTarget {
+    /// The signal which is assigned, or `None` for aggregate targets.
+    pub fn signal(&self) -> Option<SignalRef> {
+        match *self {
+            SigAssignTarget::Name(sig)
+            | SigAssignTarget::Index(sig, _)
+            | SigAssignTarget::Slice(sig, _) => Some(sig),
+            SigAssignTarget::Aggregate(..) => None,
+        }
+    }
+
+    /// The signals which are assigned, in order of appearance.
+    pub fn signals(&self) -> Vec<SignalRef> {
+        match *self {
+            SigAssignTarget::Aggregate(ref elems) => {
+                elems.iter().flat_map(|elem| elem.value.signals()).collect()
+            }
+            _ => self.signal().into_iter().collect(),
+        }
+    }
 }
 
 /// A signal assignment kind.
@@ -743,7 +943,7 @@ pub struct SubprogInst {
     /// The subprogram to be instantiated.
     pub subprog: Spanned<LatentSubprogRef>,
     /// The generic map.
-    pub generic_map: Vec<()>,
+    pub generic_map: Vec<GenericMapElem>,
 }
 
 /// A subprogram specification.
@@ -759,7 +959,7 @@ pub struct SubprogSpec {
     /// The list of generics.
     pub generics: Vec<GenericRef>,
     /// The generic map.
-    pub generic_map: Vec<()>,
+    pub generic_map: Vec<GenericMapElem>,
     /// The subprogram parameters.
     pub params: Vec<IntfObjRef>,
     /// The return type.
@@ -860,10 +1060,10 @@ pub enum VarAssignKind {
 /// See IEEE 1076-2008 section 10.7.
 #[derive(Debug)]
 pub struct CallStmt {
-    /// The target subprogram.
-    pub subprog: SubprogRef,
-    /// The optional call parameters.
-    pub params: (),
+    /// The name of the called procedure.
+    pub callee: ExprRef,
+    /// The arguments of the call. Empty if the call has no parentheses.
+    pub args: Spanned<AssocList>,
 }
 
 /// An if statement.
@@ -1074,8 +1274,8 @@ pub type AssocList = Vec<AssocElement>;
 pub struct AssocElement {
     /// The span the element covers in the source file.
     pub span: Span,
-    /// The optional formal part.
-    pub formal: Option<Spanned<ExprRef>>,
+    /// The optional formal part. Only simple names are supported.
+    pub formal: Option<Spanned<Name>>,
     /// The actual part.
     pub actual: Spanned<AssocActual>,
 }
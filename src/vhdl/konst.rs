@@ -5,7 +5,8 @@
 pub use crate::hir::Dir;
 use crate::score::TypeDeclRef;
 use crate::ty::*;
-use num::BigInt;
+use num::{BigInt, BigRational, Signed, Zero};
+use std::cmp::Ordering;
 use std::fmt;
 
 /// A constant value.
@@ -14,6 +15,7 @@ pub enum Const {
     Null,
     Int(ConstInt),
     Float(ConstFloat),
+    Physical(ConstPhysical),
     Enum(ConstEnum),
     IntRange(ConstIntRange),
     FloatRange(ConstFloatRange),
@@ -25,6 +27,7 @@ impl Const {
             Const::Null => panic!("cannot negate null"),
             Const::Int(c) => Const::Int(c.negate()),
             Const::Float(c) => Const::Float(c.negate()),
+            Const::Physical(c) => Const::Physical(c.negate()),
             Const::Enum(_) => panic!("cannot negate enumeration literal"),
             Const::IntRange(_) => panic!("cannot negate integer range"),
             Const::FloatRange(_) => panic!("cannot negate float range"),
@@ -37,6 +40,7 @@ impl Const {
             Const::Null => "null",
             Const::Int(_) => "integer",
             Const::Float(_) => "float",
+            Const::Physical(_) => "physical",
             Const::Enum(_) => "enumeration literal",
             Const::IntRange(_) => "integer range",
             Const::FloatRange(_) => "float range",
@@ -56,6 +60,12 @@ impl From<ConstFloat> for Const {
     }
 }
 
+impl From<ConstPhysical> for Const {
+    fn from(k: ConstPhysical) -> Const {
+        Const::Physical(k)
+    }
+}
+
 impl From<ConstEnum> for Const {
     fn from(k: ConstEnum) -> Const {
         Const::Enum(k)
@@ -96,15 +106,122 @@ impl ConstInt {
     pub fn negate(self) -> ConstInt {
         ConstInt::new(self.ty, -self.value)
     }
+
+    /// Divide by another constant, truncating towards zero.
+    ///
+    /// Returns `None` if `rhs` is zero. See IEEE 1076-2008 section 9.2.7.
+    pub fn div(&self, rhs: &ConstInt) -> Option<BigInt> {
+        if rhs.value.is_zero() {
+            return None;
+        }
+        Some(&self.value / &rhs.value)
+    }
+
+    /// Compute the remainder of a division by another constant.
+    ///
+    /// The result has the sign of `self`, such that `a = (a/b)*b + (a rem b)`.
+    /// Returns `None` if `rhs` is zero. See IEEE 1076-2008 section 9.2.7.
+    pub fn rem(&self, rhs: &ConstInt) -> Option<BigInt> {
+        let quot = self.div(rhs)?;
+        Some(&self.value - quot * &rhs.value)
+    }
+
+    /// Compute the modulus of a division by another constant.
+    ///
+    /// The result has the sign of `rhs`, such that `a = b*n + (a mod b)` for
+    /// some integer `n`. Returns `None` if `rhs` is zero. See IEEE 1076-2008
+    /// section 9.2.7.
+    pub fn modulo(&self, rhs: &ConstInt) -> Option<BigInt> {
+        let rem = self.rem(rhs)?;
+        if !rem.is_zero() && rem.is_negative() != rhs.value.is_negative() {
+            Some(rem + &rhs.value)
+        } else {
+            Some(rem)
+        }
+    }
 }
 
 /// A constant float value.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ConstFloat {}
+pub struct ConstFloat {
+    /// The exact value, as written in the source.
+    pub value: BigRational,
+}
 
 impl ConstFloat {
+    /// Create a new constant float.
+    pub fn new(value: BigRational) -> ConstFloat {
+        ConstFloat { value: value }
+    }
+
     pub fn negate(self) -> ConstFloat {
-        ConstFloat {}
+        ConstFloat::new(-self.value)
+    }
+}
+
+/// A constant physical value.
+///
+/// The value is kept in the unit it was written in, together with the scale of
+/// that unit. Comparisons normalize both sides to the primary unit first, such
+/// that `1 us` equals `1000 ns`. See IEEE 1076-2008 section 5.2.4.
+#[derive(Debug, Clone)]
+pub struct ConstPhysical {
+    /// The declaration of the physical type.
+    pub decl: TypeDeclRef,
+    /// The value as a multiple of `unit`.
+    pub value: BigInt,
+    /// The index of the unit in the physical type.
+    pub unit: usize,
+    /// The number of primary units in one `unit`.
+    pub primary_multiple: BigInt,
+}
+
+impl ConstPhysical {
+    /// Create a new constant physical value.
+    pub fn new(
+        decl: TypeDeclRef,
+        value: BigInt,
+        unit: usize,
+        primary_multiple: BigInt,
+    ) -> ConstPhysical {
+        ConstPhysical {
+            decl: decl,
+            value: value,
+            unit: unit,
+            primary_multiple: primary_multiple,
+        }
+    }
+
+    /// The value as a multiple of the primary unit.
+    pub fn normalized(&self) -> BigInt {
+        &self.value * &self.primary_multiple
+    }
+
+    pub fn negate(self) -> ConstPhysical {
+        ConstPhysical {
+            value: -self.value,
+            ..self
+        }
+    }
+}
+
+impl PartialEq for ConstPhysical {
+    fn eq(&self, other: &ConstPhysical) -> bool {
+        self.decl == other.decl && self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for ConstPhysical {}
+
+impl PartialOrd for ConstPhysical {
+    /// Compare two values of the same physical type. Returns `None` for values
+    /// of different physical types.
+    fn partial_cmp(&self, other: &ConstPhysical) -> Option<Ordering> {
+        if self.decl == other.decl {
+            Some(self.normalized().cmp(&other.normalized()))
+        } else {
+            None
+        }
     }
 }
 
@@ -160,6 +277,7 @@ impl fmt::Display for Const {
             Const::Null => write!(f, "null"),
             Const::Int(ref k) => k.fmt(f),
             Const::Float(ref k) => k.fmt(f),
+            Const::Physical(ref k) => k.fmt(f),
             Const::Enum(ref k) => k.fmt(f),
             Const::IntRange(ref k) => k.fmt(f),
             Const::FloatRange(ref k) => k.fmt(f),
@@ -173,6 +291,12 @@ impl fmt::Display for ConstInt {
     }
 }
 
+impl fmt::Display for ConstPhysical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <unit {}>", self.value, self.unit)
+    }
+}
+
 impl fmt::Display for ConstEnum {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "<enum>")
@@ -181,7 +305,7 @@ impl fmt::Display for ConstEnum {
 
 impl fmt::Display for ConstFloat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<float>")
+        write!(f, "{:?}", rational_to_f64(&self.value))
     }
 }
 
@@ -193,3 +317,58 @@ where
         write!(f, "{} {} {}", self.left_bound, self.dir, self.right_bound)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::score::NodeRef;
+
+    fn int(value: i64) -> ConstInt {
+        ConstInt::new(None, value.into())
+    }
+
+    fn apply(f: fn(&ConstInt, &ConstInt) -> Option<BigInt>, a: i64, b: i64) -> Option<BigInt> {
+        f(&int(a), &int(b))
+    }
+
+    #[test]
+    fn rem_sign_follows_left_operand() {
+        assert_eq!(apply(ConstInt::rem, 7, 3), Some(1.into()));
+        assert_eq!(apply(ConstInt::rem, -7, 3), Some((-1).into()));
+        assert_eq!(apply(ConstInt::rem, 7, -3), Some(1.into()));
+        assert_eq!(apply(ConstInt::rem, -7, -3), Some((-1).into()));
+        assert_eq!(apply(ConstInt::rem, 7, 0), None);
+    }
+
+    #[test]
+    fn mod_sign_follows_right_operand() {
+        assert_eq!(apply(ConstInt::modulo, 7, 3), Some(1.into()));
+        assert_eq!(apply(ConstInt::modulo, -7, 3), Some(2.into()));
+        assert_eq!(apply(ConstInt::modulo, 7, -3), Some((-2).into()));
+        assert_eq!(apply(ConstInt::modulo, -7, -3), Some((-1).into()));
+        assert_eq!(apply(ConstInt::modulo, 6, -3), Some(0.into()));
+        assert_eq!(apply(ConstInt::modulo, 7, 0), None);
+    }
+
+    #[test]
+    fn physical_values_normalize() {
+        let time = TypeDeclRef::alloc();
+        let us = ConstPhysical::new(time, 1.into(), 2, 1000.into());
+        let ns = |v: i64| ConstPhysical::new(time, v.into(), 1, 1.into());
+        assert!(us > ns(500));
+        assert!(ns(500) < us);
+        assert_eq!(us, ns(1000));
+        assert!(us < ns(1001));
+        let other = ConstPhysical::new(TypeDeclRef::alloc(), 1.into(), 2, 1000.into());
+        assert_ne!(us, other);
+        assert_eq!(us.partial_cmp(&other), None);
+        assert_eq!(us.negate().normalized(), BigInt::from(-1000));
+    }
+
+    #[test]
+    fn div_truncates_towards_zero() {
+        assert_eq!(apply(ConstInt::div, -7, 2), Some((-3).into()));
+        assert_eq!(apply(ConstInt::div, 7, -2), Some((-3).into()));
+        assert_eq!(apply(ConstInt::div, 7, 0), None);
+    }
+}
@@ -2,17 +2,21 @@
 
 //! Expressions
 
+use std::cmp::max;
 use std::collections::HashMap;
 
 use crate::common::errors::*;
-use crate::common::name::Name;
+use crate::common::name::{get_name_table, Name};
 use crate::common::score::Result;
 use crate::common::source::{Span, Spanned};
 use crate::common::Verbosity;
 
+use num::{BigInt, One, Zero};
+
 use crate::add_ctx::AddContext;
 use crate::hir;
 use crate::make_ctx::MakeContext;
+use crate::op::BinaryOp;
 use crate::overload_resolver::*;
 use crate::score::*;
 use crate::syntax::ast;
@@ -187,9 +191,15 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
 ) -> Result<&'ctx Ty> {
     match hir.data {
         hir::ExprData::ConstName(id) => tyc.ctx.lazy_typeval(id),
-        hir::ExprData::SignalName(id) => tyc.ctx.ty(id),
+        hir::ExprData::SignalName(id) => {
+            tyc.ctx.record_signal_read(id);
+            tyc.ctx.ty(id)
+        }
         hir::ExprData::VarName(id) => tyc.ctx.lazy_typeval(id),
         hir::ExprData::FileName(id) => tyc.ctx.lazy_typeval(id),
+        hir::ExprData::GenParamName(id) => tyc.ctx.lazy_typeval(id),
+        hir::ExprData::LoopParamName(id) => tyc.ctx.lazy_typeval(id),
+        hir::ExprData::ParamName(id) => tyc.ctx.ty(id),
         hir::ExprData::EnumName(ref defs) => {
             // Enums are generally overloaded. The type context is needed to
             // pick one of the available variants.
@@ -239,15 +249,18 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
                     Box::new(EnumTy::new(defs[0].0).into()),
                 )))
             } else {
+                let mut decl = None;
                 let (index_ty, filtered): (Option<_>, Vec<_>) = if let Some(tyctx) = tyctx {
                     let tyctx_flat = tyc.ctx.deref_named_type(tyctx)?;
                     match *tyctx_flat {
                         Ty::Array(ref at) if at.indices.len() == 1 => {
-                            let index_ty = match *at.indices[0].ty() {
+                            let index_ty = match *tyc.ctx.deref_named_type(at.indices[0].ty())? {
                                 Ty::Int(ref it) => Some(it.clone()),
                                 _ => None,
                             };
-                            match *at.element.as_ref() {
+                            decl = at.decl;
+                            let element = tyctx.element_type(tyc.ctx)?.unwrap();
+                            match *tyc.ctx.deref_named_type(element)? {
                                 Ty::Enum(ref et) => (
                                     index_ty,
                                     defs.iter()
@@ -268,9 +281,21 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
                 } else {
                     (None, vec![])
                 };
-                let index_ty = index_ty
-                    .unwrap_or_else(|| IntTy::new(Dir::To, 0.into(), (defs[0].1.len() - 1).into()))
-                    .into();
+                // The literal starts at the left bound of the context's index
+                // subtype, but its length is the number of its characters.
+                // See IEEE 1076-2008 section 9.3.2.
+                let len = BigInt::from(defs[0].1.len()) - BigInt::one();
+                let index_ty = match index_ty {
+                    Some(it) => {
+                        let right = match it.dir {
+                            Dir::To => &it.left_bound + len,
+                            Dir::Downto => &it.left_bound - len,
+                        };
+                        IntTy::new(it.dir, it.left_bound, right)
+                    }
+                    None => IntTy::new(Dir::To, 0.into(), len),
+                }
+                .into();
                 if filtered.len() != 1 {
                     tyc.emit(
                         DiagBuilder2::error(format!("`{}` is ambiguous", hir.span.extract()))
@@ -279,19 +304,31 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
                     return Err(());
                 } else {
                     let index = ArrayIndex::Constrained(Box::new(index_ty));
-                    Ok(tyc.ctx.intern_ty(ArrayTy::new(
-                        vec![index],
-                        Box::new(EnumTy::new(filtered[0]).into()),
-                    )))
+                    Ok(tyc.ctx.intern_ty(
+                        ArrayTy::new(vec![index], Box::new(EnumTy::new(filtered[0]).into()))
+                            .with_decl(decl),
+                    ))
                 }
             }
         }
         hir::ExprData::IntegerLiteral(ref value) => {
             if let Some(ref ty) = value.ty {
                 return Ok(tyc.ctx.intern_ty(ty.clone()));
-            } else {
-                return Ok(tyc.ctx.intern_ty(Ty::UniversalInt));
             }
+            // Without an explicit type, the literal assumes the narrowest
+            // integer type offered by the context that can hold it.
+            let candidates = match tyctx {
+                Some(tyctx) => match *tyc.ctx.deref_named_type(tyctx)? {
+                    Ty::Int(_) => vec![tyctx],
+                    _ => vec![],
+                },
+                None => vec![],
+            };
+            let origin = tyc
+                .ctx
+                .type_context_origin(expr_id)
+                .and_then(|id| tyc.ctx.span(id));
+            return tyc.typeval_int_literal(&value.value, &candidates, hir.span, origin);
             // if let Some(ty) = tyctx {
             //     if let &Ty::Int(_) = tyc.ctx.deref_named_type(ty)? {
             //         return Ok(ty);
@@ -303,22 +340,16 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
             // );
             // Err(())
         }
+        hir::ExprData::PhysLiteral(_, unit) => tyc.ctx.ty(unit.value.0),
         hir::ExprData::Qualified(ref tm, expr) => {
-            let ty = tyc.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value));
+            let ty = tyc.ctx.intern_named_ty(tm.value);
             let expr_ty = tyc.lazy_typeval(expr)?;
             tyc.must_match(ty, expr_ty, tyc.ctx.span(expr).unwrap());
             Ok(ty)
         }
-        hir::ExprData::Allocator(ref tm, expr) => {
-            let ty = tyc.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value));
-            if let Some(expr) = expr {
-                let expr_ty = tyc.lazy_typeval(expr)?;
-                tyc.must_match(ty, expr_ty, tyc.ctx.span(expr).unwrap());
-            }
-            Ok(ty)
-        }
+        hir::ExprData::Allocator(ref tm, expr) => typeval_allocator(tyc, hir, tm, expr, tyctx),
         hir::ExprData::Cast(ref tm, expr) => {
-            let ty = tyc.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value));
+            let ty = tyc.ctx.intern_named_ty(tm.value);
             let expr_ty = tyc.lazy_typeval(expr)?;
             tyc.must_cast(ty, expr_ty, tyc.ctx.span(expr).unwrap());
             Ok(ty)
@@ -329,74 +360,1151 @@ pub fn typeval_expr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
             tyc.ctx.lazy_typeval(id)
         }
         hir::ExprData::Unary(op, ref defs, arg) => {
+            let arg_ty = tyc.lazy_typeval(arg)?;
+
             // Assemble an overload resolution requirement based on the
             // operator's types.
             let req = OverloadReq::Subprog(SignatureReq {
-                return_type: match tyctx {
+                return_type: Some(match tyctx {
                     Some(tyctx) => TypeReq::One(tyctx),
                     None => TypeReq::Any,
-                },
-                positional: vec![TypeReq::One(tyc.lazy_typeval(arg)?)],
+                }),
+                positional: vec![TypeReq::One(arg_ty)],
                 named: HashMap::new(),
             });
 
             // Resolve the overload.
             let def = resolve_overloads(tyc.ctx, defs, &req, hir.span)?;
             debugln!("unary operator `{}` resolved to {:?}", op.value, def);
+            let ret = operator_return_type(tyc, def.value, op.map(|op| op.to_string()))?;
 
-            tyc.emit(
-                DiagBuilder2::bug(format!(
-                    "typeval for unary operation `{}` not implemented",
-                    hir.span.extract()
-                ))
-                .span(hir.span),
-            );
-            debugln!("Defs are {:?}", defs);
-            Err(())
+            // The numeric_std operators return an array of the size of the
+            // operand.
+            if let Def::Subprog(id) = def.value {
+                let (scope, _) = tyc.ctx.ast(id);
+                if is_numeric_pkg(tyc, scope) {
+                    if let Some(len) = array_len(tyc, arg_ty)? {
+                        return numeric_result_ty(tyc, ret, len);
+                    }
+                }
+            }
+            Ok(tyc.ctx.intern_ty(ret.clone()))
+        }
+        hir::ExprData::Binary(op, ref defs, lhs, rhs)
+            if op.value == BinaryOp::Concat
+                && defs.iter().all(|d| match d.value {
+                    Def::BuiltinOp(_) => true,
+                    _ => false,
+                }) =>
+        {
+            typeval_concat(tyc, hir, lhs, rhs, tyctx)
         }
         hir::ExprData::Binary(op, ref defs, lhs, rhs) => {
+            let (lhs_ty, rhs_ty) = typeval_operands(tyc, lhs, rhs)?;
+            check_numeric_operands(tyc, hir, lhs_ty, rhs_ty)?;
+
             // Assemble an overload resolution requirement based on the
             // operator's types.
             let req = OverloadReq::Subprog(SignatureReq {
-                return_type: match tyctx {
+                return_type: Some(match tyctx {
                     Some(tyctx) => TypeReq::One(tyctx),
                     None => TypeReq::Any,
-                },
-                positional: vec![
-                    TypeReq::One(tyc.lazy_typeval(lhs)?),
-                    TypeReq::One(tyc.lazy_typeval(rhs)?),
-                ],
+                }),
+                positional: vec![TypeReq::One(lhs_ty), TypeReq::One(rhs_ty)],
                 named: HashMap::new(),
             });
 
             // Resolve the overload.
             let def = resolve_overloads(tyc.ctx, defs, &req, hir.span)?;
             debugln!("binary operator `{}` resolved to {:?}", op.value, def);
+            let ret = operator_return_type(tyc, def.value, op.map(|op| op.to_string()))?;
 
+            // The numeric_std operators return an array whose size depends on
+            // the size of the operands.
+            if let Def::Subprog(id) = def.value {
+                let (scope, _) = tyc.ctx.ast(id);
+                if is_numeric_pkg(tyc, scope) {
+                    if let Some(len) = numeric_result_len(tyc, op.value, lhs_ty, rhs_ty)? {
+                        return numeric_result_ty(tyc, ret, len);
+                    }
+                }
+            }
+            Ok(tyc.ctx.intern_ty(ret.clone()))
+        }
+        hir::ExprData::Select(prefix, name) => typeval_select(tyc, hir, prefix, name),
+        hir::ExprData::Call(callee, ref args) => typeval_call(tyc, hir, callee, args, tyctx),
+        hir::ExprData::TypeAttr(ref tm, ref name, arg) => {
+            typeval_type_attr(tyc, hir, tm, name, arg)
+        }
+        hir::ExprData::Attr(prefix, ref name, arg) => typeval_attr(tyc, hir, prefix, name, arg),
+        hir::ExprData::Range(dir, lb, rb) => tyc.typeval_range(dir, lb, rb, hir.span),
+        _ => {
             tyc.emit(
                 DiagBuilder2::bug(format!(
-                    "typeval for binary operation `{}` not implemented",
+                    "typeval for expression `{}` not implemented",
                     hir.span.extract()
                 ))
                 .span(hir.span),
             );
-            debugln!("Defs are {:?}", defs);
+            debugln!("It is a {:#?}", hir.data);
             Err(())
         }
+    }
+}
+
+/// Evaluate the types of the operands of a binary operator.
+///
+/// An overloaded literal such as `'1'` cannot determine its type on its own.
+/// If one operand is such a literal, it assumes the type of the other operand,
+/// since the predefined operators act on operands of the same type. See IEEE
+/// 1076-2008 section 9.2.
+fn typeval_operands<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    lhs: ExprRef,
+    rhs: ExprRef,
+) -> Result<(&'ctx Ty, &'ctx Ty)> {
+    let is_overloaded = |id: ExprRef| -> Result<bool> {
+        Ok(match tyc.ctx.lazy_hir(id)?.data {
+            hir::ExprData::EnumName(ref defs) => defs.len() > 1,
+            hir::ExprData::StringLiteral(ref defs) => defs.len() > 1,
+            _ => false,
+        })
+    };
+    if is_overloaded(lhs)? && !is_overloaded(rhs)? {
+        let rhs_ty = tyc.lazy_typeval(rhs)?;
+        tyc.ctx.set_type_context(lhs, rhs_ty);
+        return Ok((tyc.lazy_typeval(lhs)?, rhs_ty));
+    }
+    let lhs_ty = tyc.lazy_typeval(lhs)?;
+    if is_overloaded(rhs)? {
+        tyc.ctx.set_type_context(rhs, lhs_ty);
+    }
+    Ok((lhs_ty, tyc.lazy_typeval(rhs)?))
+}
+
+/// Evaluate the type of an allocator, i.e. `new T` or `new T'(value)`.
+///
+/// The allocator creates an object of type `T` and yields an access value
+/// designating it. The context must expect an access type designating `T`. In
+/// absence of a context, the result is an anonymous access type. See IEEE
+/// 1076-2008 section 9.3.7.
+fn typeval_allocator<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    tm: &Spanned<TypeMarkRef>,
+    expr: Option<ExprRef>,
+    tyctx: Option<&'ctx Ty>,
+) -> Result<&'ctx Ty> {
+    let ty = tyc.ctx.intern_named_ty(tm.value);
+    if let Some(expr) = expr {
+        let expr_ty = tyc.lazy_typeval(expr)?;
+        if !tyc.must_match(ty, expr_ty, tyc.ctx.span(expr).unwrap()) {
+            return Err(());
+        }
+    } else if !tyc.is_constrained(ty)? {
+        tyc.emit(
+            DiagBuilder2::error(format!(
+                "allocator `{}` requires a constrained subtype",
+                hir.span.extract()
+            ))
+            .span(hir.span)
+            .add_note(format!("type {} is not fully constrained", ty))
+            .add_note(format!(
+                "Provide an initial value from which the constraint can be inferred, e.g. \
+                 `new {}'(...)`.",
+                tm.span.extract()
+            )),
+        );
+        return Err(());
+    }
+    match tyctx {
+        Some(tyctx) => match *tyc.ctx.deref_named_type(tyctx)? {
+            Ty::Access(ref designated) if are_types_matching(tyc.ctx, designated, ty) => Ok(tyctx),
+            _ => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "allocator `{}` yields a value of type access {}, but {} is expected",
+                        hir.span.extract(),
+                        ty,
+                        tyctx
+                    ))
+                    .span(hir.span),
+                );
+                Err(())
+            }
+        },
+        None => Ok(tyc.ctx.intern_ty(Ty::Access(Box::new(ty.clone())))),
+    }
+}
+
+/// Evaluate the type of a function call.
+///
+/// The overloads of the callee are first pruned by the type the context
+/// expects of the call, and only then by the types of the arguments.
+fn typeval_call<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    callee: ExprRef,
+    args: &Spanned<hir::AssocList>,
+    tyctx: Option<&'ctx Ty>,
+) -> Result<&'ctx Ty> {
+    let callee_hir = tyc.ctx.lazy_hir(callee)?;
+    let defs = match callee_hir.data {
+        hir::ExprData::OverloadedName(ref defs) => defs,
+        _ => return typeval_index(tyc, hir, callee, args),
+    };
+    let ret = match tyctx {
+        Some(tyctx) => TypeReq::One(tyctx),
+        None => TypeReq::Any,
+    };
+    let sig = typeck_call(tyc, callee_hir.span, defs, args, Some(ret))?;
+    match sig.ret {
+        Some(ref ret) => Ok(tyc.ctx.intern_ty(ret.as_ref().clone())),
+        None => {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is a procedure and returns no value",
+                    callee_hir.span.extract()
+                ))
+                .span(hir.span),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Resolve the subprogram called with a list of arguments, and check the
+/// arguments against its parameters.
+///
+/// The `ret` is the required return type of the subprogram, or `None` for a
+/// procedure call. Arguments whose type depends on their context, such as
+/// enum literals, accept any parameter type during resolution and are checked
+/// against the chosen parameter afterwards. Named arguments are matched
+/// against the parameters by name. Returns the signature of the subprogram.
+pub fn typeck_call<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    span: Span,
+    defs: &[Spanned<Def>],
+    args: &Spanned<hir::AssocList>,
+    ret: Option<TypeReq<'ctx>>,
+) -> Result<&'ctx SubprogTy> {
+    // Gather the positional and named arguments. Named arguments must follow
+    // all positional ones. See IEEE 1076-2008 section 6.5.7.1.
+    let mut actuals = vec![];
+    let mut named: Vec<(Spanned<Name>, ExprRef)> = vec![];
+    for elem in &args.value {
+        match (elem.formal, &elem.actual.value) {
+            (None, &hir::AssocActual::Expr(_)) if !named.is_empty() => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "positional argument `{}` follows a named argument",
+                        elem.span.extract()
+                    ))
+                    .span(elem.span),
+                );
+                return Err(());
+            }
+            (None, &hir::AssocActual::Expr(id)) => actuals.push(id),
+            (Some(formal), &hir::AssocActual::Expr(id)) => {
+                if let Some(&(prev, _)) = named.iter().find(|&&(n, _)| n.value == formal.value) {
+                    tyc.emit(
+                        DiagBuilder2::error(format!(
+                            "formal `{}` is associated more than once",
+                            formal.value
+                        ))
+                        .span(formal.span)
+                        .add_note("previous association was here:")
+                        .span(prev.span),
+                    );
+                    return Err(());
+                }
+                named.push((formal, id));
+            }
+            _ => {
+                tyc.emit(
+                    DiagBuilder2::bug(format!(
+                        "argument `{}` of subprogram call not implemented",
+                        elem.span.extract()
+                    ))
+                    .span(elem.span),
+                );
+                return Err(());
+            }
+        }
+    }
+
+    // Assemble an overload resolution requirement based on the required
+    // return type and the arguments' types.
+    let arg_req = |arg: ExprRef| -> Result<TypeReq<'ctx>> {
+        Ok(match tyc.ctx.lazy_hir(arg)?.data {
+            hir::ExprData::EnumName(..)
+            | hir::ExprData::StringLiteral(..)
+            | hir::ExprData::Aggregate(..)
+            | hir::ExprData::IntegerLiteral(..) => TypeReq::Any,
+            _ => TypeReq::One(tyc.lazy_typeval(arg)?),
+        })
+    };
+    let mut positional = vec![];
+    for &arg in &actuals {
+        positional.push(arg_req(arg)?);
+    }
+    let mut named_req = HashMap::new();
+    for &(formal, arg) in &named {
+        named_req.insert(formal.value, arg_req(arg)?);
+    }
+    let req = OverloadReq::Subprog(SignatureReq {
+        return_type: ret,
+        positional: positional,
+        named: named_req,
+    });
+
+    // Resolve the overload.
+    let def = resolve_overloads(tyc.ctx, defs, &req, span)?;
+    debugln!("call `{}` resolved to {:?}", span.extract(), def);
+    let sig = match def.value {
+        Def::BuiltinOp(id) => tyc.lazy_typeval(id)?,
+        Def::Subprog(id) => tyc.lazy_typeval(id)?,
+        Def::Alias(id) => tyc.ctx.ty(id)?,
+        _ => unreachable!(),
+    };
+    let sig = match *sig {
+        Ty::Subprog(ref sig) => sig,
+        _ => unreachable!(),
+    };
+
+    // Check the arguments against the parameters of the chosen overload. The
+    // overload only matches if every named argument has a parameter.
+    let mut result = Ok(());
+    for (&arg, param) in actuals.iter().zip(sig.args.iter()) {
+        let param_ty = tyc.ctx.intern_ty(param.ty.clone());
+        result = result.and(tyc.typeck_assigned_value(arg, param_ty));
+    }
+    for &(formal, arg) in &named {
+        let param = sig
+            .args
+            .iter()
+            .find(|p| p.name == Some(formal.value))
+            .unwrap();
+        let param_ty = tyc.ctx.intern_ty(param.ty.clone());
+        result = result.and(tyc.typeck_assigned_value(arg, param_ty));
+    }
+    result?;
+    Ok(sig)
+}
+
+/// Evaluate the type of an indexed name, e.g. `m(i, j)`.
+///
+/// The prefix must be an array, and there must be one index expression per
+/// dimension, each of which is checked against the corresponding index type.
+/// See IEEE 1076-2008 section 8.4.
+fn typeval_index<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    prefix: ExprRef,
+    args: &Spanned<hir::AssocList>,
+) -> Result<&'ctx Ty> {
+    let prefix_span = tyc.ctx.span(prefix).unwrap();
+    let prefix_ty = tyc.lazy_typeval(prefix)?;
+    let at = match *tyc.ctx.deref_named_type(prefix_ty)? {
+        Ty::Array(ref at) => at,
+        _ => {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is neither a function nor an array",
+                    prefix_span.extract()
+                ))
+                .span(prefix_span)
+                .add_note(format!(
+                    "`{}` has type {}.",
+                    prefix_span.extract(),
+                    prefix_ty
+                )),
+            );
+            return Err(());
+        }
+    };
+
+    // Gather the index expressions.
+    let mut indices = vec![];
+    for elem in &args.value {
+        match (&elem.formal, &elem.actual.value) {
+            (None, &hir::AssocActual::Expr(id)) => match tyc.ctx.lazy_hir(id)?.data {
+                hir::ExprData::Range(dir, lb, rb)
+                    if args.value.len() == 1 && at.indices.len() == 1 =>
+                {
+                    return typeval_slice(tyc, at, id, dir, lb, rb);
+                }
+                hir::ExprData::Range(..) => {
+                    tyc.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` slices an array with {} dimensions",
+                            hir.span.extract(),
+                            at.indices.len()
+                        ))
+                        .span(elem.span)
+                        .add_note("Only one-dimensional arrays can be sliced."),
+                    );
+                    return Err(());
+                }
+                _ => indices.push(id),
+            },
+            _ => {
+                tyc.emit(
+                    DiagBuilder2::error(format!("`{}` is not a valid index", elem.span.extract()))
+                        .span(elem.span),
+                );
+                return Err(());
+            }
+        }
+    }
+    if indices.len() != at.indices.len() {
+        tyc.emit(
+            DiagBuilder2::error(format!(
+                "`{}` has {} indices, but the array has {} dimensions",
+                hir.span.extract(),
+                indices.len(),
+                at.indices.len()
+            ))
+            .span(hir.span)
+            .add_note(format!(
+                "`{}` has type {}.",
+                prefix_span.extract(),
+                prefix_ty
+            )),
+        );
+        return Err(());
+    }
+
+    // Check each index against the index type of its dimension.
+    let mut result = Ok(());
+    for (&index, dim) in indices.iter().zip(at.indices.iter()) {
+        let index_ty = tyc.ctx.intern_ty(dim.ty().clone());
+        result = result.and(tyc.typeck_assigned_value(index, index_ty));
+    }
+    result?;
+    Ok(tyc.ctx.intern_ty((*at.element).clone()))
+}
+
+/// Evaluate the type of a slice name, e.g. `a(3 downto 0)`.
+///
+/// The bounds must be of the index type of the one-dimensional prefix. The
+/// result is the prefix type constrained to the range if its bounds are
+/// static, or to the index subtype of the prefix otherwise. See IEEE 1076-2008
+/// section 8.5.
+fn typeval_slice<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    at: &ArrayTy,
+    range: ExprRef,
+    dir: Dir,
+    lb: ExprRef,
+    rb: ExprRef,
+) -> Result<&'ctx Ty> {
+    let index_ty = tyc.ctx.intern_ty(at.indices[0].ty().clone());
+    let lb_result = tyc.typeck_assigned_value(lb, index_ty);
+    let rb_result = tyc.typeck_assigned_value(rb, index_ty);
+    lb_result.and(rb_result)?;
+    let index = match *tyc.lazy_typeval(range)? {
+        Ty::Range(RangeTy {
+            static_bounds: Some((ref l, ref r)),
+            ..
+        }) => IntTy::new(dir, l.clone(), r.clone()).into(),
+        _ => index_ty.clone(),
+    };
+    Ok(tyc.ctx.intern_ty(
+        ArrayTy::new(
+            vec![ArrayIndex::Constrained(Box::new(index))],
+            at.element.clone(),
+        )
+        .with_decl(at.decl),
+    ))
+}
+
+/// Evaluate the type of a selected name which denotes a record element, e.g.
+/// `r.x`.
+///
+/// See IEEE 1076-2008 section 8.3.
+fn typeval_select<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    prefix: ExprRef,
+    name: Spanned<ResolvableName>,
+) -> Result<&'ctx Ty> {
+    let prefix_span = tyc.ctx.span(prefix).unwrap();
+    let prefix_ty = tyc.lazy_typeval(prefix)?;
+    let rt = match *tyc.ctx.deref_named_type(prefix_ty)? {
+        Ty::Record(ref rt) => rt,
+        _ => {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` selects an element of `{}`, which is not a record",
+                    hir.span.extract(),
+                    prefix_span.extract()
+                ))
+                .span(hir.span)
+                .add_note(format!(
+                    "`{}` has type {}.",
+                    prefix_span.extract(),
+                    prefix_ty
+                )),
+            );
+            return Err(());
+        }
+    };
+    let field = match name.value {
+        ResolvableName::Ident(n) => rt.lookup.get(&n).map(|&i| &rt.fields[i].1),
+        _ => None,
+    };
+    match field {
+        Some(ty) => Ok(tyc.ctx.intern_ty(ty.as_ref().clone())),
+        None => {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` has no element `{}`",
+                    prefix_span.extract(),
+                    name.value
+                ))
+                .span(name.span)
+                .add_note(format!(
+                    "`{}` has type {}.",
+                    prefix_span.extract(),
+                    prefix_ty
+                )),
+            );
+            Err(())
+        }
+    }
+}
+
+/// Evaluate the type of a predefined attribute of a type.
+///
+/// See IEEE 1076-2008 section 16.2.2.
+fn typeval_type_attr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    tm: &Spanned<TypeMarkRef>,
+    name: &Spanned<ResolvableName>,
+    arg: Option<ExprRef>,
+) -> Result<&'ctx Ty> {
+    let table = get_name_table();
+    let is_image = name.value == table.intern("IMAGE", false).into();
+    let is_value = name.value == table.intern("VALUE", false).into();
+    let is_bound = ["LEFT", "RIGHT", "HIGH", "LOW"]
+        .iter()
+        .any(|n| name.value == table.intern(n, false).into());
+    let is_reverse = name.value == table.intern("REVERSE_RANGE", false).into();
+    let is_range = is_reverse || name.value == table.intern("RANGE", false).into();
+    let is_length = name.value == table.intern("LENGTH", false).into();
+    let is_ascending = name.value == table.intern("ASCENDING", false).into();
+    if !is_image && !is_value && !is_bound && !is_range && !is_length && !is_ascending {
+        tyc.emit(
+            DiagBuilder2::bug(format!("attribute `{}` not implemented", name.value))
+                .span(name.span),
+        );
+        return Err(());
+    }
+
+    // The bound, range, and length attributes of a constrained array type
+    // refer to its first index. All other attributes are only defined for
+    // scalar types. See IEEE 1076-2008 section 16.2.3.
+    let ty = tyc.ctx.intern_named_ty(tm.value);
+    let ty = match *tyc.ctx.deref_named_type(ty)? {
+        Ty::Array(ref at) if !is_image && !is_value => match at.indices[0] {
+            ArrayIndex::Constrained(ref index) => tyc.ctx.intern_ty((**index).clone()),
+            ArrayIndex::Unbounded(..) => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` requires a constrained array prefix, but `{}` is unconstrained",
+                        hir.span.extract(),
+                        tm.span.extract()
+                    ))
+                    .span(tm.span)
+                    .add_note("See IEEE 1076-2008 section 16.2.3."),
+                );
+                return Err(());
+            }
+        },
+        _ if is_length || !ty.is_scalar(tyc.ctx)? => {
+            let what = if is_length { "an array" } else { "a scalar" };
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` requires {} prefix, but `{}` is {}",
+                    hir.span.extract(),
+                    what,
+                    tm.span.extract(),
+                    tyc.ctx.deref_named_type(ty)?.kind_desc()
+                ))
+                .span(tm.span)
+                .add_note("See IEEE 1076-2008 section 16.2."),
+            );
+            return Err(());
+        }
+        _ => ty,
+    };
+
+    // `T'left`, `T'right`, `T'high`, and `T'low` are values of type `T`, and
+    // `T'range` and `T'reverse_range` are ranges of `T`. `A'length` is a
+    // universal integer, and `T'ascending` is a boolean.
+    if is_bound || is_range || is_length || is_ascending {
+        if let Some(arg) = arg {
+            tyc.emit(
+                DiagBuilder2::error(format!("`'{}` takes no argument", name.value))
+                    .span(tyc.ctx.span(arg).unwrap()),
+            );
+            return Err(());
+        }
+    }
+    if is_bound {
+        return Ok(ty);
+    }
+    if is_length {
+        return Ok(tyc.ctx.intern_ty(Ty::UniversalInt));
+    }
+    if is_ascending {
+        return Ok(tyc.ctx.intern_ty(BOOLEAN_TYPE.named_ty()));
+    }
+    if is_range {
+        return range_attr_ty(tyc, ty, is_reverse);
+    }
+
+    let arg = match arg {
+        Some(arg) => arg,
+        None => {
+            tyc.emit(
+                DiagBuilder2::error(format!("`{}` requires an argument", hir.span.extract()))
+                    .span(hir.span),
+            );
+            return Err(());
+        }
+    };
+    let arg_ty = tyc.lazy_typeval(arg)?;
+    let arg_span = tyc.ctx.span(arg).unwrap();
+
+    // `T'image(x)` maps a value of type `T` to a string, and `T'value(s)` maps
+    // a string to a value of type `T`.
+    if is_image {
+        if !tyc.must_match(ty, arg_ty, arg_span) {
+            return Err(());
+        }
+        builtin_string_ty(tyc, hir.parent, name.span)
+    } else {
+        tyc.must_be_string(arg_ty, arg_span)?;
+        Ok(ty)
+    }
+}
+
+/// Evaluate the type of a predefined attribute of an object.
+///
+/// Only the bound, range, and `'ascending` attributes of array objects, and
+/// the `'delayed` attribute of signals are supported. The former refer to the
+/// array's first index: `A'high` and `A'low` are values of the index type,
+/// and the range attributes yield a range of it.
+///
+/// See IEEE 1076-2008 sections 16.2.3 and 16.2.4.
+fn typeval_attr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    prefix: ExprRef,
+    name: &Spanned<ResolvableName>,
+    arg: Option<ExprRef>,
+) -> Result<&'ctx Ty> {
+    let table = get_name_table();
+    if name.value == table.intern("DELAYED", false).into() {
+        return typeval_delayed_attr(tyc, hir, prefix, arg);
+    }
+    if arg.is_some() {
+        tyc.emit(
+            DiagBuilder2::bug(format!(
+                "attribute `{}` with argument not implemented",
+                name.value
+            ))
+            .span(name.span),
+        );
+        return Err(());
+    }
+    let is_reverse = name.value == table.intern("REVERSE_RANGE", false).into();
+    let is_range = is_reverse || name.value == table.intern("RANGE", false).into();
+    let is_bound = ["LEFT", "RIGHT", "HIGH", "LOW"]
+        .iter()
+        .any(|n| name.value == table.intern(n, false).into());
+    let is_ascending = name.value == table.intern("ASCENDING", false).into();
+    if !is_range && !is_bound && !is_ascending {
+        tyc.emit(
+            DiagBuilder2::bug(format!("attribute `{}` not implemented", name.value))
+                .span(name.span),
+        );
+        return Err(());
+    }
+    let prefix_ty = tyc.lazy_typeval(prefix)?;
+    let prefix_span = tyc.ctx.span(prefix).unwrap();
+    let index = match *tyc.ctx.deref_named_type(prefix_ty)? {
+        Ty::Array(ref at) => match at.indices[0] {
+            ArrayIndex::Constrained(ref index) => tyc.ctx.intern_ty((**index).clone()),
+            ArrayIndex::Unbounded(..) => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` requires a constrained array prefix, but `{}` is unconstrained",
+                        hir.span.extract(),
+                        prefix_span.extract()
+                    ))
+                    .span(prefix_span)
+                    .add_note("See IEEE 1076-2008 section 16.2.3."),
+                );
+                return Err(());
+            }
+        },
+        ref other => {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` requires an array prefix, but `{}` is {}",
+                    hir.span.extract(),
+                    prefix_span.extract(),
+                    other.kind_desc()
+                ))
+                .span(prefix_span)
+                .add_note("See IEEE 1076-2008 section 16.2.3."),
+            );
+            return Err(());
+        }
+    };
+    if is_bound {
+        Ok(index)
+    } else if is_ascending {
+        Ok(tyc.ctx.intern_ty(BOOLEAN_TYPE.named_ty()))
+    } else {
+        range_attr_ty(tyc, index, is_reverse)
+    }
+}
+
+/// Evaluate the type of the `S'delayed(T)` attribute.
+///
+/// The prefix must denote a signal, and the optional delay must be of type
+/// `TIME`. The result is a signal of the same type as the prefix. See IEEE
+/// 1076-2008 section 16.2.4.
+fn typeval_delayed_attr<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    prefix: ExprRef,
+    arg: Option<ExprRef>,
+) -> Result<&'ctx Ty> {
+    let prefix_span = tyc.ctx.span(prefix).unwrap();
+    if !is_signal_expr(tyc, prefix)? {
+        tyc.emit(
+            DiagBuilder2::error(format!(
+                "`{}` requires a signal prefix, but `{}` is not a signal",
+                hir.span.extract(),
+                prefix_span.extract()
+            ))
+            .span(prefix_span)
+            .add_note("See IEEE 1076-2008 section 16.2.4."),
+        );
+        return Err(());
+    }
+    if let Some(arg) = arg {
+        let time = tyc.ctx.intern_ty(TIME_TYPE.named_ty());
+        tyc.ctx.set_type_context(arg, TypeCtx::Type(time));
+        let arg_ty = tyc.lazy_typeval(arg)?;
+        if !tyc.must_match(time, arg_ty, tyc.ctx.span(arg).unwrap()) {
+            return Err(());
+        }
+    }
+    tyc.lazy_typeval(prefix)
+}
+
+/// Check whether an expression denotes a signal.
+///
+/// This is the case for signal names and the signals implicitly declared by
+/// the `'delayed` attribute.
+fn is_signal_expr(tyc: &TypeckContext, expr: ExprRef) -> Result<bool> {
+    Ok(match tyc.ctx.lazy_hir(expr)?.data {
+        hir::ExprData::SignalName(..) | hir::ExprData::Name(Def::Signal(..), _) => true,
+        hir::ExprData::Attr(prefix, ref name, _) => {
+            name.value == get_name_table().intern("DELAYED", false).into()
+                && is_signal_expr(tyc, prefix)?
+        }
+        _ => false,
+    })
+}
+
+/// Determine the type of the `'range` or `'reverse_range` attribute of `ty`.
+fn range_attr_ty<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    ty: &'ctx Ty,
+    is_reverse: bool,
+) -> Result<&'ctx Ty> {
+    let (dir, bounds) = match *tyc.ctx.deref_named_type(ty)? {
+        Ty::Int(ref it) => (
+            it.dir,
+            Some((it.left_bound.clone(), it.right_bound.clone())),
+        ),
+        _ => (Dir::To, None),
+    };
+    let range = if is_reverse {
+        let dir = match dir {
+            Dir::To => Dir::Downto,
+            Dir::Downto => Dir::To,
+        };
+        RangeTy::new(dir, ty.clone(), bounds.map(|(l, r)| (r, l)))
+    } else {
+        RangeTy::new(dir, ty.clone(), bounds)
+    };
+    Ok(tyc.ctx.intern_ty(range))
+}
+
+/// Resolve the `STRING` type visible in a scope.
+fn builtin_string_ty<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    scope: ScopeRef,
+    span: Span,
+) -> Result<&'ctx Ty> {
+    let name = Spanned::new(get_name_table().intern("STRING", false).into(), span);
+    let defs = tyc.ctx.resolve_name(name, scope, false, true)?;
+    let tm: TypeMarkRef = match defs.first().map(|d| d.value) {
+        Some(Def::Type(id)) => id.into(),
+        Some(Def::Subtype(id)) => id.into(),
+        _ => {
+            tyc.emit(
+                DiagBuilder2::error("type `STRING` is not visible").span(span).add_note(
+                    "The result of the `'image` attribute is a `STRING`, which must be declared \
+                     or imported.",
+                ),
+            );
+            return Err(());
+        }
+    };
+    Ok(tyc.ctx.intern_named_ty(tm))
+}
+
+/// Check whether a scope is one of the `numeric_std` or `numeric_bit`
+/// packages of library `ieee`.
+fn is_numeric_pkg(tyc: &TypeckContext, scope: ScopeRef) -> bool {
+    let (parent, decl) = match scope {
+        ScopeRef::Pkg(id) => tyc.ctx.ast(id),
+        _ => return false,
+    };
+    let table = get_name_table();
+    let name = decl.name.value;
+    if name != table.intern("NUMERIC_STD", false) && name != table.intern("NUMERIC_BIT", false) {
+        return false;
+    }
+    let lib = match parent {
+        ScopeRef::CtxItems(id) => tyc.ctx.ast(id).0,
+        _ => return false,
+    };
+    match lib {
+        ScopeRef::Lib(id) => match tyc.ctx.lib_name(id) {
+            Some(name) => name.as_str().eq_ignore_ascii_case("ieee"),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Determine the `numeric_std` array type declaration of a type, if any.
+fn numeric_array_decl(tyc: &TypeckContext, ty: &Ty) -> Result<Option<TypeDeclRef>> {
+    let decl = match *tyc.ctx.deref_named_type(ty)? {
+        Ty::Array(ArrayTy {
+            decl: Some(decl), ..
+        }) if !is_builtin_type(decl) => decl,
+        _ => return Ok(None),
+    };
+    let hir = tyc.ctx.lazy_hir(decl)?;
+    if is_numeric_pkg(tyc, hir.parent) {
+        Ok(Some(decl))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Determine the return type of the operator an operation resolved to.
+///
+/// The type of the operation is the return type of the operator, which is
+/// either builtin or a user-defined operator function.
+fn operator_return_type<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    def: Def,
+    op: Spanned<String>,
+) -> Result<&'ctx Ty> {
+    let sig = match def {
+        Def::BuiltinOp(id) => tyc.lazy_typeval(id)?,
+        Def::Subprog(id) => tyc.lazy_typeval(id)?,
+        Def::Alias(id) => tyc.ctx.ty(id)?,
+        _ => unreachable!(),
+    };
+    match *sig {
+        Ty::Subprog(SubprogTy {
+            ret: Some(ref ret), ..
+        }) => Ok(ret.as_ref()),
         _ => {
             tyc.emit(
                 DiagBuilder2::bug(format!(
-                    "typeval for expression `{}` not implemented",
-                    hir.span.extract()
+                    "operator `{}` resolved to {}, which returns no value",
+                    op.value, sig
                 ))
-                .span(hir.span),
+                .span(op.span),
             );
-            debugln!("It is a {:#?}", hir.data);
             Err(())
         }
     }
 }
 
+/// Ensure that the operands of a binary operator do not mix the `signed` and
+/// `unsigned` types of `numeric_std`.
+///
+/// The package declares no operators for such mixed operands. This check
+/// provides a more helpful diagnostic than overload resolution would.
+fn check_numeric_operands(
+    tyc: &TypeckContext,
+    hir: &hir::Expr,
+    lhs_ty: &Ty,
+    rhs_ty: &Ty,
+) -> Result<()> {
+    let (lhs_decl, rhs_decl) = match (
+        numeric_array_decl(tyc, lhs_ty)?,
+        numeric_array_decl(tyc, rhs_ty)?,
+    ) {
+        (Some(l), Some(r)) if l != r => (l, r),
+        _ => return Ok(()),
+    };
+    let lhs_name = tyc.ctx.lazy_hir(lhs_decl)?.name.value;
+    let rhs_name = tyc.ctx.lazy_hir(rhs_decl)?.name.value;
+    tyc.emit(
+        DiagBuilder2::error(format!(
+            "`{}` mixes operands of type `{}` and `{}`",
+            hir.span.extract(),
+            lhs_name,
+            rhs_name
+        ))
+        .span(hir.span)
+        .add_note(format!(
+            "There are no operators for mixed `{}` and `{}` operands. Convert one of them \
+             explicitly, e.g. `{}(...)`.",
+            lhs_name, rhs_name, lhs_name
+        )),
+    );
+    Err(())
+}
+
+/// Determine the length of a one-dimensional array type, if it is constrained.
+fn array_len(tyc: &TypeckContext, ty: &Ty) -> Result<Option<BigInt>> {
+    Ok(match *tyc.ctx.deref_named_type(ty)? {
+        Ty::Array(ref at) if at.indices.len() == 1 => match *at.indices[0].ty() {
+            Ty::Int(ref it) => Some(max(it.len(), BigInt::zero())),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Determine the length of the result of a `numeric_std` operator.
+///
+/// See the result size rules of IEEE 1076.3 (`numeric_std`). Returns `None` if
+/// the operator does not return an array or the operand lengths are unknown.
+fn numeric_result_len(
+    tyc: &TypeckContext,
+    op: BinaryOp,
+    lhs_ty: &Ty,
+    rhs_ty: &Ty,
+) -> Result<Option<BigInt>> {
+    let lhs = array_len(tyc, lhs_ty)?;
+    let rhs = array_len(tyc, rhs_ty)?;
+    Ok(match (op, lhs, rhs) {
+        // Both operands are arrays.
+        (BinaryOp::Add, Some(l), Some(r)) | (BinaryOp::Sub, Some(l), Some(r)) => Some(max(l, r)),
+        (BinaryOp::Mul, Some(l), Some(r)) => Some(l + r),
+        (BinaryOp::Div, Some(l), Some(_)) => Some(l),
+        (BinaryOp::Mod, Some(_), Some(r)) | (BinaryOp::Rem, Some(_), Some(r)) => Some(r),
+        (BinaryOp::Logical(_), Some(l), Some(_)) => Some(l),
+
+        // One operand is an integer.
+        (BinaryOp::Mul, Some(l), None) => Some(&l + &l),
+        (BinaryOp::Mul, None, Some(r)) => Some(&r + &r),
+        (BinaryOp::Add, Some(l), None)
+        | (BinaryOp::Sub, Some(l), None)
+        | (BinaryOp::Div, Some(l), None)
+        | (BinaryOp::Mod, Some(l), None)
+        | (BinaryOp::Rem, Some(l), None)
+        | (BinaryOp::Shift(_), Some(l), None) => Some(l),
+        (BinaryOp::Add, None, Some(r))
+        | (BinaryOp::Sub, None, Some(r))
+        | (BinaryOp::Div, None, Some(r))
+        | (BinaryOp::Mod, None, Some(r))
+        | (BinaryOp::Rem, None, Some(r)) => Some(r),
+        _ => None,
+    })
+}
+
+/// Constrain the array returned by a `numeric_std` operator to
+/// `len-1 downto 0`.
+fn numeric_result_ty<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    ret: &Ty,
+    len: BigInt,
+) -> Result<&'ctx Ty> {
+    match *tyc.ctx.deref_named_type(ret)? {
+        Ty::Array(ref at) if at.indices.len() == 1 => {
+            let index = IntTy::new(Dir::Downto, len - BigInt::one(), BigInt::zero());
+            Ok(tyc.ctx.intern_ty(
+                ArrayTy::new(
+                    vec![ArrayIndex::Constrained(Box::new(index.into()))],
+                    at.element.clone(),
+                )
+                .with_decl(at.decl),
+            ))
+        }
+        _ => Ok(tyc.ctx.intern_ty(ret.clone())),
+    }
+}
+
+/// Determine the one-dimensional array type of an operand, if it is one.
+fn one_dim_array<'a, 'ctx: 'a>(
+    tyc: &TypeckContext<'_, '_, '_, '_, 'ctx>,
+    ty: &'a Ty,
+) -> Result<Option<&'a ArrayTy>> {
+    Ok(match *tyc.ctx.deref_named_type(ty)? {
+        Ty::Array(ref at) if at.indices.len() == 1 => Some(at),
+        _ => None,
+    })
+}
+
+/// Evaluate the type of a concatenation `lhs & rhs`.
+///
+/// Each operand is either an array or an element of that array type. The
+/// result is constrained to the summed length of the operands if it is
+/// statically known. See IEEE 1076-2008 section 9.2.5.
+fn typeval_concat<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
+    tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    hir: &hir::Expr,
+    lhs: ExprRef,
+    rhs: ExprRef,
+    tyctx: Option<&'ctx Ty>,
+) -> Result<&'ctx Ty> {
+    // Operands whose type depends on the context are either literals of the
+    // result array, of yet unknown length, or literals of its element type.
+    let tyctx_array = match tyctx {
+        Some(ty) => one_dim_array(tyc, ty)?,
+        None => None,
+    };
+    if let Some(at) = tyctx_array {
+        let unbounded = tyc.ctx.intern_ty(
+            ArrayTy::new(
+                vec![ArrayIndex::Unbounded(Box::new(at.indices[0].ty().clone()))],
+                at.element.clone(),
+            )
+            .with_decl(at.decl),
+        );
+        let element = tyc.ctx.intern_ty(at.element.as_ref().clone());
+        for &operand in &[lhs, rhs] {
+            match tyc.ctx.lazy_hir(operand)?.data {
+                hir::ExprData::StringLiteral(..) | hir::ExprData::Aggregate(..) => {
+                    tyc.ctx.set_type_context(operand, unbounded)
+                }
+                hir::ExprData::EnumName(..) => tyc.ctx.set_type_context(operand, element),
+                hir::ExprData::Binary(op, ..) if op.value == BinaryOp::Concat => {
+                    tyc.ctx.set_type_context(operand, unbounded)
+                }
+                _ => (),
+            }
+        }
+    }
+    let lhs_ty = tyc.lazy_typeval(lhs)?;
+    let rhs_ty = tyc.lazy_typeval(rhs)?;
+    let lhs_array = one_dim_array(tyc, lhs_ty)?;
+    let rhs_array = one_dim_array(tyc, rhs_ty)?;
+    let lhs_element = lhs_ty.element_type(tyc.ctx)?;
+    let rhs_element = rhs_ty.element_type(tyc.ctx)?;
+    let is_element_of = |element: Option<&Ty>, ty| match element {
+        Some(element) => are_types_matching(tyc.ctx, element, ty),
+        None => false,
+    };
+
+    // Determine the array type of the result, and which of the operands are
+    // arrays rather than elements.
+    let (at, lhs_is_array, rhs_is_array) = match (lhs_array, rhs_array) {
+        (Some(l), Some(_)) if are_types_matching(tyc.ctx, lhs_ty, rhs_ty) => (l, true, true),
+        (Some(l), _) if is_element_of(lhs_element, rhs_ty) => (l, true, false),
+        (_, Some(r)) if is_element_of(rhs_element, lhs_ty) => (r, false, true),
+        _ => match tyctx_array {
+            Some(at)
+                if are_types_matching(tyc.ctx, &at.element, lhs_ty)
+                    && are_types_matching(tyc.ctx, &at.element, rhs_ty) =>
+            {
+                (at, false, false)
+            }
+            None if lhs_array.is_none() && rhs_array.is_none() => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "type of concatenation `{}` cannot be inferred from context",
+                        hir.span.extract()
+                    ))
+                    .span(hir.span),
+                );
+                return Err(());
+            }
+            _ => {
+                tyc.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` concatenates operands with incompatible element types",
+                        hir.span.extract()
+                    ))
+                    .span(hir.span)
+                    .add_note(format!("left operand has type {}", lhs_ty))
+                    .add_note(format!("right operand has type {}", rhs_ty))
+                    .add_note(
+                        "The operands of `&` must be arrays of the same type or elements of \
+                         that array type. See IEEE 1076-2008 section 9.2.5.",
+                    ),
+                );
+                return Err(());
+            }
+        },
+    };
+
+    // Sum up the operand lengths.
+    let lhs_len = if lhs_is_array {
+        array_len(tyc, lhs_ty)?
+    } else {
+        Some(BigInt::one())
+    };
+    let rhs_len = if rhs_is_array {
+        array_len(tyc, rhs_ty)?
+    } else {
+        Some(BigInt::one())
+    };
+
+    // If the left operand is a null array, the result is the right operand,
+    // including its bounds. See IEEE 1076-1993 section 7.2.4.
+    if lhs_is_array && rhs_is_array && lhs_len.as_ref().map_or(false, |l| l.is_zero()) {
+        return Ok(rhs_ty);
+    }
+    let len = match (lhs_len, rhs_len) {
+        (Some(l), Some(r)) => l + r,
+        _ => return Ok(tyc.ctx.intern_ty(at.clone())),
+    };
+
+    // The result starts at the left bound of the left operand, or of the index
+    // subtype if the left operand is an element.
+    let base = match lhs_array {
+        Some(l) if lhs_is_array => l.indices[0].ty(),
+        _ => at.indices[0].ty(),
+    };
+    let (dir, left) = match *tyc.ctx.deref_named_type(base)? {
+        Ty::Int(ref it) => (it.dir, it.left_bound.clone()),
+        _ => return Ok(tyc.ctx.intern_ty(at.clone())),
+    };
+    let right = match dir {
+        Dir::To => &left + len - BigInt::one(),
+        Dir::Downto => &left - len + BigInt::one(),
+    };
+    let index = IntTy::new(dir, left, right);
+    Ok(tyc.ctx.intern_ty(
+        ArrayTy::new(
+            vec![ArrayIndex::Constrained(Box::new(index.into()))],
+            at.element.clone(),
+        )
+        .with_decl(at.decl),
+    ))
+}
+
 /// Evaluate the type of a record aggregate.
 pub fn typeval_record_aggregate<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
     tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
@@ -548,7 +1656,7 @@ pub fn typeval_array_aggregate<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: '
 
     // Determine the index and element types from the context.
     let (index, element) = if let Ty::Array(ref ty) = *tyctx_flat {
-        let index = ty.indices[0].ty();
+        let index = &ty.indices[0];
         let element = if ty.indices.len() > 1 {
             tyc.ctx.intern_ty(ArrayTy::new(
                 ty.indices.iter().skip(1).cloned().collect(),
@@ -562,6 +1670,30 @@ pub fn typeval_array_aggregate<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: '
         unreachable!();
     };
 
+    // Positional and named associations cannot be mixed in an array
+    // aggregate, except for a trailing `others`.
+    if let hir::AggregateKind::Array(ref fields) = hir.named {
+        if let (Some(pos), Some(field)) = (hir.positional.first(), fields.first()) {
+            tyc.emit(
+                DiagBuilder2::error(format!(
+                    "named element `{}` cannot follow positional elements in array aggregate",
+                    field.span.extract()
+                ))
+                .span(field.span)
+                .add_note(format!(
+                    "first positional element `{}` is here:",
+                    pos.span.extract()
+                ))
+                .span(pos.span)
+                .add_note(
+                    "An array aggregate must either be entirely positional or entirely named, \
+                     apart from a final `others` element. See IEEE 1076-2008 section 9.3.3.3.",
+                ),
+            );
+            return Err(());
+        }
+    }
+
     // Forward the type context and check the index and element types.
     let mut had_fails = false;
     for &pos in &hir.positional {
@@ -615,12 +1747,12 @@ pub fn typeval_array_aggregate<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: '
 pub fn typeck_array_aggregate_element<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
     tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
     hir: &Spanned<(hir::ArrayChoices, Spanned<ExprRef>)>,
-    index_ty: &'ctx Ty,
+    index: &'ctx ArrayIndex,
     element_ty: &'ctx Ty,
 ) -> Result<()> {
     let mut had_fails = false;
     for choice in &hir.value.0 {
-        match typeck_array_aggregate_choice(tyc, choice, index_ty) {
+        match typeck_array_aggregate_choice(tyc, choice, index) {
             Ok(()) => (),
             Err(()) => had_fails = true,
         }
@@ -639,8 +1771,9 @@ pub fn typeck_array_aggregate_element<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb,
 pub fn typeck_array_aggregate_choice<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb>(
     tyc: &TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
     hir: &Spanned<hir::ArrayChoice>,
-    index_ty: &'ctx Ty,
+    index: &'ctx ArrayIndex,
 ) -> Result<()> {
+    let index_ty = index.ty();
     match hir.value {
         hir::ArrayChoice::Expr(expr_id) => {
             tyc.ctx.set_type_context(expr_id, index_ty);
@@ -651,8 +1784,34 @@ pub fn typeck_array_aggregate_choice<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, '
             let ty = tyc.lazy_typeval(subtype_id)?;
             tyc.must_match(index_ty, ty, hir.span);
         }
-        hir::ArrayChoice::DiscreteRange(hir::DiscreteRange::Range(ref _range)) => {
-            // TODO: Check type.
+        hir::ArrayChoice::DiscreteRange(ref range @ hir::DiscreteRange::Range(..)) => {
+            let ty = tyc.typeval_discrete_range(range, hir.span)?;
+            if let Ty::Range(ref rt) = *ty {
+                tyc.must_match(index_ty, &rt.bound, hir.span);
+            }
+            // Only a constrained index has a direction the choice must follow.
+            // An unconstrained context takes its direction from the choice.
+            if let (
+                &hir::DiscreteRange::Range(hir::Range::Immediate(dir, ..)),
+                &ArrayIndex::Constrained(..),
+            ) = (range, index)
+            {
+                let index_dir = match *tyc.ctx.deref_named_type(index_ty)? {
+                    Ty::Int(ref it) => it.dir,
+                    _ => Dir::To,
+                };
+                if dir != index_dir {
+                    tyc.emit(
+                        DiagBuilder2::error("choice range direction does not match array index")
+                            .span(hir.span)
+                            .add_note(format!(
+                                "choice is `{}`, but index {} is `{}`",
+                                dir, index_ty, index_dir
+                            )),
+                    );
+                    return Err(());
+                }
+            }
         }
     }
     Ok(())
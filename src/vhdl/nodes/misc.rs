@@ -11,7 +11,6 @@ use crate::make_ctx::MakeContext;
 use crate::score::*;
 use crate::syntax::ast;
 use crate::term::TermContext;
-use crate::ty::*;
 
 impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     /// Add a subtype indication.
@@ -37,17 +36,32 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     /// Schedule subtype indication tasks.
     pub fn schedule_subtype_ind(&self, mk: &MakeContext<SubtypeIndRef>) {
         let id = mk.id;
+        let scope = self.scope;
         mk.typeval(Box::new(move |tyc| {
             let hir = tyc.ctx.lazy_hir(id)?;
-            let inner = tyc
-                .ctx
-                .intern_ty(Ty::Named(hir.type_mark.span.into(), hir.type_mark.value));
+            let inner = tyc.ctx.intern_named_ty(hir.type_mark.value);
+            if let Some(func) = hir.resolution {
+                tyc.check_resolution_func(func, inner)?;
+            }
             match hir.constraint {
                 None => Ok(inner),
                 Some(Spanned {
                     value: hir::Constraint::Range(ref con),
                     span,
-                }) => tyc.apply_range_constraint(inner, Spanned::new(con, span)),
+                }) => {
+                    // Subtypes in processes and subprograms may have
+                    // constraints that are only known at runtime.
+                    let dynamic = match scope {
+                        ScopeRef::Process(_) | ScopeRef::SubprogBody(_) | ScopeRef::Loop(_) => {
+                            tyc.apply_nonstatic_range_constraint(inner, Spanned::new(con, span))?
+                        }
+                        _ => None,
+                    };
+                    match dynamic {
+                        Some(ty) => Ok(ty),
+                        None => tyc.apply_range_constraint(inner, Spanned::new(con, span)),
+                    }
+                }
                 Some(Spanned {
                     value: hir::Constraint::Array(ref ac),
                     span,
@@ -8,4 +8,5 @@ mod expr;
 mod misc;
 mod obj_decl;
 mod seq_stmt;
+mod subprog;
 mod type_decl;
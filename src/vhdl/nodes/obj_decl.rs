@@ -10,6 +10,7 @@ use crate::common::score::Result;
 use crate::common::source::Spanned;
 
 use crate::add_ctx::AddContext;
+use crate::builtin::FILE_OPEN_KIND_TYPE;
 use crate::hir;
 use crate::score::*;
 use crate::syntax::ast;
@@ -43,9 +44,32 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 mk.typeval(Box::new(move |tyc| {
                     let hir = tyc.ctx.lazy_hir(id)?;
                     let ty = tyc.lazy_typeval(hir.decl.ty)?;
+                    // Deferred constants in packages obtain their value and
+                    // constraint from the full declaration in the package
+                    // body. See IEEE 1076-2008 section 6.4.2.2.
+                    let deferred = match hir.parent {
+                        ScopeRef::Pkg(_) => true,
+                        _ => false,
+                    };
+                    if hir.decl.init.is_none() && !deferred {
+                        tyc.emit(
+                            DiagBuilder2::error(format!(
+                                "constant `{}` requires a value",
+                                hir.name.value
+                            ))
+                            .span(hir.span)
+                            .add_note(
+                                "Only constants declared in a package declaration may be \
+                                 deferred. See IEEE 1076-2008 section 6.4.2.2.",
+                            ),
+                        );
+                        return Err(());
+                    }
                     if let Some(init) = hir.decl.init {
                         let init_ty = tyc.lazy_typeval(init)?;
-                        tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap());
+                        if tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap()) {
+                            let _ = tyc.check_static_range(init, ty);
+                        }
                     }
                     Ok(ty)
                 }));
@@ -97,9 +121,15 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 mk.typeval(Box::new(move |tyc| {
                     let hir = tyc.ctx.lazy_hir(id)?;
                     let ty = tyc.lazy_typeval(hir.decl.ty)?;
+                    tyc.must_be_resolved_signal(hir.decl.kind, hir.decl.ty, hir.span)?;
+                    if hir.decl.init.is_none() {
+                        tyc.must_be_constrained(ty, hir.span)?;
+                    }
                     if let Some(init) = hir.decl.init {
                         let init_ty = tyc.lazy_typeval(init)?;
-                        tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap());
+                        if tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap()) {
+                            let _ = tyc.check_static_range(init, ty);
+                        }
                     }
                     Ok(ty)
                 }));
@@ -139,9 +169,65 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 mk.typeval(Box::new(move |tyc| {
                     let hir = tyc.ctx.lazy_hir(id)?;
                     let ty = tyc.lazy_typeval(hir.decl.ty)?;
+                    if hir.decl.shared {
+                        match *tyc.ctx.deref_named_type(ty)? {
+                            Ty::Protected(..) => (),
+                            ref wrong => {
+                                tyc.emit(
+                                    DiagBuilder2::error(format!(
+                                        "shared variable `{}` must be of a protected type, \
+                                         but `{}` is a {}",
+                                        hir.name.value,
+                                        ty,
+                                        wrong.kind_desc()
+                                    ))
+                                    .span(hir.name.span)
+                                    .add_note(
+                                        "Shared variables must be of a protected type. See \
+                                         IEEE 1076-2008 section 6.4.2.4.",
+                                    ),
+                                );
+                                return Err(());
+                            }
+                        }
+                    }
+                    let flat = tyc.ctx.deref_named_type(ty)?;
+                    if flat.is_file(tyc.ctx)? {
+                        tyc.emit(
+                            DiagBuilder2::error(format!(
+                                "variable `{}` cannot be of a file type",
+                                hir.name.value
+                            ))
+                            .span(hir.name.span)
+                            .add_note(
+                                "Use a file declaration instead. See IEEE 1076-2008 section \
+                                 6.4.2.4.",
+                            ),
+                        );
+                        return Err(());
+                    }
+                    if let (&Ty::Protected(..), Some(init)) = (flat, hir.decl.init) {
+                        tyc.emit(
+                            DiagBuilder2::error(format!(
+                                "variable `{}` of protected type {} cannot have an initial value",
+                                hir.name.value, ty
+                            ))
+                            .span(tyc.ctx.span(init).unwrap())
+                            .add_note(
+                                "Protected types have no values that could be assigned. See \
+                                 IEEE 1076-2008 section 6.4.2.4.",
+                            ),
+                        );
+                        return Err(());
+                    }
+                    if hir.decl.init.is_none() {
+                        tyc.must_be_constrained(ty, hir.span)?;
+                    }
                     if let Some(init) = hir.decl.init {
                         let init_ty = tyc.lazy_typeval(init)?;
-                        tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap());
+                        if tyc.must_match(ty, init_ty, tyc.ctx.span(init).unwrap()) {
+                            let _ = tyc.check_static_range(init, ty);
+                        }
                     }
                     Ok(ty)
                 }));
@@ -182,6 +268,10 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                         }
                         None => (None, None),
                     };
+                    sbc.set_type_context_optional(
+                        mode,
+                        sbc.intern_ty(FILE_OPEN_KIND_TYPE.named_ty()),
+                    );
                     Ok(hir::Decl {
                         parent: scope,
                         span: dn.span,
@@ -196,9 +286,31 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 mk.typeval(Box::new(move |tyc| {
                     let hir = tyc.ctx.lazy_hir(id)?;
                     let ty = tyc.ctx.lazy_typeval(hir.decl.ty)?;
-                    // TODO: Check that the type of expressions are okay.
-                    let file_ty = tyc.ctx.intern_ty(Ty::File(Box::new(ty.clone())));
-                    Ok(file_ty)
+                    if !ty.is_file(tyc.ctx)? {
+                        let span = tyc.ctx.span(hir.decl.ty).unwrap_or(hir.span);
+                        tyc.emit(
+                            DiagBuilder2::error(format!("`{}` is not a file type", span.extract()))
+                                .span(span)
+                                .add_note(format!(
+                                    "File `{}` must be declared with a file type. See IEEE \
+                                 1076-2008 section 6.4.2.5.",
+                                    hir.name.value
+                                )),
+                        );
+                        return Err(());
+                    }
+                    if let Some(mode) = hir.decl.mode {
+                        let exp = tyc.ctx.intern_ty(FILE_OPEN_KIND_TYPE.named_ty());
+                        let act = tyc.lazy_typeval(mode)?;
+                        if !tyc.must_match(exp, act, tyc.ctx.span(mode).unwrap()) {
+                            return Err(());
+                        }
+                    }
+                    if let Some(filename) = hir.decl.filename {
+                        let act = tyc.lazy_typeval(filename)?;
+                        tyc.must_be_string(act, tyc.ctx.span(filename).unwrap())?;
+                    }
+                    Ok(ty)
                 }));
                 Ok(mk.finish().into())
             })
@@ -10,11 +10,12 @@ use crate::common::score::Result;
 use crate::common::source::Spanned;
 use crate::common::util::{HasDesc, HasSpan};
 
+use super::expr::typeck_call;
 use crate::add_ctx::AddContext;
 use crate::hir;
 use crate::score::*;
 use crate::syntax::ast;
-use crate::term::TermContext;
+use crate::term::{Term, TermContext};
 
 impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     /// Add multiple sequential statements.
@@ -93,7 +94,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
 
     /// Add a wait statement.
     pub fn add_wait_stmt(&self, stmt: &'ast ast::Stmt) -> Result<WaitStmtRef> {
-        let (mk, id, scope) = self.make(stmt.span);
+        let (mk, id, scope) = self.make::<WaitStmtRef>(stmt.span);
         let (on, until, time) = match stmt.data {
             ast::WaitStmt {
                 ref on,
@@ -124,17 +125,50 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            // hir.stmt.cond.map(|id| tyc.must_match(tyc.ctx.lazy_typeval(id), tyc.ctx.builtin_boolean_type()));
-            // hir.stmt.timeout.map(|id| tyc.must_match(tyc.ctx.lazy_typeval(id), tyc.ctx.builtin_time_type()));
-            Ok(())
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let mut had_fails = false;
+            if let Some(cond) = hir.stmt.cond {
+                had_fails |= tyc.typeck_condition(cond).is_err();
+            }
+            if let Some(timeout) = hir.stmt.timeout {
+                had_fails |= tyc.typeck_time_expr(timeout, "timeout").is_err();
+            }
+
+            // Wait statements must not appear in functions, or in processes
+            // with a sensitivity list.
+            let within = {
+                let region = tyc.region();
+                if region.subprog.map_or(false, |ret| ret.is_some()) {
+                    Some("a function")
+                } else if region.sensitized {
+                    Some("a process with a sensitivity list")
+                } else {
+                    None
+                }
+            };
+            if let Some(within) = within {
+                tyc.emit(
+                    DiagBuilder2::error(format!("`wait` statement in {}", within))
+                        .span(hir.span)
+                        .add_note(
+                            "Functions and processes with a sensitivity list must not \
+                             suspend. See IEEE 1076-2008 section 10.2.",
+                        ),
+                );
+                had_fails = true;
+            }
+            if had_fails {
+                Err(())
+            } else {
+                Ok(())
+            }
         }));
         Ok(mk.finish())
     }
 
     /// Add an assert statement.
     pub fn add_assert_stmt(&self, stmt: &'ast ast::Stmt) -> Result<AssertStmtRef> {
-        let (mk, id, scope) = self.make(stmt.span);
+        let (mk, id, scope) = self.make::<AssertStmtRef>(stmt.span);
         let (cond, report, severity) = match stmt.data {
             ast::AssertStmt {
                 ref cond,
@@ -150,7 +184,8 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             let severity = ctx.add_optional(severity, AddContext::add_expr);
             let (cond, report, severity) = (cond?, report?, severity?);
             sbc.set_type_context(cond, TypeCtx::Type(sbc.builtin_boolean_type()));
-            sbc.set_type_context_optional(report, sbc.builtin_string_type());
+            // The report message has no type context, since it may be any
+            // one-dimensional array of CHARACTER.
             sbc.set_type_context_optional(severity, sbc.builtin_severity_type());
             Ok(hir::Stmt {
                 parent: scope,
@@ -164,18 +199,17 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            // tyc.must_match(tyc.ctx.lazy_typeval(hir.stmt.cond), tyc.ctx.builtin_boolean_type());
-            // hir.stmt.report.map(|id| tyc.must_match(tyc.ctx.lazy_typeval(id), tyc.ctx.builtin_string_type()));
-            // hir.stmt.severity.map(|id| tyc.must_match(tyc.ctx.lazy_typeval(id), tyc.ctx.builtin_severity_type()));
-            Ok(())
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let cond = tyc.typeck_condition(hir.stmt.cond);
+            let report = tyc.typeck_report(hir.stmt.report, hir.stmt.severity);
+            cond.and(report)
         }));
         Ok(mk.finish())
     }
 
     /// Add a report statement.
     pub fn add_report_stmt(&self, stmt: &'ast ast::Stmt) -> Result<ReportStmtRef> {
-        let (mk, id, scope) = self.make(stmt.span);
+        let (mk, id, scope) = self.make::<ReportStmtRef>(stmt.span);
         let (report, severity) = match stmt.data {
             ast::ReportStmt {
                 ref msg,
@@ -188,7 +222,6 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             let report = ctx.add_expr(report);
             let severity = ctx.add_optional(severity, AddContext::add_expr);
             let (report, severity) = (report?, severity?);
-            sbc.set_type_context(report, sbc.builtin_string_type());
             sbc.set_type_context_optional(severity, sbc.builtin_severity_type());
             Ok(hir::Stmt {
                 parent: scope,
@@ -201,17 +234,29 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            // tyc.must_match(tyc.ctx.lazy_typeval(hir.stmt.report), tyc.ctx.builtin_string_type());
-            // hir.stmt.severity.map(|id| tyc.must_match(tyc.ctx.lazy_typeval(id), tyc.ctx.builtin_severity_type()));
-            Ok(())
+            let hir = tyc.ctx.lazy_hir(id)?;
+            tyc.typeck_report(Some(hir.stmt.report), hir.stmt.severity)
         }));
         Ok(mk.finish())
     }
 
     /// Add a sig_assign statement.
+    ///
+    /// The statement is lowered from its AST on demand, such that the target
+    /// is resolved and the type context of the waveform is set up in one go.
     pub fn add_sig_assign_stmt(&self, stmt: &'ast ast::Stmt) -> Result<SigAssignStmtRef> {
-        self.unimp(stmt)
+        match stmt.data {
+            ast::AssignStmt { .. } => {
+                let (mk, id, scope) = self.make::<SigAssignStmtRef>(stmt.span);
+                self.ctx.set_ast(id, (scope, stmt));
+                mk.typeck(Box::new(move |tyc| {
+                    let hir = tyc.ctx.hir(id)?;
+                    tyc.typeck_sig_assign_stmt(hir)
+                }));
+                Ok(mk.finish())
+            }
+            _ => self.unimp(stmt),
+        }
     }
 
     /// Add a var_assign statement.
@@ -335,15 +380,120 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            Ok(())
+            // Check the target before lowering it, such that assignments to a
+            // loop parameter point at the parameter's declaration.
+            tyc.must_not_assign_loop_param(target)?;
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let target_ty = match hir.stmt.target.value {
+                hir::Target::Name(expr) => {
+                    tyc.must_be_variable(expr)?;
+                    tyc.lazy_typeval(expr)?
+                }
+                hir::Target::Aggregate(..) => {
+                    tyc.emit(
+                        DiagBuilder2::bug("assignment to aggregate variable not implemented")
+                            .span(hir.stmt.target.span),
+                    );
+                    return Err(());
+                }
+            };
+            let mut had_fails = false;
+            let values: Vec<ExprRef> = match hir.stmt.kind {
+                hir::VarAssignKind::Simple(value) => vec![value],
+                hir::VarAssignKind::Cond(ref cond) => {
+                    for &(_, cond) in &cond.when {
+                        had_fails |= tyc.typeck_condition(cond).is_err();
+                    }
+                    cond.when
+                        .iter()
+                        .map(|&(value, _)| value)
+                        .chain(cond.other)
+                        .collect()
+                }
+                hir::VarAssignKind::Sel(ref sel) => {
+                    tyc.typeck_choices(
+                        sel.disc,
+                        sel.matching,
+                        sel.when.iter().map(|&(_, ref c)| c),
+                        hir.span,
+                    )?;
+                    sel.when.iter().map(|&(value, _)| value).collect()
+                }
+            };
+            for value in values {
+                had_fails |= tyc.typeck_assigned_value(value, target_ty).is_err();
+            }
+            if had_fails {
+                Err(())
+            } else {
+                Ok(())
+            }
         }));
         Ok(mk.finish())
     }
 
-    /// Add a call statement.
+    /// Add a procedure call statement.
     pub fn add_call_stmt(&self, stmt: &'ast ast::Stmt) -> Result<CallStmtRef> {
-        self.unimp(stmt)
+        let name = match stmt.data {
+            ast::InstOrCallStmt {
+                target: None,
+                ref name,
+                generics: None,
+                ports: None,
+            } => name,
+            ast::InstOrCallStmt { .. } => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a procedure call",
+                        stmt.human_span().extract()
+                    ))
+                    .span(stmt.human_span()),
+                );
+                return Err(());
+            }
+            _ => unreachable!(),
+        };
+        let (mk, id, scope) = self.make::<CallStmtRef>(stmt.span);
+        mk.lower_to_hir(Box::new(move |sbc| {
+            let ctx = TermContext::new(sbc, scope);
+            let term = ctx.termify_compound_name(name)?;
+            let (callee, args) = match term.value {
+                Term::SuffixParen(callee, args) => (*callee, ctx.term_to_assoc_list(*args)?),
+                other => (
+                    Spanned::new(other, term.span),
+                    Spanned::new(vec![], term.span),
+                ),
+            };
+            Ok(hir::Stmt {
+                parent: scope,
+                span: stmt.span,
+                label: stmt.label,
+                stmt: hir::CallStmt {
+                    callee: ctx.term_to_expr(callee)?,
+                    args: args,
+                },
+            })
+        }));
+        mk.typeck(Box::new(move |tyc| {
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let callee = tyc.ctx.lazy_hir(hir.stmt.callee)?;
+            match callee.data {
+                hir::ExprData::OverloadedName(ref defs) => {
+                    typeck_call(tyc, callee.span, defs, &hir.stmt.args, None).map(|_| ())
+                }
+                _ => {
+                    tyc.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is not a procedure",
+                            callee.span.extract()
+                        ))
+                        .span(callee.span),
+                    );
+                    Err(())
+                }
+            }
+        }));
+        Ok(mk.finish())
     }
 
     /// Add an if statement.
@@ -383,10 +533,19 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }));
         mk.typeck(Box::new(move |tyc| {
             let hir = tyc.ctx.lazy_hir(id)?;
-            for &(_cond, _) in &hir.stmt.branches {
-                // tyc.must_match(tyc.lazy_typeval(cond), tyc.ctx.builtin_boolean_type());
+            let mut had_fails = false;
+            for &(cond, ref stmts) in &hir.stmt.branches {
+                had_fails |= tyc.typeck_condition(cond).is_err();
+                tyc.typeck_slice(stmts);
+            }
+            if let Some(ref stmts) = hir.stmt.otherwise {
+                tyc.typeck_slice(stmts);
+            }
+            if had_fails {
+                Err(())
+            } else {
+                Ok(())
             }
-            Ok(())
         }));
         Ok(mk.finish())
     }
@@ -421,12 +580,6 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             // Then we re-collect the vector, but this time into a result, which
             // will stop at the first `Err`.
             let (switch, cases) = (switch?, cases?);
-            for &(ref _choices, _) in &cases {
-                // TODO: Set the type context for each choice.
-                // for choice in choices {
-                //     sbc.set_type_context(choice, switch);
-                // }
-            }
             Ok(hir::Stmt {
                 parent: scope,
                 span: stmt.span,
@@ -440,18 +593,25 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }));
         mk.typeck(Box::new(move |tyc| {
             let hir = tyc.ctx.lazy_hir(id)?;
-            let _switch_ty = tyc.lazy_typeval(hir.stmt.switch)?;
-            for &(ref _choices, ref stmts) in &hir.stmt.cases {
-                // tyc.typeck_choices(choices, switch_ty);
+            let choices = tyc.typeck_choices(
+                hir.stmt.switch,
+                hir.stmt.matching,
+                hir.stmt.cases.iter().map(|&(ref choices, _)| choices),
+                hir.span,
+            );
+            for &(_, ref stmts) in &hir.stmt.cases {
                 tyc.typeck_slice(stmts);
             }
-            Ok(())
+            choices
         }));
         Ok(mk.finish())
     }
 
-    /// Add a while loop statement.
-    #[allow(unused_variables)]
+    /// Add a loop statement.
+    ///
+    /// The statements in the loop body are added to a subscope of the loop,
+    /// where the parameter of a `for` loop is declared as a constant. See IEEE
+    /// 1076-2008 section 10.10.
     pub fn add_loop_stmt(&self, stmt: &'ast ast::Stmt) -> Result<LoopStmtRef> {
         let (mk, id, scope) = self.make::<LoopStmtRef>(stmt.span);
         let (scheme, body) = match stmt.data {
@@ -461,18 +621,38 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             } => (scheme, body),
             _ => unreachable!(),
         };
+        let body_scope = ScopeRef::Loop(id);
+        self.ctx.subscope(body_scope, scope);
+        if let ast::LoopScheme::For(name, _) = *scheme {
+            self.ctx
+                .define(body_scope, name.map_into(), Def::LoopParam(id))?;
+        }
+        // The label is visible within the loop, such that nested next and exit
+        // statements may refer to it. See IEEE 1076-2008 sections 10.11 and
+        // 10.12.
+        if let Some(label) = stmt.label {
+            self.ctx.define(
+                body_scope,
+                label.map_into(),
+                Def::Stmt(StmtRef::Seq(SeqStmtRef::Loop(id))),
+            )?;
+        }
         mk.lower_to_hir(Box::new(move |sbc| {
-            // TODO: Make a subscope here! No clue how...
             let ctx = AddContext::new(sbc, scope);
             let scheme = (|| match *scheme {
                 ast::LoopScheme::Loop => Ok(hir::LoopScheme::Loop),
-                ast::LoopScheme::While(ref cond) => Ok(hir::LoopScheme::While(ctx.add_expr(cond)?)),
+                ast::LoopScheme::While(ref cond) => {
+                    let cond = ctx.add_expr(cond)?;
+                    sbc.set_type_context(cond, sbc.builtin_boolean_type());
+                    Ok(hir::LoopScheme::While(cond))
+                }
                 ast::LoopScheme::For(name, ref range) => Ok(hir::LoopScheme::For(
                     name.into(),
                     ctx.add_discrete_range(range)?,
                 )),
             })();
-            let stmts = ctx.add_seq_stmts(&body.stmts, "a loop body");
+            let body_ctx = AddContext::new(sbc, body_scope);
+            let stmts = body_ctx.add_seq_stmts(&body.stmts, "a loop body");
             let (scheme, stmts) = (scheme?, stmts?);
             Ok(hir::Stmt {
                 parent: scope,
@@ -484,11 +664,24 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 },
             })
         }));
+        // The loop parameter takes on the type of the range's elements.
+        mk.typeval(Box::new(move |tyc| {
+            let hir = tyc.ctx.lazy_hir(id)?;
+            match hir.stmt.scheme {
+                hir::LoopScheme::For(_, ref range) => tyc.typeval_range_element(range.as_ref()),
+                _ => unreachable!(),
+            }
+        }));
         mk.typeck(Box::new(move |tyc| {
             let hir = tyc.ctx.lazy_hir(id)?;
-            // TODO: Check the types of the loop scheme.
+            let scheme = match hir.stmt.scheme {
+                hir::LoopScheme::Loop => Ok(()),
+                hir::LoopScheme::While(cond) => tyc.typeck_condition(cond),
+                hir::LoopScheme::For(..) => tyc.lazy_typeval(id).map(|_| ()),
+            };
+            let _region = tyc.with_scope(|r| r.loops.push(id));
             tyc.typeck_slice(&hir.stmt.stmts);
-            Ok(())
+            scheme
         }));
         Ok(mk.finish())
     }
@@ -513,6 +706,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             let target = ctx.add_optional(target, AddContext::add_label);
             let cond = ctx.add_optional(cond, AddContext::add_expr);
             let (target, cond) = (target?, cond?);
+            sbc.set_type_context_optional(cond, sbc.builtin_boolean_type());
             let target = match target {
                 Some(Spanned {
                     value: StmtRef::Seq(SeqStmtRef::Loop(id)),
@@ -539,9 +733,41 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            // tyc.must_match(tyc.lazy_typeval(hir.stmt.cond), tyc.ctx.builtin_boolean_type());
-            Ok(())
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let cond = match hir.stmt.cond {
+                Some(cond) => tyc.typeck_condition(cond),
+                None => Ok(()),
+            };
+            let what = match hir.stmt.mode {
+                hir::NexitMode::Next => "next",
+                hir::NexitMode::Exit => "exit",
+            };
+            let region = tyc.region();
+            match hir.stmt.target {
+                Some(ref target) if !region.loops.contains(&target.value) => {
+                    tyc.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` statement is not within loop `{}`",
+                            what,
+                            target.span.extract()
+                        ))
+                        .span(hir.span)
+                        .add_note(
+                            "A loop label must refer to a loop enclosing the statement. See \
+                             IEEE 1076-2008 sections 10.11 and 10.12.",
+                        ),
+                    );
+                    Err(())
+                }
+                None if region.loops.is_empty() => {
+                    tyc.emit(
+                        DiagBuilder2::error(format!("`{}` statement outside of a loop", what))
+                            .span(hir.span),
+                    );
+                    Err(())
+                }
+                _ => cond,
+            }
         }));
         Ok(mk.finish())
     }
@@ -555,9 +781,9 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         };
         mk.lower_to_hir(Box::new(move |sbc| {
             let ctx = AddContext::new(sbc, scope);
+            // The type context of the expression is set during type checking,
+            // where the return type of the enclosing function is known.
             let expr = ctx.add_optional(expr, AddContext::add_expr)?;
-            // TODO: Set a type context for the expression based on the outer
-            // function signature.
             Ok(hir::Stmt {
                 parent: scope,
                 span: stmt.span,
@@ -566,9 +792,21 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             })
         }));
         mk.typeck(Box::new(move |tyc| {
-            let _hir = tyc.ctx.lazy_hir(id)?;
-            // TODO: Check the type of the expression.
-            Ok(())
+            let hir = tyc.ctx.lazy_hir(id)?;
+            let subprog = tyc.region().subprog;
+            let msg = match (subprog, hir.stmt.expr) {
+                (Some(Some(ty)), Some(expr)) => return tyc.typeck_assigned_value(expr, ty),
+                (Some(None), None) => return Ok(()),
+                (Some(Some(ty)), None) => format!("`return` in a function must return a {}", ty),
+                (Some(None), Some(_)) => "`return` in a procedure cannot return a value".into(),
+                (None, _) => "`return` statement outside of a subprogram".into(),
+            };
+            tyc.emit(DiagBuilder2::error(msg).span(hir.span).add_note(
+                "A return statement must appear in a subprogram body, and returns a value \
+                     if and only if the subprogram is a function. See IEEE 1076-2008 section \
+                     10.13.",
+            ));
+            Err(())
         }));
         Ok(mk.finish())
     }
@@ -653,7 +891,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         if any_cond {
             // Cut away the optional trailing else without condition.
             let (slice, otherwise) = match *ast.last().unwrap() {
-                ast::CondWave(ref wave, None) => (&ast[..ast.len() - 2], Some(wave)),
+                ast::CondWave(ref wave, None) => (&ast[..ast.len() - 1], Some(wave)),
                 _ => (&ast[..], None),
             };
             let conds = slice
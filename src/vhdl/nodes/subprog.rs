@@ -0,0 +1,24 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Subprogram declarations
+
+use crate::common::score::Result;
+
+use crate::add_ctx::AddContext;
+use crate::score::*;
+use crate::syntax::ast;
+
+impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
+    /// Add a subprogram declaration.
+    ///
+    /// The type of the declaration is the subprogram's signature, which is
+    /// used to resolve overloaded names and operators.
+    pub fn add_subprog_decl(&self, decl: &'ast ast::Subprog) -> Result<SubprogDeclRef> {
+        let (mk, id, scope) = self.make(decl.span);
+        self.ctx.set_ast(id, (scope, decl));
+        mk.typeval(Box::new(move |tyc| {
+            tyc.ctx.subprog_spec_ty(scope, &decl.spec)
+        }));
+        Ok(mk.finish())
+    }
+}
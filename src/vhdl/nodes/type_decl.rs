@@ -6,6 +6,7 @@ use crate::common::errors::*;
 use crate::common::name::Name;
 use crate::common::score::{NodeRef, Result};
 use crate::common::source::Spanned;
+use crate::common::util::{HasDesc, HasSpan};
 
 use num::BigInt;
 
@@ -18,9 +19,57 @@ use crate::term::{Term, TermContext};
 impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
     /// Add a type declaration.
     pub fn add_type_decl(&self, decl: &'ast ast::TypeDecl) -> Result<TypeDeclRef> {
+        let id = self.add_unscoped_type_decl(decl)?;
+        // A protected type body completes an earlier protected type
+        // declaration and does not introduce a name of its own.
+        match decl.data {
+            Some(Spanned {
+                value: ast::ProtectedBody(..),
+                ..
+            }) => (),
+            _ => self
+                .ctx
+                .define(self.scope, decl.name.map_into(), Def::Type(id))?,
+        }
+        // Enumeration literals and physical units are visible alongside the
+        // type. See IEEE 1076-2008 section 12.3.
+        match decl.data {
+            Some(Spanned {
+                value: ast::EnumType(ref paren_elems),
+                ..
+            }) => {
+                for (i, lit) in paren_elems.value.iter().enumerate() {
+                    if let ast::NameExpr(ref name) = lit.expr.data {
+                        let name = self.ctx.resolvable_from_primary_name(&name.primary)?;
+                        self.ctx
+                            .define(self.scope, name, Def::Enum(EnumRef(id, i)))?;
+                    }
+                }
+            }
+            Some(Spanned {
+                value: ast::RangeType(_, Some(ref units)),
+                ..
+            }) => {
+                for (i, unit) in units.iter().enumerate() {
+                    self.ctx.define(
+                        self.scope,
+                        Spanned::new(unit.0.name.into(), unit.0.span),
+                        Def::Unit(UnitRef(id, i)),
+                    )?;
+                }
+            }
+            _ => (),
+        }
+        Ok(id)
+    }
+
+    /// Add a type declaration without defining its name in the scope.
+    ///
+    /// Architectures, processes, and subprograms collect their definitions
+    /// from the declarations themselves, so only the lowering and type
+    /// checking of the declaration is scheduled here.
+    pub fn add_unscoped_type_decl(&self, decl: &'ast ast::TypeDecl) -> Result<TypeDeclRef> {
         let (mk, id, scope) = self.make(decl.span);
-        self.ctx
-            .define(scope, decl.name.map_into(), Def::Type(id))?;
         mk.lower_to_hir(Box::new(move |sbc| {
             let ctx = AddContext::new(sbc, scope);
             Ok(hir::TypeDecl {
@@ -272,9 +321,68 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> AddContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 hir::TypeData::Record(fields)
             }
 
-            ast::ProtectedType(..) => {
-                self.emit(DiagBuilder2::fatal("protected types not implemented").span(name.span));
-                return Err(());
+            ast::ProtectedType(ref items) => {
+                let mut had_fails = false;
+                let mut methods = Vec::new();
+                for item in items {
+                    match *item {
+                        ast::DeclItem::SubprogDecl(
+                            ref decl @ ast::Subprog {
+                                data: ast::SubprogData::Decl,
+                                ..
+                            },
+                        ) => match self.add_subprog_decl(decl) {
+                            Ok(m) => methods.push(m),
+                            Err(()) => had_fails = true,
+                        },
+                        // Use clauses and attribute specifications are allowed,
+                        // but have no bearing on the methods of the type.
+                        ast::DeclItem::UseClause(..) => (),
+                        ast::DeclItem::AttrDecl(ast::AttrDecl {
+                            data: ast::AttrData::Spec { .. },
+                            ..
+                        }) => (),
+                        ref wrong => {
+                            self.emit(
+                                DiagBuilder2::error(format!(
+                                    "a {} cannot appear in a protected type declaration",
+                                    wrong.desc()
+                                ))
+                                .span(wrong.human_span())
+                                .add_note(
+                                    "Only subprogram declarations, use clauses, and attribute \
+                                     specifications can appear in a protected type declaration. \
+                                     See IEEE 1076-2008 section 5.6.2.",
+                                ),
+                            );
+                            had_fails = true;
+                        }
+                    }
+                }
+                if had_fails {
+                    return Err(());
+                }
+                hir::TypeData::Protected(methods)
+            }
+
+            ast::ProtectedBody(ref items) => {
+                // Only the specifications of the subprogram bodies are of
+                // interest here, since they have to match the methods of the
+                // protected type declaration. The remaining items are local to
+                // the body.
+                let methods = items
+                    .iter()
+                    .filter_map(|item| match *item {
+                        ast::DeclItem::SubprogDecl(
+                            ref decl @ ast::Subprog {
+                                data: ast::SubprogData::Body { .. },
+                                ..
+                            },
+                        ) => Some(self.add_subprog_decl(decl)),
+                        _ => None,
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                hir::TypeData::ProtectedBody(methods)
             }
         };
         Ok(Spanned::new(td, data.span))
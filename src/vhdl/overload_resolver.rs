@@ -5,14 +5,15 @@
 #![deny(missing_docs)]
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use crate::common::errors::*;
 use crate::common::name::Name;
 use crate::common::score::Result;
-use crate::common::source::{Span, Spanned};
+use crate::common::source::{Span, Spanned, INVALID_SPAN};
 
 use crate::score::{Def, ScoreContext};
-use crate::ty::Ty;
+use crate::ty::{SubprogTy, Ty};
 
 /// A type requirement on an overloaded entity.
 ///
@@ -29,10 +30,10 @@ pub enum OverloadReq<'ctx> {
 
 impl<'ctx> OverloadReq<'ctx> {
     /// Check if a type matches this requirement.
-    pub fn matches(&self, ty: &Ty) -> bool {
+    pub fn matches(&self, ctx: &ScoreContext, ty: &Ty) -> bool {
         match *self {
-            OverloadReq::Enum(ref req) => req.matches(ty),
-            OverloadReq::Subprog(ref req) => req.matches(ty),
+            OverloadReq::Enum(ref req) => req.matches(ctx, ty),
+            OverloadReq::Subprog(ref req) => req.matches(ctx, ty),
         }
     }
 }
@@ -40,8 +41,8 @@ impl<'ctx> OverloadReq<'ctx> {
 /// A signature requirement on an overloaded entity.
 #[derive(Debug)]
 pub struct SignatureReq<'ctx> {
-    /// The required return type.
-    pub return_type: TypeReq<'ctx>,
+    /// The required return type, or `None` if a procedure is required.
+    pub return_type: Option<TypeReq<'ctx>>,
     /// The required type of the positional arguments.
     pub positional: Vec<TypeReq<'ctx>>,
     /// The required type of the named arguments.
@@ -50,15 +51,9 @@ pub struct SignatureReq<'ctx> {
 
 impl<'ctx> SignatureReq<'ctx> {
     /// Check if a type matches this requirement.
-    pub fn matches(&self, ty: &Ty) -> bool {
+    pub fn matches(&self, ctx: &ScoreContext, ty: &Ty) -> bool {
         if let Ty::Subprog(ref ty) = *ty {
-            if !self.return_type.is_any()
-                && !ty
-                    .ret
-                    .as_ref()
-                    .map(|t| self.return_type.matches(t))
-                    .unwrap_or(false)
-            {
+            if !self.matches_return_type(ctx, ty) {
                 debugln!("return type mismatch: {} vs {:?}", ty, self);
                 return false;
             }
@@ -69,7 +64,7 @@ impl<'ctx> SignatureReq<'ctx> {
             let mut arg_iter = ty.args.iter();
             for req in &self.positional {
                 let arg = arg_iter.next().unwrap(); // never fails due to above check
-                if !req.matches(&arg.ty) {
+                if !req.matches(ctx, &arg.ty) {
                     debugln!(
                         "positional mismatch: {} vs {:?} in {} vs {:?}",
                         arg.ty,
@@ -101,7 +96,7 @@ impl<'ctx> SignatureReq<'ctx> {
                         return false;
                     }
                 };
-                if !req.matches(&arg.ty) {
+                if !req.matches(ctx, &arg.ty) {
                     debugln!(
                         "named mismatch `{}`: {} vs {:?} in {} vs {:?}",
                         name,
@@ -121,6 +116,26 @@ impl<'ctx> SignatureReq<'ctx> {
     }
 }
 
+impl<'ctx> SignatureReq<'ctx> {
+    /// Check if the return type of a subprogram matches this requirement.
+    ///
+    /// Procedures never match a specific return type, and functions never
+    /// match if a procedure is required.
+    pub fn matches_return_type(&self, ctx: &ScoreContext, ty: &SubprogTy) -> bool {
+        match self.return_type {
+            Some(ref req) => {
+                req.is_any()
+                    || ty
+                        .ret
+                        .as_ref()
+                        .map(|t| req.matches(ctx, t))
+                        .unwrap_or(false)
+            }
+            None => ty.ret.is_none(),
+        }
+    }
+}
+
 /// A type requirement on an overloaded entity.
 #[derive(Debug)]
 pub enum TypeReq<'ctx> {
@@ -142,11 +157,30 @@ impl<'ctx> TypeReq<'ctx> {
     }
 
     /// Check if a type matches this requirement.
-    pub fn matches(&self, ty: &Ty) -> bool {
+    pub fn matches(&self, ctx: &ScoreContext, ty: &Ty) -> bool {
         match *self {
             TypeReq::Any => true,
-            TypeReq::One(req) => are_types_matching(req, ty),
-            TypeReq::Many(ref reqs) => reqs.iter().any(|&req| are_types_matching(req, ty)),
+            TypeReq::One(req) => are_types_matching(ctx, req, ty),
+            TypeReq::Many(ref reqs) => reqs.iter().any(|&req| are_types_matching(ctx, req, ty)),
+        }
+    }
+}
+
+impl<'ctx> fmt::Display for TypeReq<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TypeReq::Any => write!(f, "any type"),
+            TypeReq::One(ty) => write!(f, "type {}", ty),
+            TypeReq::Many(ref tys) => {
+                write!(f, "any of the types ")?;
+                for (i, ty) in tys.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -158,86 +192,187 @@ impl<'ctx> Default for TypeReq<'ctx> {
 }
 
 /// Check if two types match.
-fn are_types_matching(a: &Ty, b: &Ty) -> bool {
+///
+/// Constraints are ignored, such that an `unsigned(7 downto 0)` operand
+/// matches an `unsigned` parameter. Array types are distinguished by their
+/// declaration if they have one, such that `signed` and `unsigned` do not
+/// match even though they have the same structure.
+pub fn are_types_matching(ctx: &ScoreContext, a: &Ty, b: &Ty) -> bool {
+    // Overload resolution only considers base types, so a subtype matches
+    // the type it constrains. See IEEE 1076-2008 section 12.5.
+    if let (&Ty::Named(_, ia), &Ty::Named(_, ib)) = (a, b) {
+        if ia == ib {
+            return true;
+        }
+        if let (Ok(ta), Ok(tb)) = (ctx.base_type_mark(ia), ctx.base_type_mark(ib)) {
+            return ta == tb;
+        }
+    }
+    let anonymous = !is_named(a) || !is_named(b);
+    let (a, b) = match (ctx.deref_named_type(a), ctx.deref_named_type(b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        _ => return false,
+    };
     match (a, b) {
-        (&Ty::Named(_, ia), &Ty::Named(_, ib)) => ia == ib,
+        // Universal integers are implicitly converted to any integer type.
+        // See IEEE 1076-2008 section 9.3.6.
+        (&Ty::Int(_), &Ty::UniversalInt) | (&Ty::UniversalInt, &Ty::Int(_)) => true,
+        // TODO: Anonymous integer subtypes such as `INTEGER range 0 to 7`
+        // carry no reference to the type they constrain, so they match any
+        // integer type.
+        (&Ty::Int(_), &Ty::Int(_)) => anonymous,
+        (&Ty::Enum(ref a), &Ty::Enum(ref b)) => a.decl == b.decl,
+        (&Ty::Physical(ref a), &Ty::Physical(ref b)) => a.decl == b.decl,
+        (&Ty::Range(ref a), &Ty::Range(ref b)) => are_types_matching(ctx, &a.bound, &b.bound),
+        (&Ty::Array(ref a), &Ty::Array(ref b)) => match (a.decl, b.decl) {
+            (Some(da), Some(db)) => da == db,
+            _ => {
+                a.indices.len() == b.indices.len()
+                    && are_types_matching(ctx, &a.element, &b.element)
+            }
+        },
         (a, b) => a == b,
     }
 }
 
-/// Reduce overloaded definitions.
-pub fn reduce_overloads(
-    ctx: &ScoreContext,
+/// Check whether a type refers to a type declaration by name.
+fn is_named(ty: &Ty) -> bool {
+    match *ty {
+        Ty::Named(..) => true,
+        _ => false,
+    }
+}
+
+/// Determine the definitions that can possibly apply to a requirement,
+/// together with their type.
+fn candidates<'ctx>(
+    ctx: &ScoreContext<'_, '_, '_, 'ctx>,
     defs: &[Spanned<Def>],
     req: &OverloadReq,
-    _span: Span,
-) -> Result<Vec<Spanned<Def>>> {
-    debugln!("resolving overloaded {:?} with requirement {:?}", defs, req);
-
+) -> Result<Vec<(Spanned<Def>, &'ctx Ty)>> {
     // Filter the definitions by kind such that only those remain which have any
     // chance of applying to the requirement.
-    let filtered: Vec<_> = defs
-        .iter()
-        .enumerate()
-        .filter(|&(_, def)| match (def.value, req) {
+    defs.iter()
+        .filter(|def| match (def.value, req) {
             (Def::Enum(..), &OverloadReq::Enum(..)) => true,
             (Def::BuiltinOp(..), &OverloadReq::Subprog(..)) => true,
             (Def::Subprog(..), &OverloadReq::Subprog(..)) => true,
+            (Def::Alias(id), &OverloadReq::Subprog(..)) => ctx.is_subprog_alias(id),
             _ => false,
         })
-        .collect();
-    if filtered.is_empty() {
-        return Ok(vec![]);
-    }
-
-    // Determine the type of the applicable definitions.
-    let types = filtered
-        .iter()
-        .map(|&(i, def)| {
+        .map(|&def| {
             Ok((
-                i,
+                def,
                 match def.value {
                     Def::Enum(id) => ctx.lazy_typeval(id)?,
                     Def::BuiltinOp(id) => ctx.lazy_typeval(id)?,
                     Def::Subprog(id) => ctx.lazy_typeval(id)?,
+                    Def::Alias(id) => ctx.ty(id)?,
                     _ => unreachable!(),
                 },
             ))
         })
         .collect::<Vec<Result<_>>>()
         .into_iter()
-        .collect::<Result<Vec<_>>>()?;
+        .collect()
+}
 
-    // Match each of the types against the requirement.
-    let matched = types
-        .into_iter()
-        .filter_map(|(i, ty)| if req.matches(ty) { Some(defs[i]) } else { None })
-        .collect();
+/// Discard the candidates whose return type does not match a subprogram
+/// requirement.
+///
+/// This is done before the arguments are considered, such that the type
+/// context of a call can pick among overloads that only differ in their
+/// return type.
+fn prune_by_return_type<'ctx>(
+    ctx: &ScoreContext,
+    candidates: Vec<(Spanned<Def>, &'ctx Ty)>,
+    req: &OverloadReq,
+) -> Vec<(Spanned<Def>, &'ctx Ty)> {
+    match *req {
+        OverloadReq::Subprog(ref req) => candidates
+            .into_iter()
+            .filter(|&(_, ty)| match *ty {
+                Ty::Subprog(ref ty) => req.matches_return_type(ctx, ty),
+                _ => false,
+            })
+            .collect(),
+        OverloadReq::Enum(..) => candidates,
+    }
+}
+
+/// Add the given candidates and their types to a diagnostic.
+fn list_candidates(mut diag: DiagBuilder2, candidates: &[(Spanned<Def>, &Ty)]) -> DiagBuilder2 {
+    for &(def, ty) in candidates {
+        diag = diag.add_note(format!("candidate: {}", ty));
+        if def.span != INVALID_SPAN {
+            diag = diag.span(def.span);
+        }
+    }
+    diag
+}
 
-    Ok(matched)
+/// Reduce overloaded definitions.
+pub fn reduce_overloads(
+    ctx: &ScoreContext,
+    defs: &[Spanned<Def>],
+    req: &OverloadReq,
+    _span: Span,
+) -> Result<Vec<Spanned<Def>>> {
+    debugln!("resolving overloaded {:?} with requirement {:?}", defs, req);
+    let candidates = prune_by_return_type(ctx, candidates(ctx, defs, req)?, req);
+    Ok(candidates
+        .into_iter()
+        .filter(|&(_, ty)| req.matches(ctx, ty))
+        .map(|(def, _)| def)
+        .collect())
 }
 
 /// Resolve overloaded definitions to exactly one unambiguous definition.
+///
+/// Candidates are first pruned by their return type, and only then by their
+/// arguments. If no candidate or more than one candidate remains, an error
+/// listing the relevant candidates is emitted.
 pub fn resolve_overloads(
     ctx: &ScoreContext,
     defs: &[Spanned<Def>],
     req: &OverloadReq,
     span: Span,
 ) -> Result<Spanned<Def>> {
-    let reduced = reduce_overloads(ctx, defs, req, span)?;
-    if reduced.is_empty() {
-        ctx.emit(
-            DiagBuilder2::error("no overload applies").span(span), // TODO: Show available implementations.
-        );
-        debugln!("available definitions: {:#?}", defs);
+    debugln!("resolving overloaded {:?} with requirement {:?}", defs, req);
+    let all = candidates(ctx, defs, req)?;
+    let by_return = prune_by_return_type(ctx, all.clone(), req);
+    if by_return.is_empty() && !all.is_empty() {
+        let msg = match *req {
+            OverloadReq::Subprog(SignatureReq {
+                return_type: Some(ref ret),
+                ..
+            }) => format!("no overload of `{}` returns {}", span.extract(), ret),
+            OverloadReq::Subprog(..) => {
+                format!("no overload of `{}` is a procedure", span.extract())
+            }
+            OverloadReq::Enum(..) => unreachable!(),
+        };
+        ctx.emit(list_candidates(DiagBuilder2::error(msg).span(span), &all));
+        return Err(());
+    }
+    let mut matched: Vec<_> = by_return
+        .iter()
+        .cloned()
+        .filter(|&(_, ty)| req.matches(ctx, ty))
+        .collect();
+    if matched.is_empty() {
+        ctx.emit(list_candidates(
+            DiagBuilder2::error(format!("no overload of `{}` applies", span.extract())).span(span),
+            &by_return,
+        ));
         Err(())
-    } else if reduced.len() > 1 {
-        ctx.emit(
-            DiagBuilder2::error(format!("`{}` is ambiguous", span.extract())).span(span), // TODO: Show implementations that matched.
-        );
-        debugln!("matching definitions: {:#?}", reduced);
+    } else if matched.len() > 1 {
+        ctx.emit(list_candidates(
+            DiagBuilder2::error(format!("`{}` is ambiguous", span.extract())).span(span),
+            &matched,
+        ));
         Err(())
     } else {
-        Ok(reduced.into_iter().next().unwrap())
+        Ok(matched.pop().unwrap().0)
     }
 }
@@ -2,6 +2,8 @@
 
 //! This module implements constant value computation.
 
+use num::{BigInt, One, ToPrimitive, Zero};
+
 use crate::score::*;
 
 // Calculate the constant value of an expression.
@@ -9,11 +11,34 @@ impl_make!(self, id: ExprRef => &Const {
     let hir = self.lazy_hir(id)?;
     Ok(match hir.data {
         // Integer literals.
-        hir::ExprData::IntegerLiteral(ref c) => self.intern_const(c.clone()),
+        hir::ExprData::IntegerLiteral(ref c) => {
+            if c.value.to_i64().is_none() {
+                self.emit(
+                    DiagBuilder2::error("integer value out of range")
+                    .span(hir.span)
+                    .add_note(format!("Universal integers are limited to the range {} to {}.", std::i64::MIN, std::i64::MAX))
+                );
+                return Err(());
+            }
+            self.intern_const(c.clone())
+        }
 
         // Float literals.
         hir::ExprData::FloatLiteral(ref c) => self.intern_const(c.clone()),
 
+        // Physical literals, normalized to the primary unit.
+        hir::ExprData::PhysLiteral(ref value, unit) => {
+            match *self.deref_named_type(self.ty(unit.value.0)?)? {
+                Ty::Physical(ref ty) => self.intern_const(ConstPhysical::new(
+                    ty.decl,
+                    value.clone(),
+                    unit.value.1,
+                    ty.units[unit.value.1].abs.clone(),
+                )),
+                _ => unreachable!(),
+            }
+        }
+
         // Unary operators.
         hir::ExprData::Unary(op, _, arg_id) => {
             let arg = self.const_value(arg_id)?;
@@ -55,6 +80,150 @@ impl_make!(self, id: ExprRef => &Const {
             }
         }
 
+        // Integer and physical binary operators.
+        hir::ExprData::Binary(op, _, lhs_id, rhs_id) => {
+            let lhs = self.const_value(lhs_id)?;
+            let rhs = self.const_value(rhs_id)?;
+
+            // Physical values are compared in their primary unit.
+            if let BinaryOp::Rel(rel) = op.value {
+                let ordering = match (lhs, rhs) {
+                    (&Const::Int(ref lhs), &Const::Int(ref rhs)) => Some(lhs.value.cmp(&rhs.value)),
+                    (&Const::Physical(ref lhs), &Const::Physical(ref rhs)) => lhs.partial_cmp(rhs),
+                    _ => None,
+                };
+                if let Some(ordering) = ordering {
+                    use std::cmp::Ordering;
+                    let value = match rel {
+                        RelationalOp::Eq => ordering == Ordering::Equal,
+                        RelationalOp::Neq => ordering != Ordering::Equal,
+                        RelationalOp::Lt => ordering == Ordering::Less,
+                        RelationalOp::Leq => ordering != Ordering::Greater,
+                        RelationalOp::Gt => ordering == Ordering::Greater,
+                        RelationalOp::Geq => ordering != Ordering::Less,
+                    };
+                    return Ok(self.intern_const(ConstEnum::new(BOOLEAN_TYPE.id, value as usize)));
+                }
+            }
+
+            match (lhs, rhs) {
+                (&Const::Physical(ref lhs), &Const::Physical(ref rhs))
+                    if lhs.decl == rhs.decl && (op.value == BinaryOp::Add || op.value == BinaryOp::Sub) =>
+                {
+                    let value = if op.value == BinaryOp::Add {
+                        lhs.normalized() + rhs.normalized()
+                    } else {
+                        lhs.normalized() - rhs.normalized()
+                    };
+                    let primary = match *self.ty(lhs.decl)? {
+                        Ty::Physical(ref ty) => ty.primary,
+                        _ => unreachable!(),
+                    };
+                    self.intern_const(ConstPhysical::new(lhs.decl, value, primary, BigInt::one()))
+                }
+                (&Const::Int(ref lhs), &Const::Int(ref rhs)) => {
+                    let value = match op.value {
+                        BinaryOp::Add => &lhs.value + &rhs.value,
+                        BinaryOp::Sub => &lhs.value - &rhs.value,
+                        BinaryOp::Mul => &lhs.value * &rhs.value,
+                        BinaryOp::Div | BinaryOp::Mod | BinaryOp::Rem => {
+                            let value = match op.value {
+                                BinaryOp::Div => lhs.div(rhs),
+                                BinaryOp::Mod => lhs.modulo(rhs),
+                                _ => lhs.rem(rhs),
+                            };
+                            match value {
+                                Some(v) => v,
+                                None => {
+                                    self.emit(
+                                        DiagBuilder2::error(format!("division by zero in `{}`", hir.span.extract()))
+                                        .span(self.lazy_hir(rhs_id)?.span)
+                                    );
+                                    return Err(());
+                                }
+                            }
+                        }
+                        _ => {
+                            self.emit(
+                                DiagBuilder2::bug(format!("constant binary operator {:?} not yet implemented", op.value))
+                                .span(op.span)
+                            );
+                            return Err(());
+                        }
+                    };
+                    let ty = lhs.ty.clone().or_else(|| rhs.ty.clone());
+                    self.intern_const(ConstInt::new(ty, value))
+                }
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error("expression does not have a constant value")
+                        .span(hir.span)
+                    );
+                    return Err(());
+                }
+            }
+        }
+
+        // Bounds and direction of integer types, and bounds, direction, and
+        // length of constrained array types. Note that `'high` and `'low`
+        // depend on the direction, unlike `'left` and `'right`.
+        hir::ExprData::TypeAttr(ref tm, ref name, None) => {
+            let table = get_name_table();
+            let ty = self.intern_named_ty(tm.value);
+            let flat = self.deref_named_type(ty)?;
+            let index = match *flat {
+                Ty::Array(ref at) => match at.indices[0] {
+                    ArrayIndex::Constrained(ref index) => Some(self.deref_named_type(index)?),
+                    ArrayIndex::Unbounded(..) => {
+                        self.emit(
+                            DiagBuilder2::error(format!("`{}` requires a constrained array prefix", hir.span.extract()))
+                            .span(tm.span)
+                        );
+                        return Err(());
+                    }
+                },
+                _ => None,
+            };
+            let it = match *index.unwrap_or(flat) {
+                Ty::Int(ref it) => it,
+                _ => {
+                    self.emit(
+                        DiagBuilder2::bug(format!("constant value of `{}` not yet implemented", hir.span.extract()))
+                        .span(hir.span)
+                    );
+                    return Err(());
+                }
+            };
+            let (low, high) = match it.dir {
+                Dir::To => (&it.left_bound, &it.right_bound),
+                Dir::Downto => (&it.right_bound, &it.left_bound),
+            };
+            if index.is_some() && name.value == table.intern("LENGTH", false).into() {
+                let len = std::cmp::max(high - low + BigInt::one(), BigInt::zero());
+                return Ok(self.intern_const(ConstInt::new(None, len)));
+            }
+            if name.value == table.intern("ASCENDING", false).into() {
+                let ascending = it.dir == Dir::To;
+                return Ok(self.intern_const(ConstEnum::new(BOOLEAN_TYPE.id, ascending as usize)));
+            }
+            let value = if name.value == table.intern("LEFT", false).into() {
+                &it.left_bound
+            } else if name.value == table.intern("RIGHT", false).into() {
+                &it.right_bound
+            } else if name.value == table.intern("HIGH", false).into() {
+                high
+            } else if name.value == table.intern("LOW", false).into() {
+                low
+            } else {
+                self.emit(
+                    DiagBuilder2::error("expression does not have a constant value")
+                    .span(hir.span)
+                );
+                return Err(());
+            };
+            self.intern_const(ConstInt::new(Some(it.clone()), value.clone()))
+        }
+
         // Names.
         hir::ExprData::Name(Def::Enum(EnumRef(decl, index)), _) => self.intern_const(ConstEnum::new(decl, index)),
 
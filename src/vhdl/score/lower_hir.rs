@@ -8,6 +8,7 @@
 
 use crate::add_ctx::AddContext;
 use crate::common::score::NodeRef;
+use crate::lazy::LazyNode;
 use crate::make_ctx::MakeContext;
 use crate::op::*;
 use crate::score::*;
@@ -123,9 +124,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             match *decl {
                 ast::DeclItem::SubprogDecl(ref decl) => match decl.data {
                     ast::SubprogData::Decl => {
-                        let subid = SubprogDeclRef(NodeId::alloc());
-                        self.set_ast(subid, (scope_id, decl));
-                        refs.push(subid.into());
+                        refs.push(ctx.add_subprog_decl(decl)?.into());
                     }
                     ast::SubprogData::Body { .. } => {
                         let subid = SubprogBodyRef(NodeId::alloc());
@@ -154,7 +153,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     refs.push(subid.into());
                 }
                 ast::DeclItem::TypeDecl(ref decl) => {
-                    let subid = TypeDeclRef(NodeId::alloc());
+                    let subid = ctx.add_unscoped_type_decl(decl)?;
                     self.set_ast(subid, (scope_id, decl));
                     refs.push(subid.into());
                 }
@@ -271,9 +270,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             match *decl {
                 ast::DeclItem::SubprogDecl(ref decl) => match decl.data {
                     ast::SubprogData::Decl => {
-                        let subid = SubprogDeclRef(NodeId::alloc());
-                        self.set_ast(subid, (scope_id, decl));
-                        refs.push(subid.into());
+                        refs.push(ctx.add_subprog_decl(decl)?.into());
                     }
                     ast::SubprogData::Body { .. } => {
                         let subid = SubprogBodyRef(NodeId::alloc());
@@ -302,7 +299,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     refs.push(subid.into());
                 }
                 ast::DeclItem::TypeDecl(ref decl) => {
-                    let subid = TypeDeclRef(NodeId::alloc());
+                    let subid = ctx.add_unscoped_type_decl(decl)?;
                     self.set_ast(subid, (scope_id, decl));
                     refs.push(subid.into());
                 }
@@ -410,9 +407,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             match *decl {
                 ast::DeclItem::SubprogDecl(ref decl) => match decl.data {
                     ast::SubprogData::Decl => {
-                        let subid = SubprogDeclRef(NodeId::alloc());
-                        self.set_ast(subid, (scope_id, decl));
-                        refs.push(subid.into());
+                        refs.push(ctx.add_subprog_decl(decl)?.into());
                     }
                     ast::SubprogData::Body { .. } => {
                         let subid = SubprogBodyRef(NodeId::alloc());
@@ -441,7 +436,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     refs.push(subid.into());
                 }
                 ast::DeclItem::TypeDecl(ref decl) => {
-                    let subid = TypeDeclRef(NodeId::alloc());
+                    let subid = ctx.add_unscoped_type_decl(decl)?;
                     self.set_ast(subid, (scope_id, decl));
                     refs.push(subid.into());
                 }
@@ -548,36 +543,77 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         for stmt in stmts {
             match stmt.data {
                 ast::BlockStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                    let id = BlockStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
+                }
+                ast::InstOrCallStmt {
+                    target: None,
+                    generics: None,
+                    ports: None,
+                    ..
+                } => {
+                    let id = ConcCallStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
+                }
+                ast::InstOrCallStmt { target: None, .. }
+                | ast::InstOrCallStmt {
+                    target: Some(ast::InstTarget::Comp),
+                    ..
+                } => {
+                    let id = CompInstStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
                 }
                 ast::InstOrCallStmt { .. } => {
                     unimp(stmt);
                     had_fails = true;
                 }
                 ast::AssertStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                    let id = ConcAssertStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
                 }
-                ast::AssignStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                ast::AssignStmt {
+                    kind: ast::AssignKind::Signal,
+                    ..
+                }
+                | ast::SelectAssignStmt {
+                    kind: ast::AssignKind::Signal,
+                    ..
+                } => {
+                    let id = ConcSigAssignStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
                 }
-                ast::SelectAssignStmt { .. } => {
+                ast::AssignStmt { .. } | ast::SelectAssignStmt { .. } => {
                     unimp(stmt);
                     had_fails = true;
                 }
                 ast::IfGenStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                    let id = IfGenStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
                 }
                 ast::CaseGenStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                    let id = CaseGenStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    refs.push(id.into());
                 }
                 ast::ForGenStmt { .. } => {
-                    unimp(stmt);
-                    had_fails = true;
+                    let id = ForGenStmtRef(NodeId::alloc());
+                    self.set_ast(id, (scope_id, stmt));
+                    // The generate parameter takes on the type of the range's
+                    // elements. See IEEE 1076-2008 section 11.8.
+                    self.lazy.typeval.borrow_mut().insert(
+                        id.into(),
+                        LazyNode::Pending(Box::new(move |tyc| {
+                            let hir = tyc.ctx.hir(id)?;
+                            tyc.typeval_range_element(hir.range.as_ref())
+                        })),
+                    );
+                    refs.push(id.into());
                 }
 
                 ast::ProcStmt { .. } => {
@@ -633,51 +669,152 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         target: &'ast ast::AssignTarget,
     ) -> Result<hir::SigAssignTarget> {
         match *target {
-            ast::AssignTarget::Name(ref name) => {
-                let (_res_name, mut defs, res_span, tail) =
-                    self.resolve_compound_name(name, scope_id, false)?;
-                if !tail.is_empty() {
-                    self.emit(
-                        DiagBuilder2::bug(
-                            "handling of non-name signal assignment targets not implemented",
-                        )
-                        .span(name.span),
-                    );
-                    return Err(());
+            ast::AssignTarget::Name(ref name) => self.unpack_signal_assign_name(scope_id, name),
+            ast::AssignTarget::Aggregate(ref elems) => {
+                self.unpack_aggregate_signal_assign_target(scope_id, elems)
+            }
+        }
+    }
+
+    /// Unpack a signal assignment target given as a name.
+    fn unpack_signal_assign_name(
+        &self,
+        scope_id: ScopeRef,
+        name: &'ast ast::CompoundName,
+    ) -> Result<hir::SigAssignTarget> {
+        let (_res_name, mut defs, res_span, tail) =
+            self.resolve_compound_name(name, scope_id, false)?;
+        let sig = match defs.pop() {
+            Some(Spanned {
+                value: Def::Signal(id),
+                ..
+            }) => id,
+            Some(_) => {
+                self.emit(
+                    DiagBuilder2::error(format!("`{}` is not a signal", res_span.extract()))
+                        .span(res_span),
+                );
+                return Err(());
+            }
+            None => unreachable!(),
+        };
+        if !defs.is_empty() {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is ambiguous", res_span.extract()))
+                    .span(res_span),
+            );
+            return Err(());
+        }
+        match *tail {
+            [] => Ok(hir::SigAssignTarget::Name(sig)),
+            [ast::NamePart::Call(ref elems)] => {
+                self.unpack_indexed_signal_assign_target(scope_id, sig, elems)
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::bug(
+                        "handling of non-name signal assignment targets not implemented",
+                    )
+                    .span(name.span),
+                );
+                Err(())
+            }
+        }
+    }
+
+    /// Unpack an aggregate signal assignment target, e.g. `(a, b)`.
+    ///
+    /// Each element must itself be a signal name or an aggregate thereof. Only
+    /// positional elements are supported. See IEEE 1076-2008 section 10.5.2.1.
+    fn unpack_aggregate_signal_assign_target(
+        &self,
+        scope_id: ScopeRef,
+        elems: &'ast ast::ParenElems,
+    ) -> Result<hir::SigAssignTarget> {
+        let mut had_fails = false;
+        let mut targets = Vec::new();
+        for elem in &elems.value {
+            if !elem.choices.value.is_empty() {
+                self.emit(
+                    DiagBuilder2::bug(
+                        "named elements in aggregate signal assignment targets not implemented",
+                    )
+                    .span(elem.span),
+                );
+                had_fails = true;
+                continue;
+            }
+            let target = match elem.expr.data {
+                ast::NameExpr(ref name) => self.unpack_signal_assign_name(scope_id, name),
+                ast::ParenExpr(ref elems) => {
+                    self.unpack_aggregate_signal_assign_target(scope_id, elems)
                 }
-                let sig = match defs.pop() {
-                    Some(Spanned {
-                        value: Def::Signal(id),
-                        ..
-                    }) => id,
-                    Some(_) => {
-                        self.emit(
-                            DiagBuilder2::error(format!(
-                                "`{}` is not a signal",
-                                res_span.extract()
-                            ))
-                            .span(res_span),
-                        );
-                        return Err(());
-                    }
-                    None => unreachable!(),
-                };
-                if !defs.is_empty() {
+                _ => {
                     self.emit(
-                        DiagBuilder2::error(format!("`{}` is ambiguous", res_span.extract()))
-                            .span(res_span),
+                        DiagBuilder2::error(format!(
+                            "`{}` is not a signal",
+                            elem.expr.span.extract()
+                        ))
+                        .span(elem.expr.span),
                     );
-                    return Err(());
+                    Err(())
                 }
-                Ok(hir::SigAssignTarget::Name(sig))
+            };
+            match target {
+                Ok(target) => targets.push(Spanned::new(target, elem.expr.span)),
+                Err(()) => had_fails = true,
             }
-            ast::AssignTarget::Aggregate(ref elems) => {
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(hir::SigAssignTarget::Aggregate(targets))
+        }
+    }
+
+    /// Unpack an indexed or sliced signal assignment target.
+    ///
+    /// A single range in parentheses selects a slice of the signal, e.g.
+    /// `a(3 downto 0)`. Otherwise the expressions are the indices of an
+    /// element, e.g. `a(i)`. See IEEE 1076-2008 sections 8.4 and 8.5.
+    fn unpack_indexed_signal_assign_target(
+        &self,
+        scope_id: ScopeRef,
+        sig: SignalRef,
+        elems: &'ast ast::ParenElems,
+    ) -> Result<hir::SigAssignTarget> {
+        let ctx = TermContext::new(self, scope_id);
+        let term = ctx.termify_paren_elems(elems)?;
+        let mut terms = match term.value {
+            Term::Paren(terms) => terms,
+            Term::Aggregate(..) => {
                 self.emit(
-                    DiagBuilder2::error("aggregate signal assignment not implemented")
-                        .span(elems.span),
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid index or slice",
+                        elems.span.extract()
+                    ))
+                    .span(elems.span),
                 );
-                Err(())
+                return Err(());
             }
+            _ => vec![term],
+        };
+        let is_slice = match terms.as_slice() {
+            [Spanned {
+                value: Term::Range(..),
+                ..
+            }] => true,
+            _ => false,
+        };
+        if is_slice {
+            let range = ctx.term_to_discrete_range(terms.pop().unwrap())?;
+            Ok(hir::SigAssignTarget::Slice(sig, range))
+        } else {
+            let indices = terms
+                .into_iter()
+                .map(|t| ctx.term_to_expr(t))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(hir::SigAssignTarget::Index(sig, indices))
         }
     }
 
@@ -737,7 +874,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 ast::DelayMech::Transport => hir::DelayMechanism::Transport,
                 ast::DelayMech::Inertial => hir::DelayMechanism::Inertial,
                 ast::DelayMech::InertialReject(ref expr) => {
-                    let expr = self.unpack_expr(expr, scope_id)?;
+                    let expr = AddContext::new(self, scope_id).add_expr(expr)?;
                     hir::DelayMechanism::RejectInertial(expr)
                 }
             })
@@ -746,27 +883,70 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
-    /// Unpack the the waves of a simple wave assignment.
+    /// Unpack the waves of a selected wave assignment.
+    ///
+    /// See IEEE 1076-2008 section 10.5.4.
+    pub fn unpack_sel_waveforms(
+        &self,
+        scope_id: ScopeRef,
+        select: &'ast ast::Expr,
+        matching: bool,
+        waves: &'ast [ast::SelectWave],
+        tyctx: &TypeCtx<'ctx>,
+    ) -> Result<hir::Sel<hir::Waveform>> {
+        let ctx = AddContext::new(self, scope_id);
+        let disc = ctx.add_expr(select);
+        let when = waves
+            .iter()
+            .map(|&ast::SelectWave(ref wave, ref choices)| {
+                let wave = self.unpack_waveform(scope_id, wave, tyctx);
+                let choices = ctx.add_choices(choices.as_ref().map(|c| c.iter()));
+                Ok((wave?, choices?))
+            })
+            .collect::<Vec<Result<_>>>()
+            .into_iter()
+            .collect::<Result<Vec<_>>>();
+        Ok(hir::Sel {
+            matching: matching,
+            disc: disc?,
+            when: when?,
+        })
+    }
+
+    /// Unpack the waves of a conditional signal assignment.
+    ///
+    /// See IEEE 1076-2008 section 10.5.3.
     pub fn unpack_cond_waveforms(
         &self,
-        _scope_id: ScopeRef,
+        scope_id: ScopeRef,
         waves: &'ast [ast::CondWave],
-        _tyctx: &TypeCtx<'ctx>,
+        tyctx: &TypeCtx<'ctx>,
     ) -> Result<hir::Cond<hir::Waveform>> {
         // Determine if we have a "else".
-        let (_when, _other) = if waves.last().unwrap().1.is_some() {
+        let (when, other) = if waves.last().unwrap().1.is_some() {
             (&waves[..], None)
         } else {
-            (&waves[..waves.len() - 1], Some(&waves.last().unwrap().1))
+            (&waves[..waves.len() - 1], Some(&waves.last().unwrap().0))
         };
-        // for w in when {
-
-        // }
-        // let o = match other {
-        //     Some(o) => Some(self.unpack_waveform(scope_id, other)?),
-        //     None => None,
-        // };
-        unimp!(self, "conditional waveforms");
+        let ctx = AddContext::new(self, scope_id);
+        let when = when
+            .iter()
+            .map(|&ast::CondWave(ref wave, ref cond)| {
+                let wave = self.unpack_waveform(scope_id, wave, tyctx);
+                let cond = ctx.add_expr(cond.as_ref().unwrap());
+                Ok((wave?, cond?))
+            })
+            .collect::<Vec<Result<_>>>()
+            .into_iter()
+            .collect::<Result<Vec<_>>>();
+        let other = match other {
+            Some(o) => Some(self.unpack_waveform(scope_id, o, tyctx)?),
+            None => None,
+        };
+        Ok(hir::Cond {
+            when: when?,
+            other: other,
+        })
     }
 
     /// Unpack a single waveform.
@@ -778,6 +958,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         wave: &'ast ast::Wave,
         tyctx: &TypeCtx<'ctx>,
     ) -> Result<hir::Waveform> {
+        let ctx = AddContext::new(self, scope_id);
         wave.elems
             .iter()
             .flat_map(|i| i.iter())
@@ -786,16 +967,16 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     value: match value.data {
                         ast::NullExpr => None,
                         _ => {
-                            let expr = self.unpack_expr(value, scope_id)?;
+                            let expr = ctx.add_expr(value)?;
                             self.set_type_context(expr, tyctx.clone());
                             Some(expr)
                         }
                     },
                     after: match *after {
                         Some(ref expr) => {
-                            let expr = self.unpack_expr(expr, scope_id)?;
-                            // TODO: Set the type context for the expression.
-                            // self.set_type_context(expr, TypeCtx::Type(/* somehow refer to builtin `time` type */));
+                            let expr = ctx.add_expr(expr)?;
+                            let time = self.intern_ty(TIME_TYPE.named_ty());
+                            self.set_type_context(expr, TypeCtx::Type(time));
                             Some(expr)
                         }
                         None => None,
@@ -865,20 +1046,13 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         if let Some(ref gc) = ast.generic_clause {
             self.unpack_generics(scope_id, gc, &mut generics)?;
         }
-        if let Some(ref gm) = ast.generic_map {
-            unimp_msg!(
-                self,
-                "lowering of generic maps in subprogram specifications",
-                gm.span
-            );
-        }
-        let generic_map = vec![];
-        if ast.params.is_some() {
-            unimp_msg!(
-                self,
-                "lowering of parameters in subprogram specifications",
-                name.span
-            );
+        let generic_map = match ast.generic_map {
+            Some(ref gm) => self.unpack_generic_map(scope_id, gm)?,
+            None => vec![],
+        };
+        let mut params = Vec::new();
+        if let Some(ref decls) = ast.params {
+            self.unpack_params(scope_id, decls, &mut params)?;
         }
         let return_type = match ast.retty {
             Some(ref name) => Some(self.unpack_type_mark(name.into(), scope_id)?),
@@ -900,7 +1074,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             kind: kind,
             generics: generics,
             generic_map: generic_map,
-            params: vec![],
+            params: params,
             return_type: return_type,
         })
     }
@@ -1002,18 +1176,249 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
+    /// Unpack ports from a list of interface declarations.
+    ///
+    /// For ports only signal interface declarations are allowed. See IEEE
+    /// 1076-2008 section 6.5.6.3.
+    pub fn unpack_ports(
+        &self,
+        scope_id: ScopeRef,
+        decls: &'ast [ast::IntfDecl],
+        into: &mut Vec<IntfSignalRef>,
+    ) -> Result<()> {
+        let ctx = AddContext::new(self, scope_id);
+        let mut had_fails = false;
+        for decl in decls {
+            match *decl {
+                ast::IntfDecl::ObjDecl(
+                    ref decl @ ast::IntfObjDecl {
+                        kind: ast::IntfObjKind::Signal,
+                        ..
+                    },
+                ) => {
+                    let ty = match ctx.add_subtype_ind(&decl.ty) {
+                        Ok(ty) => ty,
+                        Err(()) => {
+                            had_fails = true;
+                            continue;
+                        }
+                    };
+                    for name in &decl.names {
+                        let id = IntfSignalRef(NodeId::alloc());
+                        self.set_ast(id, (scope_id, decl, ty, name));
+                        into.push(id);
+                    }
+                }
+                ref wrong => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "a {} cannot appear in a port clause",
+                            wrong.desc()
+                        ))
+                        .span(wrong.human_span()),
+                    );
+                    had_fails = true;
+                }
+            }
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Unpack subprogram parameters from a list of interface declarations.
+    ///
+    /// Parameters may be constants, variables, signals, or files. See IEEE
+    /// 1076-2008 section 4.2.2.
+    pub fn unpack_params(
+        &self,
+        scope_id: ScopeRef,
+        decls: &'ast [ast::IntfDecl],
+        into: &mut Vec<IntfObjRef>,
+    ) -> Result<()> {
+        let ctx = AddContext::new(self, scope_id);
+        let mut had_fails = false;
+        for decl in decls {
+            match *decl {
+                ast::IntfDecl::ObjDecl(ref decl) => {
+                    let ty = match ctx.add_subtype_ind(&decl.ty) {
+                        Ok(ty) => ty,
+                        Err(()) => {
+                            had_fails = true;
+                            continue;
+                        }
+                    };
+                    for name in &decl.names {
+                        let id = NodeId::alloc();
+                        let value = (scope_id, decl, ty, name);
+                        into.push(match decl.kind {
+                            ast::IntfObjKind::Const => {
+                                self.set_ast(IntfConstRef(id), value);
+                                IntfConstRef(id).into()
+                            }
+                            ast::IntfObjKind::Var => {
+                                self.set_ast(IntfVarRef(id), value);
+                                IntfVarRef(id).into()
+                            }
+                            ast::IntfObjKind::Signal => {
+                                self.set_ast(IntfSignalRef(id), value);
+                                IntfSignalRef(id).into()
+                            }
+                            ast::IntfObjKind::File => {
+                                self.set_ast(IntfFileRef(id), value);
+                                IntfFileRef(id).into()
+                            }
+                        });
+                    }
+                }
+                ref wrong => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "a {} cannot appear in a parameter list",
+                            wrong.desc()
+                        ))
+                        .span(wrong.human_span()),
+                    );
+                    had_fails = true;
+                }
+            }
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Unpack a generic map from a parenthesized list of elements.
     ///
     /// See IEEE 1076-2008 section 6.5.7.2.
     pub fn unpack_generic_map(
         &self,
-        _scope_id: ScopeRef,
+        scope_id: ScopeRef,
+        elems: &'ast ast::ParenElems,
+    ) -> Result<Vec<hir::GenericMapElem>> {
+        let mut had_fails = false;
+        let mut map = Vec::new();
+        for elem in &elems.value {
+            let formal = match elem.choices.value.len() {
+                0 => None,
+                1 => match elem.choices.value[0].data {
+                    ast::NameExpr(ast::CompoundName {
+                        primary:
+                            ast::PrimaryName {
+                                kind: ast::PrimaryNameKind::Ident(name),
+                                span,
+                                ..
+                            },
+                        ref parts,
+                        ..
+                    }) if parts.is_empty() => Some(Spanned::new(name, span)),
+                    _ => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "`{}` is not a valid formal generic",
+                                elem.choices.span.extract()
+                            ))
+                            .span(elem.choices.span),
+                        );
+                        had_fails = true;
+                        continue;
+                    }
+                },
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error("generic map element must have exactly one formal")
+                            .span(elem.choices.span),
+                    );
+                    had_fails = true;
+                    continue;
+                }
+            };
+            let actual = GenericMapRef(NodeId::alloc());
+            self.set_ast(actual, (scope_id, &elem.expr));
+            map.push(hir::GenericMapElem {
+                span: elem.span,
+                formal: formal,
+                actual: actual,
+            });
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(map)
+        }
+    }
+
+    /// Unpack a port map from a parenthesized list of elements.
+    ///
+    /// See IEEE 1076-2008 section 6.5.7.3.
+    pub fn unpack_port_map(
+        &self,
+        scope_id: ScopeRef,
         elems: &'ast ast::ParenElems,
-    ) -> Result<Vec<()>> {
-        if !elems.value.is_empty() {
-            unimp_msg!(self, "generic map aspect", elems.span);
+    ) -> Result<Spanned<hir::AssocList>> {
+        let ctx = TermContext::new(self, scope_id);
+        let mut had_fails = false;
+        let mut map = Vec::new();
+        for elem in &elems.value {
+            let formal = match elem.choices.value.len() {
+                0 => None,
+                1 => match ctx
+                    .termify_expr(&elem.choices.value[0])
+                    .and_then(|term| ctx.term_to_formal_name(term))
+                {
+                    Ok(formal) => Some(formal),
+                    Err(()) => {
+                        had_fails = true;
+                        continue;
+                    }
+                },
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error("port map element must have exactly one formal")
+                            .span(elem.choices.span),
+                    );
+                    had_fails = true;
+                    continue;
+                }
+            };
+            let actual = match elem.expr.data {
+                ast::OpenExpr => hir::AssocActual::Open,
+                _ => match ctx
+                    .termify_expr(&elem.expr)
+                    .and_then(|term| ctx.term_to_expr(term))
+                {
+                    Ok(expr) => hir::AssocActual::Expr(expr),
+                    Err(()) => {
+                        had_fails = true;
+                        continue;
+                    }
+                },
+            };
+            map.push(hir::AssocElement {
+                span: elem.span,
+                formal: formal,
+                actual: Spanned::new(actual, elem.expr.span),
+            });
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(Spanned::new(map, elems.span))
         }
-        Ok(Vec::new())
+    }
+
+    /// Unpack a body of an if-generate or case-generate statement.
+    ///
+    /// The body is lowered lazily, since its declarations and statements form
+    /// a region of their own. See IEEE 1076-2008 section 11.8.
+    pub fn unpack_gen_body(&self, scope_id: ScopeRef, body: &'ast ast::GenBody) -> GenBodyRef {
+        let id = GenBodyRef(NodeId::alloc());
+        self.set_ast(id, (scope_id, body));
+        id
     }
 }
 
@@ -1085,8 +1490,8 @@ impl_make!(self, id: IntfSignalRef => &hir::IntfSignal {
     let (scope_id, decl, subty_id, ident) = self.ast(id);
     let init = match decl.default {
         Some(ref e) => {
-            let subid = ExprRef(NodeId::alloc());
-            self.set_ast(subid, (scope_id, e));
+            let subid = AddContext::new(self, scope_id).add_expr(e)?;
+            self.set_type_context(subid, TypeCtx::TypeOf(subty_id.into()));
             Some(subid)
         }
         None => None
@@ -1112,8 +1517,7 @@ impl_make!(self, id: PkgDeclRef => &hir::Package {
     let (outer_scope, ast) = self.ast(id);
     let scope = id.into();
     self.subscope(scope, outer_scope);
-    let generics = Vec::new();
-    // let generic_maps = Vec::new();
+    let mut generics = Vec::new();
     let mut decls = Vec::new();
     let mut had_fails = false;
 
@@ -1125,9 +1529,7 @@ impl_make!(self, id: PkgDeclRef => &hir::Package {
             ast::DeclItem::SubprogDecl(ref decl) => {
                 match decl.data {
                     ast::SubprogData::Decl => {
-                        let subid = SubprogDeclRef(NodeId::alloc());
-                        self.set_ast(subid, (scope, decl));
-                        decls.push(subid.into());
+                        decls.push(ctx.add_subprog_decl(decl)?.into());
                     }
                     ast::SubprogData::Body{..} => {
                         self.emit(
@@ -1156,10 +1558,9 @@ impl_make!(self, id: PkgDeclRef => &hir::Package {
                 decls.push(subid.into());
             }
             ast::DeclItem::TypeDecl(ref decl) => {
-                decls.push(ctx.add_type_decl(decl)?.into());
-                // let subid = TypeDeclRef(NodeId::alloc());
-                // self.set_ast(subid, (scope, decl));
-                // decls.push(subid.into());
+                let subid = ctx.add_type_decl(decl)?;
+                self.set_ast(subid, (scope, decl));
+                decls.push(subid.into());
             }
             ast::DeclItem::SubtypeDecl(ref decl) => {
                 let subid = SubtypeDeclRef(NodeId::alloc());
@@ -1232,6 +1633,19 @@ impl_make!(self, id: PkgDeclRef => &hir::Package {
                     }
                 }
             }
+            ast::DeclItem::PortgenClause(_, Spanned{ value: ast::PortgenKind::Generic, .. }, ref gc) => {
+                if !generics.is_empty() {
+                    self.emit(
+                        DiagBuilder2::error("package declaration has multiple generic clauses")
+                        .span(decl.human_span())
+                    );
+                    had_fails = true;
+                    continue;
+                }
+                if self.unpack_generics(scope, &gc.value, &mut generics).is_err() {
+                    had_fails = true;
+                }
+            }
             ast::DeclItem::UseClause(..) => (),
             ref wrong => {
                 self.emit(
@@ -1259,18 +1673,22 @@ impl_make!(self, id: PkgDeclRef => &hir::Package {
 // Lower a package body to HIR.
 impl_make!(self, id: PkgBodyRef => &hir::PackageBody {
     let (scope_id, ast) = self.ast(id);
-    let pkg = self.unpack_package_name((&ast.name).into(), scope_id)?;
+    // A package body in a design file completes a package of the same
+    // library, which need not be made visible by its context clause.
+    let pkg_scope = match scope_id {
+        ScopeRef::CtxItems(ctx_id) => self.ast(ctx_id).0,
+        other => other,
+    };
+    let pkg = self.unpack_package_name((&ast.name).into(), pkg_scope)?;
     let mut decls = Vec::new();
     let mut had_fails = false;
-    let ctx = AddContext::new(self, scope_id);
+    let ctx = AddContext::new(self, id.into());
     for decl in &ast.decls {
         match *decl {
             ast::DeclItem::SubprogDecl(ref decl) => {
                 match decl.data {
                     ast::SubprogData::Decl => {
-                        let subid = SubprogDeclRef(NodeId::alloc());
-                        self.set_ast(subid, (id.into(), decl));
-                        decls.push(subid.into());
+                        decls.push(ctx.add_subprog_decl(decl)?.into());
                     }
                     ast::SubprogData::Body{..} => {
                         let subid = SubprogBodyRef(NodeId::alloc());
@@ -1300,7 +1718,12 @@ impl_make!(self, id: PkgBodyRef => &hir::PackageBody {
                 decls.push(subid.into());
             }
             ast::DeclItem::TypeDecl(ref decl) => {
-                let subid = TypeDeclRef(NodeId::alloc());
+                let subid = match decl.data {
+                    Some(Spanned{ value: ast::ProtectedBody(..), .. }) => {
+                        ctx.add_type_decl(decl)?
+                    }
+                    _ => TypeDeclRef(NodeId::alloc()),
+                };
                 self.set_ast(subid, (id.into(), decl));
                 decls.push(subid.into());
             }
@@ -1396,6 +1819,10 @@ impl_make!(self, id: PkgBodyRef => &hir::PackageBody {
 // Lower a package instantiation to HIR.
 impl_make!(self, id: PkgInstRef => &hir::PackageInst {
     let (scope_id, ast) = self.ast(id);
+    let scope_id = match scope_id {
+        ScopeRef::CtxItems(id) => self.make_ctx_items_scope(id, None)?.into(),
+        others => others,
+    };
     let pkg = self.unpack_package_name((&ast.target).into(), scope_id)?;
     let gm = match ast.generics {
         Some(ref g) => self.unpack_generic_map(scope_id, g)?,
@@ -1837,24 +2264,105 @@ impl_make!(self, id: ArchRef => &hir::Arch {
     }))
 });
 
+// Lower a block statement to HIR. The generic and port clauses and maps of the
+// block header are parsed as declarative items, and are separated from the
+// block's declarations here. The guard condition is lowered within the block,
+// where its ports are visible. See IEEE 1076-2008 section 11.2.
+impl_make!(self, id: BlockStmtRef => &hir::BlockStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::BlockStmt {
+            ref guard,
+            ref decls,
+            ref stmts,
+        } => {
+            let mut generics = Vec::new();
+            let mut generic_map = Vec::new();
+            let mut ports = Vec::new();
+            let mut port_map = Spanned::new(Vec::new(), ast.span);
+            let mut had_fails = false;
+            let header_len = decls
+                .iter()
+                .take_while(|d| match **d {
+                    ast::DeclItem::PortgenClause(..) | ast::DeclItem::PortgenMap(..) => true,
+                    _ => false,
+                })
+                .count();
+            for decl in &decls[..header_len] {
+                let result = match *decl {
+                    ast::DeclItem::PortgenClause(_, Spanned { value: ast::PortgenKind::Generic, .. }, ref decls) => {
+                        self.unpack_generics(scope_id, &decls.value, &mut generics)
+                    }
+                    ast::DeclItem::PortgenClause(_, Spanned { value: ast::PortgenKind::Port, .. }, ref decls) => {
+                        self.unpack_ports(scope_id, &decls.value, &mut ports)
+                    }
+                    ast::DeclItem::PortgenMap(_, Spanned { value: ast::PortgenKind::Generic, .. }, ref elems) => {
+                        self.unpack_generic_map(scope_id, elems).map(|m| generic_map = m)
+                    }
+                    ast::DeclItem::PortgenMap(_, Spanned { value: ast::PortgenKind::Port, .. }, ref elems) => {
+                        self.unpack_port_map(scope_id, elems).map(|m| port_map = m)
+                    }
+                    _ => unreachable!(),
+                };
+                had_fails |= result.is_err();
+            }
+            // TODO(strict): Complain about multiple or misordered generic and
+            // port clauses and maps.
+            let guard = match *guard {
+                Some(ref guard) => Some(AddContext::new(self, id.into()).add_expr(guard)?),
+                None => None,
+            };
+            let decls = self.unpack_block_decls(id.into(), &decls[header_len..], "a block");
+            let stmts = self.unpack_concurrent_stmts(id.into(), stmts, "a block");
+            if had_fails {
+                return Err(());
+            }
+            Ok(self.sb.arenas.hir.block_stmt.alloc(hir::BlockStmt {
+                parent: scope_id,
+                span: ast.span,
+                label: ast.label,
+                guard: guard,
+                generics: generics,
+                generic_map: generic_map,
+                ports: ports,
+                port_map: port_map,
+                decls: decls?,
+                stmts: stmts?,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
 impl_make!(self, id: ProcessStmtRef => &hir::ProcessStmt {
     let (scope_id, ast) = self.ast(id);
     match ast.data {
         ast::ProcStmt {
-            // ref sensitivity,
+            ref sensitivity,
             ref decls,
             ref stmts,
             postponed,
-            ..
         } => {
-            // TODO: map sensititivty
+            // The sensitivity list is resolved outside the process, where its
+            // declarations are not yet visible.
+            let sensitivity = match *sensitivity {
+                None => hir::ProcessSensitivity::None,
+                Some(ast::Sensitivity::All) => hir::ProcessSensitivity::All,
+                Some(ast::Sensitivity::List(ref names)) => {
+                    let sens = AddContext::new(self, scope_id)
+                        .add_sensitivity_list(Spanned::new(names.iter(), ast.span))?;
+                    hir::ProcessSensitivity::List(
+                        sens.value.into_iter().map(|s| Def::Signal(s.value)).collect(),
+                    )
+                }
+            };
             let decls = self.unpack_process_decls(id.into(), decls, "a process")?;
             let stmts = self.unpack_sequential_stmts(id.into(), stmts, "a process")?;
             Ok(self.sb.arenas.hir.process_stmt.alloc(hir::ProcessStmt {
                 parent: scope_id,
                 label: ast.label,
                 postponed: postponed,
-                sensitivity: hir::ProcessSensitivity::None,
+                sensitivity: sensitivity,
                 decls: decls,
                 stmts: stmts,
             }))
@@ -1863,6 +2371,106 @@ impl_make!(self, id: ProcessStmtRef => &hir::ProcessStmt {
     }
 });
 
+impl_make!(self, id: ForGenStmtRef => &hir::ForGenStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::ForGenStmt {
+            param,
+            ref range,
+            ref body,
+        } => {
+            // The range is evaluated outside the generated region, where the
+            // generate parameter is not yet visible.
+            let range = AddContext::new(self, scope_id).add_discrete_range(range);
+            let decls = self.unpack_block_decls(id.into(), &body.decls, "a generate statement");
+            let stmts = self.unpack_concurrent_stmts(id.into(), &body.stmts, "a generate statement");
+            Ok(self.sb.arenas.hir.for_gen_stmt.alloc(hir::ForGenStmt {
+                parent: scope_id,
+                label: ast.label,
+                param: param,
+                range: range?,
+                decls: decls?,
+                stmts: stmts?,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
+impl_make!(self, id: IfGenStmtRef => &hir::IfGenStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::IfGenStmt {
+            ref conds,
+            ref alt,
+        } => {
+            let conds = conds
+                .iter()
+                .map(|&(ref cond, ref body)| {
+                    let cond = AddContext::new(self, scope_id).add_expr(cond);
+                    let body = self.unpack_gen_body(scope_id, body);
+                    Ok((cond?, body))
+                })
+                .collect::<Vec<Result<_>>>()
+                .into_iter()
+                .collect::<Result<Vec<_>>>();
+            let alt = alt.as_ref().map(|body| self.unpack_gen_body(scope_id, body));
+            Ok(self.sb.arenas.hir.if_gen_stmt.alloc(hir::IfGenStmt {
+                parent: scope_id,
+                label: ast.label,
+                conds: conds?,
+                alt: alt,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
+impl_make!(self, id: CaseGenStmtRef => &hir::CaseGenStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::CaseGenStmt {
+            ref switch,
+            ref cases,
+        } => {
+            let ctx = AddContext::new(self, scope_id);
+            let switch = ctx.add_expr(switch);
+            let cases = cases
+                .iter()
+                .map(|&(ref choices, ref body)| {
+                    let choices = ctx.add_choices(choices.as_ref().map(|c| c.iter()));
+                    let body = self.unpack_gen_body(scope_id, body);
+                    Ok((choices?, body))
+                })
+                .collect::<Vec<Result<_>>>()
+                .into_iter()
+                .collect::<Result<Vec<_>>>();
+            Ok(self.sb.arenas.hir.case_gen_stmt.alloc(hir::CaseGenStmt {
+                parent: scope_id,
+                span: ast.span,
+                label: ast.label,
+                switch: switch?,
+                cases: cases?,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
+// Lower a body of an if-generate or case-generate statement to HIR. Each body
+// is a separate declarative region. See IEEE 1076-2008 section 11.8.
+impl_make!(self, id: GenBodyRef => &hir::GenBody {
+    let (scope_id, ast) = self.ast(id);
+    let decls = self.unpack_block_decls(id.into(), &ast.decls, "a generate statement");
+    let stmts = self.unpack_concurrent_stmts(id.into(), &ast.stmts, "a generate statement");
+    Ok(self.sb.arenas.hir.gen_body.alloc(hir::GenBody {
+        parent: scope_id,
+        label: ast.label,
+        decls: decls?,
+        stmts: stmts?,
+    }))
+});
+
 impl_make!(self, id: SigAssignStmtRef => &hir::SigAssignStmt {
     let (scope_id, ast) = self.ast(id);
     match ast.data {
@@ -1873,9 +2481,12 @@ impl_make!(self, id: SigAssignStmtRef => &hir::SigAssignStmt {
             ..
         } => {
             let target = self.unpack_signal_assign_target(scope_id, target)?;
-            let tyctx = match target {
-                hir::SigAssignTarget::Name(id) => TypeCtx::TypeOf(id.into()),
-                hir::SigAssignTarget::Aggregate => unimplemented!(),
+            // The values assigned to indexed and sliced targets obtain their
+            // exact type context once the target has been type checked. The
+            // type of an aggregate target is determined by the value itself.
+            let tyctx = match target.signal() {
+                Some(id) => TypeCtx::TypeOf(id.into()),
+                None => TypeCtx::Inherit(id.into()),
             };
             let kind = self.unpack_signal_assign_mode(scope_id, mode, &tyctx)?;
             if guarded {
@@ -1891,10 +2502,168 @@ impl_make!(self, id: SigAssignStmtRef => &hir::SigAssignStmt {
                 label: ast.label,
                 target: target,
                 target_span: target_span,
+                guarded: false,
+                kind: kind.value,
+                kind_span: kind.span,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
+// Lower a concurrent procedure call to its equivalent sequential statement.
+// Without a keyword or map aspects, the statement may also instantiate a
+// component, which is not yet supported. See IEEE 1076-2008 section 11.4.
+impl_make!(self, id: ConcCallStmtRef => CallStmtRef {
+    let (scope_id, ast) = self.ast(id);
+    if let ast::InstOrCallStmt { ref name, .. } = ast.data {
+        let primary = self.resolvable_from_primary_name(&name.primary)?;
+        let defs = self.resolve_name(primary, scope_id, false, true)?;
+        if defs.iter().any(|d| match d.value { Def::Comp(..) => true, _ => false }) {
+            unimp_msg!(self, "component instantiation", ast.human_span());
+        }
+    }
+    AddContext::new(self, scope_id).add_call_stmt(ast)
+});
+
+// Lower a component declaration to HIR. The generics and ports are lowered in
+// the scope the component is declared in. See IEEE 1076-2008 section 6.8.
+impl_make!(self, id: CompDeclRef => &hir::Component {
+    let (scope_id, ast) = self.ast(id);
+    let mut comp = hir::Component {
+        name: ast.name,
+        generics: Vec::new(),
+        ports: Vec::new(),
+    };
+    let mut had_fails = false;
+    if let Some(ref generics) = ast.generics {
+        had_fails |= self.unpack_generics(scope_id, &generics.value, &mut comp.generics).is_err();
+    }
+    if let Some(ref ports) = ast.ports {
+        had_fails |= self.unpack_ports(scope_id, &ports.value, &mut comp.ports).is_err();
+    }
+    if had_fails {
+        return Err(());
+    }
+    Ok(self.sb.arenas.hir.component.alloc(comp))
+});
+
+// Lower a component instantiation statement to HIR. Instantiations without the
+// `component` keyword may also name an entity or configuration, which is not
+// yet supported. See IEEE 1076-2008 section 11.7.
+impl_make!(self, id: CompInstStmtRef => &hir::CompInstStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::InstOrCallStmt {
+            ref name,
+            ref generics,
+            ref ports,
+            ..
+        } => {
+            let ctx = TermContext::new(self, scope_id);
+            let term = ctx.termify_compound_name(name)?;
+            let comp = match term.value {
+                Term::Ident(Spanned { value: Def::Comp(comp), .. }) => comp,
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error(format!("`{}` is not a component", term.span.extract()))
+                        .span(term.span)
+                    );
+                    return Err(());
+                }
+            };
+            let generic_map = match *generics {
+                Some(ref g) => self.unpack_generic_map(scope_id, g),
+                None => Ok(vec![]),
+            };
+            let port_map = match *ports {
+                Some(ref p) => self.unpack_port_map(scope_id, p),
+                None => Ok(Spanned::new(vec![], name.span)),
+            };
+            Ok(self.sb.arenas.hir.comp_inst_stmt.alloc(hir::CompInstStmt {
+                parent: scope_id,
+                span: ast.span,
+                label: ast.label,
+                comp: Spanned::new(comp, term.span),
+                generic_map: generic_map?,
+                port_map: port_map?,
+            }))
+        }
+        _ => unreachable!()
+    }
+});
+
+// Lower a concurrent assertion to its equivalent sequential statement. See
+// IEEE 1076-2008 section 11.5.
+impl_make!(self, id: ConcAssertStmtRef => AssertStmtRef {
+    let (scope_id, ast) = self.ast(id);
+    AddContext::new(self, scope_id).add_assert_stmt(ast)
+});
+
+// Lower a concurrent simple, conditional, or selected signal assignment to
+// HIR. Unlike their sequential counterparts, these may be guarded. See IEEE
+// 1076-2008 section 11.6.
+impl_make!(self, id: ConcSigAssignStmtRef => &hir::SigAssignStmt {
+    let (scope_id, ast) = self.ast(id);
+    match ast.data {
+        ast::AssignStmt {
+            target: Spanned{ value: ref target, span: target_span },
+            ref mode,
+            guarded,
+            ..
+        } => {
+            let target = self.unpack_signal_assign_target(scope_id, target)?;
+            let tyctx = match target.signal() {
+                Some(id) => TypeCtx::TypeOf(id.into()),
+                None => TypeCtx::Inherit(id.into()),
+            };
+            let kind = self.unpack_signal_assign_mode(scope_id, mode, &tyctx)?;
+            Ok(self.sb.arenas.hir.sig_assign_stmt.alloc(hir::SigAssignStmt {
+                parent: scope_id,
+                span: ast.span,
+                label: ast.label,
+                target: target,
+                target_span: target_span,
+                guarded: guarded,
                 kind: kind.value,
                 kind_span: kind.span,
             }))
         }
+        ast::SelectAssignStmt {
+            ref select,
+            qm,
+            target: Spanned{ value: ref target, span: target_span },
+            guarded,
+            ref mode,
+            ref waves,
+            ..
+        } => {
+            let target = self.unpack_signal_assign_target(scope_id, target)?;
+            // The values assigned to indexed and sliced targets obtain their
+            // exact type context once the target has been type checked. The
+            // type of an aggregate target is determined by the value itself.
+            let tyctx = match target.signal() {
+                Some(id) => TypeCtx::TypeOf(id.into()),
+                None => TypeCtx::Inherit(id.into()),
+            };
+            let dm = match *mode {
+                ast::SelectAssignMode::Normal(ref dm) => self.unpack_delay_mechanism(scope_id, dm)?,
+                ast::SelectAssignMode::Force(..) => {
+                    unimp_msg!(self, "selected force signal assignment", ast.human_span());
+                }
+            };
+            let sel = self.unpack_sel_waveforms(scope_id, select, qm, waves, &tyctx)?;
+            Ok(self.sb.arenas.hir.sig_assign_stmt.alloc(hir::SigAssignStmt {
+                parent: scope_id,
+                span: ast.span,
+                label: ast.label,
+                target: target,
+                target_span: target_span,
+                guarded: guarded,
+                kind: hir::SigAssignKind::SelWave(dm, sel),
+                kind_span: ast.span,
+            }))
+        }
         _ => unreachable!()
     }
 });
@@ -2008,3 +2777,21 @@ impl_make!(self, id: LatentTypeMarkRef => Spanned<TypeMarkRef> {
     let term = ctx.termify_latent_name(ast)?;
     ctx.term_to_type_mark(term)
 });
+
+impl_make!(self, id: LatentPkgRef => Spanned<PkgRef> {
+    let (scope_id, ast) = self.ast(id);
+    let ctx = TermContext::new(self, scope_id);
+    let term = ctx.termify_latent_name(ast)?;
+    let def = ctx.term_to_ident(term)?;
+    match def.value {
+        Def::Pkg(pkg) => Ok(Spanned::new(pkg.into(), def.span)),
+        Def::PkgInst(pkg) => Ok(Spanned::new(pkg.into(), def.span)),
+        _ => {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is not a package", def.span.extract()))
+                .span(def.span)
+            );
+            Err(())
+        }
+    }
+});
@@ -7,7 +7,7 @@
 
 use std;
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 
 use moore_common::errors::*;
@@ -20,8 +20,10 @@ use moore_common::{Session, Verbosity};
 
 use llhd;
 use num::{BigInt, Signed};
+use serde::Serialize;
 use typed_arena::Arena;
 
+use crate::add_ctx::AddContext;
 use crate::arenas::Alloc;
 use crate::builtin;
 pub use crate::builtin::*;
@@ -30,7 +32,7 @@ use crate::hir;
 use crate::konst::*;
 use crate::lazy::*;
 use crate::op::*;
-use crate::syntax::ast;
+use crate::syntax::{self, ast};
 use crate::ty::*;
 use crate::typeck::{Typeck, TypeckContext};
 
@@ -100,10 +102,17 @@ pub struct ScoreBoard<'ast, 'ctx> {
     lldef_table: RefCell<HashMap<NodeId, llhd::ValueRef>>,
     /// A table of types.
     pub ty_table: RefCell<HashMap<NodeId, &'ctx Ty>>,
-    /// A table of scopes.
-    scope_table: RefCell<HashMap<ScopeRef, &'ctx Scope>>,
-    /// A table of nodes' constant values.
-    const_table: RefCell<HashMap<NodeId, &'ctx Const>>,
+    /// A table of named types, one per type mark. Ensures that all references
+    /// to a declaration compare equal, regardless of where they appear.
+    pub named_ty_table: RefCell<HashMap<TypeMarkRef, &'ctx Ty>>,
+    /// A table of interned types, such that equal types share an allocation.
+    interned_ty_table: RefCell<HashSet<&'ctx Ty>>,
+    /// A table of scopes. Failures are memoized as well, such that a scope
+    /// that cannot be constructed is only diagnosed once.
+    scope_table: RefCell<HashMap<ScopeRef, Result<&'ctx Scope>>>,
+    /// A table of expressions' constant values. Failures are memoized as well,
+    /// such that each expression is folded at most once.
+    const_table: RefCell<HashMap<ExprRef, Result<&'ctx Const>>>,
     /// A table of type contexts for expressions.
     tyctx_table: RefCell<HashMap<NodeId, TypeCtx<'ctx>>>,
     /// A table of typeck results.
@@ -112,6 +121,13 @@ pub struct ScoreBoard<'ast, 'ctx> {
     pub typeval_table: RefCell<HashMap<NodeId, Result<&'ctx Ty>>>,
     /// A table of scopes. Revised; will replace `scope_table` and `def_table`.
     pub scope2_table: RefCell<HashMap<ScopeRef, crate::scope::Scope>>,
+    /// A table of the actual types substituted for generic type formals,
+    /// indexed by the instantiating node.
+    generic_type_table: RefCell<HashMap<(NodeId, IntfTypeRef), Spanned<TypeMarkRef>>>,
+    /// The signals read by any expression type checked so far.
+    signal_reads: RefCell<HashSet<SignalDeclRef>>,
+    /// The signals assigned by any statement type checked so far.
+    signal_writes: RefCell<HashSet<SignalDeclRef>>,
 }
 
 impl<'ast, 'ctx> ScoreBoard<'ast, 'ctx> {
@@ -130,17 +146,33 @@ impl<'ast, 'ctx> ScoreBoard<'ast, 'ctx> {
             lldecl_table: RefCell::new(HashMap::new()),
             lldef_table: RefCell::new(HashMap::new()),
             ty_table: RefCell::new(HashMap::new()),
+            named_ty_table: RefCell::new(HashMap::new()),
+            interned_ty_table: RefCell::new(HashSet::new()),
             scope_table: RefCell::new(HashMap::new()),
             const_table: RefCell::new(HashMap::new()),
             tyctx_table: RefCell::new(HashMap::new()),
             typeck_table: RefCell::new(HashMap::new()),
             typeval_table: RefCell::new(HashMap::new()),
             scope2_table: RefCell::new(HashMap::new()),
+            generic_type_table: RefCell::new(HashMap::new()),
+            signal_reads: RefCell::new(HashSet::new()),
+            signal_writes: RefCell::new(HashSet::new()),
         };
         builtin::register_builtins(&sb);
         sb
     }
 
+    /// Discard all memoized types and constant values.
+    ///
+    /// Constant values are derived from the types of the expressions they are
+    /// folded from, so both caches must be invalidated together whenever the
+    /// sources they were computed from change.
+    pub fn invalidate_types(&self) {
+        self.ty_table.borrow_mut().clear();
+        self.typeval_table.borrow_mut().clear();
+        self.const_table.borrow_mut().clear();
+    }
+
     /// Internalize a constant.
     ///
     /// Returns a reference to the constant whose lifetime is bound to that of
@@ -160,7 +192,41 @@ impl<'ast, 'ctx> ScoreBoard<'ast, 'ctx> {
     where
         T: Into<Ty>,
     {
-        self.arenas.ty.alloc(ty.into())
+        self.intern_ty_tracked(ty).0
+    }
+
+    /// Internalize a type, reporting whether it was already interned.
+    ///
+    /// Equal types are only allocated once. The returned flag is `true` if an
+    /// existing allocation was reused.
+    pub fn intern_ty_tracked<T>(&self, ty: T) -> (&'ctx Ty, bool)
+    where
+        T: Into<Ty>,
+    {
+        let ty = ty.into();
+        if let Some(&interned) = self.interned_ty_table.borrow().get(&ty) {
+            return (interned, true);
+        }
+        let interned = self.arenas.ty.alloc(ty);
+        self.interned_ty_table.borrow_mut().insert(interned);
+        (interned, false)
+    }
+
+    /// Internalize a named type.
+    ///
+    /// Returns the same type for every reference to a type mark, such that
+    /// uses of a declaration in different units are identical. The name is
+    /// only used the first time a type mark is encountered.
+    pub fn intern_named_ty<N>(&self, name: N, tm: TypeMarkRef) -> &'ctx Ty
+    where
+        N: Into<TyName>,
+    {
+        if let Some(&ty) = self.named_ty_table.borrow().get(&tm) {
+            return ty;
+        }
+        let ty = self.intern_ty(Ty::Named(name.into(), tm));
+        self.named_ty_table.borrow_mut().insert(tm, ty);
+        ty
     }
 }
 
@@ -171,6 +237,11 @@ impl<'lazy, 'sb, 'ast, 'ctx> DiagEmitter for ScoreContext<'lazy, 'sb, 'ast, 'ctx
 }
 
 impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
+    /// Get the compiler session.
+    pub fn sess(&self) -> &'lazy Session {
+        self.sess
+    }
+
     /// Add a library of AST nodes. This function is called by the global
     /// scoreboard to add VHDL-specific AST nodes.
     pub fn add_library(&self, name: Name, id: LibRef, lib: Vec<&'ast ast::DesignUnit>) {
@@ -178,6 +249,16 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         self.sb.lib_names.borrow_mut().insert(name, id);
     }
 
+    /// Obtain the name of a library.
+    pub fn lib_name(&self, id: LibRef) -> Option<Name> {
+        self.sb
+            .lib_names
+            .borrow()
+            .iter()
+            .find(|&(_, &lib)| lib == id)
+            .map(|(&name, _)| name)
+    }
+
     /// Obtain the span associated with a node ID.
     pub fn span<I>(&self, id: I) -> Option<Span>
     where
@@ -475,48 +556,344 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
+    /// Check the types of a single architecture and its entity, or of a
+    /// package and its body.
+    ///
+    /// The `name` is either of the form `entity.arch`, the name of an entity
+    /// whose most recently analyzed architecture is checked, the name of an
+    /// architecture which is unique within the library, or the name of a
+    /// package. Other units of the library are only checked as far as the
+    /// unit depends on them. Returns `true` if no errors were found.
+    pub fn typeck_unit(&self, lib: LibRef, name: &str) -> bool {
+        if let Some(pkg) = self.resolve_pkg_name(lib, name) {
+            let ctx = TypeckContext::new(self);
+            ctx.typeck(pkg);
+            let pkg_name = self.ast(pkg).1.name.value;
+            if let Ok(hir) = self.hir(lib) {
+                for &body in &hir.pkg_bodies {
+                    if self.ast(body).1.name.value == pkg_name {
+                        ctx.typeck(body);
+                    }
+                }
+            }
+            return ctx.finish();
+        }
+        let (entity, arch) = match self.resolve_arch_name(lib, name) {
+            Ok(x) => x,
+            Err(()) => return false,
+        };
+        let ctx = TypeckContext::new(self);
+        ctx.typeck(entity);
+        ctx.typeck(arch);
+        ctx.finish()
+    }
+
+    /// Check the types of an entire library and summarize the result.
+    ///
+    /// This is the entry point for editor integrations. Diagnostics emitted
+    /// during the check are captured in the summary rather than passed on to
+    /// the session's sink. The summary lists the nodes of the library whose
+    /// type has been determined, but not those of other libraries it depends
+    /// on.
+    pub fn typeck_summary(&self, lib: LibRef) -> TypeckSummary {
+        let (ok, diagnostics) = self.sess.capture_diagnostics(|| {
+            let ctx = TypeckContext::new(self);
+            ctx.typeck(lib);
+            ctx.finish()
+        });
+        let units: Vec<Span> = self.sb.libs.borrow()[&lib]
+            .iter()
+            .map(|unit| match unit.data {
+                ast::DesignUnitData::EntityDecl(ref decl) => decl.span,
+                ast::DesignUnitData::CfgDecl(ref decl) => decl.span,
+                ast::DesignUnitData::PkgDecl(ref decl) => decl.span,
+                ast::DesignUnitData::PkgInst(ref decl) => decl.span,
+                ast::DesignUnitData::CtxDecl(ref decl) => decl.span,
+                ast::DesignUnitData::ArchBody(ref body) => body.span,
+                ast::DesignUnitData::PkgBody(ref body) => body.span,
+            })
+            .collect();
+        let within_lib = |span: Span| {
+            units.iter().any(|unit| {
+                unit.source == span.source && unit.begin <= span.begin && span.end <= unit.end
+            })
+        };
+        let mut types: Vec<_> = self
+            .sb
+            .typeval_table
+            .borrow()
+            .iter()
+            .filter_map(|(&id, &ty)| self.span(id).map(|span| (span, ty)))
+            .filter(|&(span, _)| within_lib(span))
+            .map(|(span, ty)| TypeckSummaryNode {
+                span: span.into(),
+                ty: ty.ok().map(|ty| ty.to_string()),
+            })
+            .collect();
+        types.sort_by(|a, b| a.span.cmp(&b.span));
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diag| TypeckSummaryDiag {
+                severity: diag.get_severity().to_string(),
+                message: diag.get_message().to_string(),
+                span: diag.get_primary_span().map(Into::into),
+            })
+            .collect();
+        TypeckSummary {
+            ok: ok,
+            types: types,
+            diagnostics: diagnostics,
+        }
+    }
+
+    /// Find the entity and architecture referred to by a name of the form
+    /// accepted by `typeck_unit`.
+    /// Find the package declaration of a library with the given name.
+    fn resolve_pkg_name(&self, lib: LibRef, name: &str) -> Option<PkgDeclRef> {
+        let name = get_name_table().intern(name, false);
+        match self
+            .defs(ScopeRef::Lib(lib.into()))
+            .ok()?
+            .get(&name.into())
+            .and_then(|d| d.last())
+        {
+            Some(&Spanned {
+                value: Def::Pkg(pkg),
+                ..
+            }) => Some(pkg),
+            _ => None,
+        }
+    }
+
+    fn resolve_arch_name(&self, lib: LibRef, name: &str) -> Result<(EntityRef, ArchRef)> {
+        let table = get_name_table();
+        let mut parts = name.splitn(2, '.');
+        let first = table.intern(parts.next().unwrap(), false);
+        let second = parts.next().map(|n| table.intern(n, false));
+        let archs = self.archs(lib)?;
+        let defs = self.defs(ScopeRef::Lib(lib.into()))?;
+        let entity = match defs.get(&first.into()).and_then(|d| d.last()) {
+            Some(&Spanned {
+                value: Def::Entity(e),
+                ..
+            }) => Some(e),
+            _ => None,
+        };
+        match (entity, second) {
+            (Some(entity), Some(arch)) => match archs.by_entity[&entity].by_name.get(&arch) {
+                Some(&id) => Ok((entity, id)),
+                None => {
+                    self.emit(DiagBuilder2::error(format!(
+                        "`{}` is not an architecture of entity `{}`",
+                        arch, first
+                    )));
+                    Err(())
+                }
+            },
+            (Some(entity), None) => match archs.by_entity[&entity].ordered.last() {
+                Some(&id) => Ok((entity, id)),
+                None => {
+                    self.emit(DiagBuilder2::error(format!(
+                        "entity `{}` has no architecture",
+                        first
+                    )));
+                    Err(())
+                }
+            },
+            (None, Some(_)) => {
+                self.emit(DiagBuilder2::error(format!("unknown entity `{}`", first)));
+                Err(())
+            }
+            (None, None) => {
+                let mut found: Vec<_> = archs
+                    .by_arch
+                    .iter()
+                    .filter(|&(&id, _)| self.ast(id).2.name.value == first)
+                    .map(|(&id, &entity)| (entity, id))
+                    .collect();
+                match found.len() {
+                    1 => Ok(found.pop().unwrap()),
+                    0 => {
+                        self.emit(DiagBuilder2::error(format!(
+                            "no entity or architecture named `{}`",
+                            first
+                        )));
+                        Err(())
+                    }
+                    _ => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "architecture name `{}` is ambiguous",
+                                first
+                            ))
+                            .add_note(format!(
+                                "Use `<entity>.{}` to select one of {} architectures.",
+                                first,
+                                found.len()
+                            )),
+                        );
+                        Err(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Parse a subtype indication from text and determine its type.
+    ///
+    /// Accepts anything that may appear as a subtype indication, e.g.
+    /// `integer range 0 to 7` or `bit_vector(3 downto 0)`. Names are resolved
+    /// in the root scope, such that the `std.standard` package is visible.
+    /// Syntax and type errors are emitted as diagnostics. The parsed AST is
+    /// allocated in the scoreboard's arenas, since nodes refer to it for as
+    /// long as the scoreboard lives.
+    pub fn parse_and_intern_ty(&self, text: &str) -> Result<&'ctx Ty>
+    where
+        'ctx: 'ast,
+    {
+        let src = get_source_manager().add_anonymous(text);
+        let ast = syntax::parse_with(src, self.sess, |p| {
+            syntax::parser::rules::parse_subtype_ind(p)
+        })?;
+        let ast: &'ast ast::SubtypeInd = self.sb.arenas.subtype_ind.alloc(ast);
+        let id = AddContext::new(self, *builtin::ROOT_SCOPE_REF).add_subtype_ind(ast)?;
+        self.lazy_typeval(id)
+    }
+
+    /// Parse an expression from text, determine its type, and fold it.
+    ///
+    /// Names are resolved in the root scope, as for `parse_and_intern_ty`. If
+    /// `context_ty` is given, the expression is type checked against it. The
+    /// expression is folded to a constant value if it consists only of
+    /// literals, type attributes, and operators; otherwise no value is
+    /// returned. Syntax and type errors are emitted as diagnostics.
+    pub fn eval_expr(
+        &self,
+        text: &str,
+        context_ty: Option<&'ctx Ty>,
+    ) -> Result<(&'ctx Ty, Option<&'ctx Const>)>
+    where
+        'ctx: 'ast,
+    {
+        let src = get_source_manager().add_anonymous(text);
+        let ast = syntax::parse_with(src, self.sess, |p| syntax::parser::rules::parse_expr(p))?;
+        let ast: &'ast ast::Expr = self.sb.arenas.expr.alloc(ast);
+        let id = AddContext::new(self, *builtin::ROOT_SCOPE_REF).add_expr(ast)?;
+        if let Some(exp) = context_ty {
+            self.set_type_context(id, exp);
+        }
+        let ty = self.lazy_typeval(id)?;
+        if let Some(exp) = context_ty {
+            if !TypeckContext::new(self).must_match(exp, ty, ast.span) {
+                return Err(());
+            }
+        }
+        let value = if self.is_foldable_expr(id)? {
+            Some(self.const_value(id)?)
+        } else {
+            None
+        };
+        Ok((ty, value))
+    }
+
+    /// Check whether an expression may be folded to a constant value.
+    ///
+    /// This is the case for expressions built only from literals, enumeration
+    /// literals, type attributes, and operators. Other expressions, e.g. names
+    /// of signals, are rejected upfront rather than when folding them fails.
+    fn is_foldable_expr(&self, id: ExprRef) -> Result<bool> {
+        Ok(match self.lazy_hir(id)?.data {
+            hir::ExprData::IntegerLiteral(..)
+            | hir::ExprData::FloatLiteral(..)
+            | hir::ExprData::PhysLiteral(..)
+            | hir::ExprData::TypeAttr(_, _, None)
+            | hir::ExprData::Name(Def::Enum(..), _) => true,
+            hir::ExprData::Unary(_, _, arg) => self.is_foldable_expr(arg)?,
+            hir::ExprData::Binary(_, _, lhs, rhs) | hir::ExprData::Range(_, lhs, rhs) => {
+                self.is_foldable_expr(lhs)? && self.is_foldable_expr(rhs)?
+            }
+            _ => false,
+        })
+    }
+
     pub fn scope(&self, id: ScopeRef) -> Result<&'ctx Scope> {
-        if let Some(node) = self.sb.scope_table.borrow().get(&id.into()).cloned() {
-            return Ok(node);
+        if let Some(&result) = self.sb.scope_table.borrow().get(&id.into()) {
+            return result;
         }
         if self.sess.opts.trace_scoreboard {
             debugln!("[SB][VHDL] make scope for {:?}", id);
         }
-        let node = self.make(id)?;
+        let result = self.make(id);
         if self.sess.opts.trace_scoreboard {
-            debugln!("[SB][VHDL] scope for {:?} is {:?}", id, node);
+            debugln!("[SB][VHDL] scope for {:?} is {:?}", id, result);
         }
-        if self.sb.scope_table.borrow_mut().insert(id, node).is_some() {
+        if self
+            .sb
+            .scope_table
+            .borrow_mut()
+            .insert(id, result)
+            .is_some()
+        {
             panic!("node should not exist");
         }
-        Ok(node)
+        result
     }
 
-    pub fn const_value<I>(&self, id: I) -> Result<&'ctx Const>
-    where
-        I: 'ctx + Copy + Debug + Into<NodeId>,
-        ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Const>,
-    {
-        if let Some(node) = self.sb.const_table.borrow().get(&id.into()).cloned() {
-            return Ok(node);
+    /// Determine the constant value of an expression.
+    ///
+    /// If called for the first time with the given `id`, folds the expression
+    /// by calling `self.make(id)`. Otherwise returns the memoized result, such
+    /// that checks which repeatedly evaluate the same expression do not fold
+    /// it again, nor emit its diagnostics twice.
+    pub fn const_value(&self, id: ExprRef) -> Result<&'ctx Const> {
+        if let Some(&result) = self.sb.const_table.borrow().get(&id) {
+            return result;
         }
         if self.sess.opts.trace_scoreboard {
             debugln!("[SB][VHDL] make const for {:?}", id);
         }
-        let node = self.make(id)?;
+        let result: Result<&'ctx Const> = self.make(id);
         if self.sess.opts.trace_scoreboard {
-            debugln!("[SB][VHDL] const for {:?} is {:?}", id, node);
+            debugln!("[SB][VHDL] const for {:?} is {:?}", id, result);
         }
         if self
             .sb
             .const_table
             .borrow_mut()
-            .insert(id.into(), node)
+            .insert(id, result)
             .is_some()
         {
             panic!("node should not exist");
         }
-        Ok(node)
+        result
+    }
+
+    /// Record the actual type substituted for a generic type formal.
+    ///
+    /// The substitution is only valid within the unit instantiated by `inst`.
+    pub fn set_generic_type<I>(&self, inst: I, formal: IntfTypeRef, actual: Spanned<TypeMarkRef>)
+    where
+        I: Into<NodeId>,
+    {
+        self.sb
+            .generic_type_table
+            .borrow_mut()
+            .insert((inst.into(), formal), actual);
+    }
+
+    /// Obtain the actual type substituted for a generic type formal.
+    ///
+    /// Returns `None` if `inst` does not map `formal`, or has not been
+    /// typechecked yet.
+    pub fn generic_type<I>(&self, inst: I, formal: IntfTypeRef) -> Option<Spanned<TypeMarkRef>>
+    where
+        I: Into<NodeId>,
+    {
+        self.sb
+            .generic_type_table
+            .borrow()
+            .get(&(inst.into(), formal))
+            .cloned()
     }
 
     /// Obtain the type context for an expression.
@@ -529,9 +906,12 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         self.sb.tyctx_table.borrow().get(&id.into()).map(|&t| t)
     }
 
-    /// Obtain the type indicated by the type context for an expression.
+    /// Obtain the type expected of an expression by its context.
     ///
-    /// Returns `None` if no context information is available.
+    /// Answers the question "what type is expected here?". Contexts that refer
+    /// to another node or inherit the context of an enclosing expression are
+    /// followed until a type is found. Returns `None` if no context
+    /// information is available.
     pub fn type_context_resolved<I>(&self, id: I) -> Result<Option<&'ctx Ty>>
     where
         I: Copy + Debug + Into<NodeId>,
@@ -544,6 +924,24 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         })
     }
 
+    /// Obtain the node that establishes the type context of an expression.
+    ///
+    /// This is the node whose type `type_context_resolved` returns, e.g. the
+    /// subtype indication of an object whose initial value the expression is.
+    /// Useful to point diagnostics at the reason a type is expected. Returns
+    /// `None` if there is no context, or if the context is a type that is not
+    /// tied to any node.
+    pub fn type_context_origin<I>(&self, id: I) -> Option<NodeId>
+    where
+        I: Copy + Debug + Into<NodeId>,
+    {
+        match self.type_context(id) {
+            Some(TypeCtx::TypeOf(id)) => Some(id.into()),
+            Some(TypeCtx::Inherit(id)) => self.type_context_origin(id),
+            Some(TypeCtx::Type(_)) | None => None,
+        }
+    }
+
     /// Store a type context for an expression.
     ///
     /// Upon type checking, the expression is likely to consult this context to
@@ -559,6 +957,38 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             .insert(id.into(), tyctx.into());
     }
 
+    /// Record that a signal is read.
+    ///
+    /// Only declared signals are tracked, since ports are used outside of the
+    /// architecture that declares them.
+    pub fn record_signal_read(&self, id: SignalRef) {
+        if let SignalRef::Decl(id) = id {
+            self.sb.signal_reads.borrow_mut().insert(id);
+        }
+    }
+
+    /// Record that a signal is assigned.
+    ///
+    /// Only declared signals are tracked, since ports are used outside of the
+    /// architecture that declares them.
+    pub fn record_signal_write(&self, id: SignalRef) {
+        if let SignalRef::Decl(id) = id {
+            self.sb.signal_writes.borrow_mut().insert(id);
+        }
+    }
+
+    /// Check whether a signal has been read by any expression type checked so
+    /// far.
+    pub fn is_signal_read(&self, id: SignalDeclRef) -> bool {
+        self.sb.signal_reads.borrow().contains(&id)
+    }
+
+    /// Check whether a signal has been assigned by any statement type checked
+    /// so far.
+    pub fn is_signal_written(&self, id: SignalDeclRef) -> bool {
+        self.sb.signal_writes.borrow().contains(&id)
+    }
+
     /// Store a type context for an optional expression.
     ///
     /// Upon type checking, the expression is likely to consult this context to
@@ -664,7 +1094,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 scope_id
             );
         }
-        let mut found_defs = Vec::new();
+        let mut found_defs: Vec<Spanned<Def>> = Vec::new();
         let parent_id = if !(*BUILTIN_SCOPE_REFS).contains(&scope_id) {
             if only_defs {
                 let defs = self.defs(scope_id)?;
@@ -710,6 +1140,29 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
             }
         }
 
+        // Declarations that are known to both scoping mechanisms are found
+        // twice, but denote the same thing.
+        let mut seen = HashSet::new();
+        found_defs.retain(|def| seen.insert(def.value));
+
+        // Overloadable declarations do not hide the overloads declared in
+        // outer regions, but are visible alongside them. See IEEE 1076-2008
+        // section 12.3.
+        let overloadable = |def: &Spanned<Def>| match def.value {
+            Def::Enum(_) | Def::Subprog(_) | Def::SubprogInst(_) | Def::BuiltinOp(_) => true,
+            Def::Alias(id) => self.is_subprog_alias(id),
+            _ => false,
+        };
+        if !found_defs.is_empty() && found_defs.iter().all(overloadable) {
+            if let Some(parent_id) = parent_id {
+                for def in self.resolve_name(name, parent_id, only_defs, true)? {
+                    if overloadable(&def) && seen.insert(def.value) {
+                        found_defs.push(def);
+                    }
+                }
+            }
+        }
+
         // If nothing matched the definition, try to escalate to the parent
         // scope. If there is no parent scope, i.e. we're the parent, fail with
         // a diagnostic.
@@ -738,6 +1191,124 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
+    /// Resolve the name of a type or subtype within a scope.
+    ///
+    /// Returns the named type the type mark refers to. Emits an error anchored
+    /// at the name's span if it does not refer to a type or subtype, and
+    /// suggests a similarly spelled type if the name is unknown.
+    pub fn resolve_type_mark(
+        &self,
+        name: Spanned<ResolvableName>,
+        scope_id: ScopeRef,
+    ) -> Result<&'ctx Ty> {
+        let defs = self.resolve_name(name, scope_id, false, true)?;
+        let tms: Vec<TypeMarkRef> = defs
+            .iter()
+            .filter_map(|def| match def.value {
+                Def::Type(id) => Some(id.into()),
+                Def::Subtype(id) => Some(id.into()),
+                _ => None,
+            })
+            .collect();
+        if tms.len() == 1 {
+            return Ok(self.intern_named_ty(tms[0]));
+        }
+        let mut diag = if defs.is_empty() {
+            let mut diag =
+                DiagBuilder2::error(format!("unknown type `{}`", name.value)).span(name.span);
+            if let Some(similar) = self.similar_type_name(name.value, scope_id)? {
+                diag = diag.add_note(format!("did you mean `{}`?", similar));
+            }
+            diag
+        } else if tms.is_empty() {
+            DiagBuilder2::error(format!("`{}` is not a type or subtype", name.value))
+                .span(name.span)
+        } else {
+            DiagBuilder2::error(format!("type `{}` is ambiguous", name.value)).span(name.span)
+        };
+        for def in &defs {
+            if def.span != INVALID_SPAN {
+                diag = diag.add_note("declared here:").span(def.span);
+            }
+        }
+        self.emit(diag);
+        Err(())
+    }
+
+    /// Find the visible type or subtype whose name is most similar to `name`.
+    ///
+    /// Returns `None` if no type name is close enough to be a plausible typo.
+    fn similar_type_name(
+        &self,
+        name: ResolvableName,
+        scope_id: ScopeRef,
+    ) -> Result<Option<ResolvableName>> {
+        let name = match name {
+            ResolvableName::Ident(name) => name.as_str().to_lowercase(),
+            _ => return Ok(None),
+        };
+        let is_type = |defs: &Vec<Spanned<Def>>| {
+            defs.iter().any(|def| match def.value {
+                Def::Type(..) | Def::Subtype(..) => true,
+                _ => false,
+            })
+        };
+
+        // Gather the type names visible from the scope.
+        let mut candidates = vec![];
+        let mut next = Some(scope_id);
+        while let Some(scope_id) = next {
+            next = None;
+            if !(*BUILTIN_SCOPE_REFS).contains(&scope_id) {
+                let scope = self.scope(scope_id)?;
+                for &defs_id in &scope.defs {
+                    for (name, defs) in self.defs(defs_id)? {
+                        if is_type(defs) {
+                            candidates.push(*name);
+                        }
+                    }
+                }
+                for (name, defs) in &scope.explicit_defs {
+                    if is_type(defs) {
+                        candidates.push(*name);
+                    }
+                }
+                next = scope.parent;
+            }
+            let tbl = self.sb.scope2_table.borrow();
+            if let Some(scope) = tbl.get(&scope_id) {
+                for (name, defs) in scope.defs.iter().chain(scope.imported_defs.iter()) {
+                    if is_type(defs) {
+                        candidates.push(*name);
+                    }
+                }
+                for id in &scope.imported_scopes {
+                    if let Some(scope) = tbl.get(id) {
+                        for (name, defs) in &scope.defs {
+                            if is_type(defs) {
+                                candidates.push(*name);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Pick the closest one, allowing roughly one typo per three characters.
+        let max_distance = std::cmp::max(1, name.len() / 3);
+        Ok(candidates
+            .into_iter()
+            .filter_map(|candidate| match candidate {
+                ResolvableName::Ident(n) => {
+                    Some((edit_distance(&name, &n.as_str().to_lowercase()), candidate))
+                }
+                _ => None,
+            })
+            .filter(|&(distance, _)| distance > 0 && distance <= max_distance)
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, candidate)| candidate))
+    }
+
     /// Resolve a compound name within a scope.
     pub fn resolve_compound_name<'a>(
         &self,
@@ -801,9 +1372,21 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
 
                     // Perform the name resolution in the scope determined
                     // above.
+                    let prefix_span = seen_span;
                     seen_span.expand(pn.span);
                     res_name = self.resolvable_from_primary_name(pn)?;
-                    defs = self.resolve_name(res_name, scope, true, false)?;
+                    defs = self.resolve_name(res_name, scope, true, true)?;
+                    if defs.is_empty() {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "no declaration `{}` in `{}`",
+                                res_name.value,
+                                prefix_span.extract()
+                            ))
+                            .span(res_name.span),
+                        );
+                        return Err(());
+                    }
                 }
 
                 // All other name parts we do not resolve and simply pass back
@@ -820,12 +1403,12 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
 
     /// Get the builtin type `standard.boolean`.
     pub fn builtin_boolean_type(&self) -> &'ctx Ty {
-        self.intern_ty(Ty::Null)
+        self.intern_ty(BOOLEAN_TYPE.named_ty())
     }
 
     /// Get the builtin type `standard.time`.
     pub fn builtin_time_type(&self) -> &'ctx Ty {
-        self.intern_ty(Ty::Null)
+        self.intern_ty(TIME_TYPE.named_ty())
     }
 
     /// Get the builtin type `standard.string`.
@@ -833,9 +1416,9 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         self.intern_ty(Ty::Null)
     }
 
-    /// Get the builtin type `standard.severity`.
+    /// Get the builtin type `standard.severity_level`.
     pub fn builtin_severity_type(&self) -> &'ctx Ty {
-        self.intern_ty(Ty::Null)
+        self.intern_ty(SEVERITY_LEVEL_TYPE.named_ty())
     }
 }
 
@@ -1020,16 +1603,20 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 // TODO: Replace with the first literal in the enum.
                 Ok(self.intern_const(Const::Null))
             }
-            Ty::Physical(ref ty) => Ok(self.intern_const(ConstInt::new(
-                Some(ty.base.clone()),
+            Ty::Physical(ref ty) => Ok(self.intern_const(ConstPhysical::new(
+                ty.decl,
                 ty.base.left_bound.clone(),
+                ty.primary,
+                ty.units[ty.primary].abs.clone(),
             ))),
             Ty::Int(ref ty) => {
                 Ok(self.intern_const(ConstInt::new(Some(ty.clone()), ty.left_bound.clone())))
             }
+            Ty::Float(ref ty) => Ok(self.intern_const(ConstFloat::new(ty.left_bound.clone()))),
             Ty::UniversalInt => panic!("universal integer has no default value"),
             Ty::UnboundedInt => panic!("unbounded integer has no default value"),
             Ty::Subprog(..) => panic!("subprogram type has no default value"),
+            Ty::Range(..) => panic!("range type has no default value"),
             Ty::Access(_) => Ok(self.intern_const(Const::Null)),
             Ty::Array(ref ty) => {
                 self.emit(DiagBuilder2::bug(format!(
@@ -1055,6 +1642,14 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 // TODO: Use the correct default value.
                 Ok(self.intern_const(Const::Null))
             }
+            Ty::Protected(ref ty) => {
+                self.emit(DiagBuilder2::bug(format!(
+                    "default value for type `{}` not implemented",
+                    ty
+                )));
+                // TODO: Use the correct default value.
+                Ok(self.intern_const(Const::Null))
+            }
         }
     }
 
@@ -1070,12 +1665,31 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
 
     /// Internalize a type.
     ///
-    /// See `ScoreBoard::intern_ty`.
+    /// See `ScoreBoard::intern_ty`. Records in the session whether the type
+    /// had already been interned.
     pub fn intern_ty<T>(&self, ty: T) -> &'ctx Ty
     where
         T: Into<Ty>,
     {
-        self.sb.intern_ty(ty)
+        let (ty, hit) = self.sb.intern_ty_tracked(ty);
+        self.sess.record_intern(hit);
+        ty
+    }
+
+    /// Internalize the named type of a type mark.
+    ///
+    /// The type is named after its declaration rather than the name it is
+    /// referred to by, such that the shared named type does not depend on
+    /// which reference is encountered first. See `ScoreBoard::intern_named_ty`.
+    pub fn intern_named_ty(&self, tm: TypeMarkRef) -> &'ctx Ty {
+        if let Some(&ty) = self.sb.named_ty_table.borrow().get(&tm) {
+            return ty;
+        }
+        let name = match tm {
+            TypeMarkRef::Type(id) => self.ast(id).1.name.span,
+            TypeMarkRef::Subtype(id) => self.ast(id).1.name.span,
+        };
+        self.sb.intern_named_ty(name, tm)
     }
 }
 
@@ -1087,6 +1701,8 @@ pub struct Arenas {
     pub scope: Arena<Scope>,
     pub ty: Arena<Ty>,
     pub konst: Arena<Const>,
+    pub subtype_ind: Arena<ast::SubtypeInd>,
+    pub expr: Arena<ast::Expr>,
 }
 
 impl Arenas {
@@ -1099,6 +1715,54 @@ impl Arenas {
             scope: Arena::new(),
             ty: Arena::new(),
             konst: Arena::new(),
+            subtype_ind: Arena::new(),
+            expr: Arena::new(),
+        }
+    }
+}
+
+/// The outcome of type checking a library, as returned by `typeck_summary`.
+#[derive(Debug, Serialize)]
+pub struct TypeckSummary {
+    /// Whether the library was checked without errors.
+    pub ok: bool,
+    /// The nodes of the library whose type has been determined, sorted by
+    /// span.
+    pub types: Vec<TypeckSummaryNode>,
+    /// The diagnostics emitted during the check, sorted by span.
+    pub diagnostics: Vec<TypeckSummaryDiag>,
+}
+
+/// A node listed in a `TypeckSummary`.
+#[derive(Debug, Serialize)]
+pub struct TypeckSummaryNode {
+    pub span: TypeckSummarySpan,
+    /// The node's type, or `None` if it could not be determined.
+    pub ty: Option<String>,
+}
+
+/// A diagnostic listed in a `TypeckSummary`.
+#[derive(Debug, Serialize)]
+pub struct TypeckSummaryDiag {
+    pub severity: String,
+    pub message: String,
+    pub span: Option<TypeckSummarySpan>,
+}
+
+/// A location in a source file, as listed in a `TypeckSummary`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct TypeckSummarySpan {
+    pub file: String,
+    pub begin: usize,
+    pub end: usize,
+}
+
+impl From<Span> for TypeckSummarySpan {
+    fn from(span: Span) -> TypeckSummarySpan {
+        TypeckSummarySpan {
+            file: span.source.get_path().to_string(),
+            begin: span.begin,
+            end: span.end,
         }
     }
 }
@@ -1240,6 +1904,26 @@ impl From<char> for ResolvableName {
     }
 }
 
+/// Compute the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diag
+            } else {
+                1 + std::cmp::min(diag, std::cmp::min(above, row[j]))
+            };
+            diag = above;
+        }
+    }
+    row[b.len()]
+}
+
 impl From<Operator> for ResolvableName {
     fn from(op: Operator) -> ResolvableName {
         ResolvableName::Operator(op)
@@ -1322,6 +2006,7 @@ node_ref!(CompInstStmtRef);
 node_ref!(ForGenStmtRef);
 node_ref!(IfGenStmtRef);
 node_ref!(CaseGenStmtRef);
+node_ref!(GenBodyRef);
 node_ref!(ConstDeclRef);
 node_ref!(SignalDeclRef);
 node_ref!(VarDeclRef);
@@ -1391,6 +2076,9 @@ node_ref_group!(
     Subprog(SubprogDeclRef),
     SubprogInst(SubprogInstRef),
     Stmt(StmtRef),
+    GenParam(ForGenStmtRef),
+    LoopParam(LoopStmtRef),
+    Param(IntfObjRef),
 );
 
 node_ref_group!(
@@ -1401,7 +2089,11 @@ node_ref_group!(
     Pkg(PkgDeclRef),
     PkgBody(PkgBodyRef),
     Arch(ArchRef),
+    Block(BlockStmtRef),
     Process(ProcessStmtRef),
+    ForGen(ForGenStmtRef),
+    GenBody(GenBodyRef),
+    Loop(LoopStmtRef),
     Subprog(SubprogDeclRef),
     SubprogBody(SubprogBodyRef),
 );
@@ -1432,7 +2124,11 @@ impl From<TypeMarkRef> for Def {
     }
 }
 
-node_ref_group!(SignalRef: Intf(IntfSignalRef), Decl(SignalDeclRef),);
+node_ref_group!(
+    SignalRef: Intf(IntfSignalRef),
+    Decl(SignalDeclRef),
+    Guard(BlockStmtRef),
+);
 
 node_ref_group!(PkgRef: Decl(PkgDeclRef), Inst(PkgInstRef),);
 
@@ -1735,6 +2431,11 @@ node_storage!(AstTable<'ast>:
     intf_subprogs:   IntfSubprogRef     => (ScopeRef, &'ast ast::IntfSubprogDecl),
     intf_pkgs:       IntfPkgRef         => (ScopeRef, &'ast ast::PkgInst),
     intf_consts:     IntfConstRef       => (ScopeRef, &'ast ast::IntfObjDecl, SubtypeIndRef, &'ast ast::Ident),
+    intf_vars:       IntfVarRef         => (ScopeRef, &'ast ast::IntfObjDecl, SubtypeIndRef, &'ast ast::Ident),
+    intf_files:      IntfFileRef        => (ScopeRef, &'ast ast::IntfObjDecl, SubtypeIndRef, &'ast ast::Ident),
+
+    // Generic map actuals
+    generic_map_actuals: GenericMapRef => (ScopeRef, &'ast ast::Expr),
 
     // Declarations
     type_decls:            TypeDeclRef           => (ScopeRef, &'ast ast::TypeDecl),
@@ -1754,9 +2455,18 @@ node_storage!(AstTable<'ast>:
     exprs: ExprRef => (ScopeRef, &'ast ast::Expr),
 
     // Statements
-    proc_stmts:       ProcessStmtRef   => (ScopeRef, &'ast ast::Stmt),
-    sig_assign_stmts: SigAssignStmtRef => (ScopeRef, &'ast ast::Stmt),
-    var_assign_stmts: VarAssignStmtRef => (ScopeRef, &'ast ast::Stmt),
+    block_stmts:           BlockStmtRef         => (ScopeRef, &'ast ast::Stmt),
+    proc_stmts:            ProcessStmtRef       => (ScopeRef, &'ast ast::Stmt),
+    for_gen_stmts:         ForGenStmtRef        => (ScopeRef, &'ast ast::Stmt),
+    if_gen_stmts:          IfGenStmtRef         => (ScopeRef, &'ast ast::Stmt),
+    case_gen_stmts:        CaseGenStmtRef       => (ScopeRef, &'ast ast::Stmt),
+    gen_bodies:            GenBodyRef           => (ScopeRef, &'ast ast::GenBody),
+    sig_assign_stmts:      SigAssignStmtRef     => (ScopeRef, &'ast ast::Stmt),
+    conc_sig_assign_stmts: ConcSigAssignStmtRef => (ScopeRef, &'ast ast::Stmt),
+    conc_call_stmts:       ConcCallStmtRef      => (ScopeRef, &'ast ast::Stmt),
+    conc_assert_stmts:     ConcAssertStmtRef    => (ScopeRef, &'ast ast::Stmt),
+    comp_inst_stmts:       CompInstStmtRef      => (ScopeRef, &'ast ast::Stmt),
+    var_assign_stmts:      VarAssignStmtRef     => (ScopeRef, &'ast ast::Stmt),
 
     array_type_indices: ArrayTypeIndexRef => (ScopeRef, &'ast ast::Expr),
     type_marks:         LatentTypeMarkRef => (ScopeRef, LatentName<'ast>),
@@ -1783,8 +2493,18 @@ node_storage!(HirTable<'ctx>:
     signal_decls:          SignalDeclRef         => &'ctx hir::Decl<hir::SignalDecl>,
     variable_decls:        VarDeclRef            => &'ctx hir::Decl<hir::VarDecl>,
     file_decls:            FileDeclRef           => &'ctx hir::Decl<hir::FileDecl>,
+    block_stmts:           BlockStmtRef          => &'ctx hir::BlockStmt,
     process_stmts:         ProcessStmtRef        => &'ctx hir::ProcessStmt,
+    for_gen_stmts:         ForGenStmtRef         => &'ctx hir::ForGenStmt,
+    if_gen_stmts:          IfGenStmtRef          => &'ctx hir::IfGenStmt,
+    case_gen_stmts:        CaseGenStmtRef        => &'ctx hir::CaseGenStmt,
+    gen_bodies:            GenBodyRef            => &'ctx hir::GenBody,
     sig_assign_stmts:      SigAssignStmtRef      => &'ctx hir::SigAssignStmt,
+    conc_sig_assign_stmts: ConcSigAssignStmtRef  => &'ctx hir::SigAssignStmt,
+    conc_call_stmts:       ConcCallStmtRef       => CallStmtRef,
+    conc_assert_stmts:     ConcAssertStmtRef     => AssertStmtRef,
+    comp_decls:            CompDeclRef           => &'ctx hir::Component,
+    comp_inst_stmts:       CompInstStmtRef       => &'ctx hir::CompInstStmt,
     array_type_indices:    ArrayTypeIndexRef     => &'ctx Spanned<hir::ArrayTypeIndex>,
     subprogs:              SubprogDeclRef        => &'ctx hir::Subprog,
     subprog_bodies:        SubprogBodyRef        => &'ctx hir::SubprogBody,
@@ -1885,3 +2605,148 @@ impl<'ast> HasDesc for LatentName<'ast> {
         "name"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moore_common::InternStats;
+
+    struct Global;
+    impl GenericContext for Global {}
+
+    /// Set up a fresh scoreboard whose diagnostics are collected rather than
+    /// printed, and bind the session and context to the given names.
+    macro_rules! score_context {
+        ($sess:ident, $ctx:ident) => {
+            let $sess = Session::with_sink(RefCell::new(Vec::<DiagBuilder2>::new()));
+            let arenas = Arenas::new();
+            let sb = ScoreBoard::new(&arenas);
+            let lazy = LazyPhaseTable::new(&sb);
+            let $ctx = ScoreContext {
+                sess: &$sess,
+                global: &Global,
+                sb: &sb,
+                lazy: &lazy,
+            };
+        };
+    }
+
+    #[test]
+    fn intern_ty_stats() {
+        score_context!(sess, ctx);
+        let subty = || IntTy::new(ast::Dir::To, 3.into(), 11.into());
+        let a = ctx.intern_ty(subty());
+        let b = ctx.intern_ty(subty());
+        assert!(std::ptr::eq(a, b));
+        assert_eq!(
+            sess.intern_stats(),
+            InternStats {
+                hits: 1,
+                allocations: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn explain_type_diff() {
+        score_context!(sess, ctx);
+        let int = |dir, l: isize, r: isize| Ty::from(IntTy::new(dir, l.into(), r.into()));
+        let narrow = int(ast::Dir::To, 0, 7);
+        let bit = Ty::Enum(EnumTy::new(builtin::BIT_TYPE.id));
+        assert_eq!(ctx.explain_type_diff(&narrow, &narrow), None);
+        assert_eq!(
+            ctx.explain_type_diff(&narrow, &bit),
+            Some("base types differ: 0 to 7 vs enum".to_owned())
+        );
+        assert_eq!(
+            ctx.explain_type_diff(&narrow, &int(ast::Dir::To, 0, 15)),
+            Some("ranges differ: 0 to 7 vs 0 to 15".to_owned())
+        );
+        assert_eq!(
+            ctx.explain_type_diff(&narrow, &int(ast::Dir::Downto, 7, 0)),
+            Some("directions differ: 0 to 7 vs 7 downto 0".to_owned())
+        );
+    }
+
+    #[test]
+    fn type_mismatch_span() {
+        score_context!(sess, ctx);
+        let source = get_source_manager().add("type_mismatch_span.vhd", "s <= a + b;");
+        let operand = Span::new(source, 5, 10);
+        let exp = Ty::from(IntTy::new(ast::Dir::To, 0.into(), 7.into()));
+        let act = Ty::from(IntTy::new(ast::Dir::To, 0.into(), 15.into()));
+        let diag = ctx.type_mismatch_diag_at(Some(operand), &exp, &act);
+        assert_eq!(diag.get_primary_span(), Some(operand));
+        let diag = ctx.type_mismatch_diag_at(None, &exp, &act);
+        assert_eq!(diag.get_primary_span(), None);
+    }
+
+    #[test]
+    fn invalidate_types() {
+        score_context!(sess, ctx);
+        let expr = |data| {
+            let id = ExprRef(NodeId::alloc());
+            let hir = ctx.sb.arenas.hir.alloc(hir::Expr {
+                parent: *builtin::ROOT_SCOPE_REF,
+                span: INVALID_SPAN,
+                data: data,
+            });
+            ctx.set_hir(id, hir);
+            id
+        };
+        let lit = expr(hir::ExprData::IntegerLiteral(ConstInt::new(None, 5.into())));
+        let neg = expr(hir::ExprData::Unary(
+            Spanned::new(UnaryOp::Neg, INVALID_SPAN),
+            vec![],
+            lit,
+        ));
+        let ty = ctx.intern_ty(IntTy::new(ast::Dir::To, 0.into(), 7.into()));
+        ctx.sb.typeval_table.borrow_mut().insert(lit.into(), Ok(ty));
+
+        // Folding the expression again is a memoized hit, which returns the
+        // very same constant rather than allocating a new one.
+        let first = ctx.const_value(neg).unwrap();
+        assert!(std::ptr::eq(first, ctx.const_value(neg).unwrap()));
+        assert_eq!(ctx.sb.const_table.borrow().len(), 2);
+
+        ctx.sb.invalidate_types();
+        assert!(ctx.sb.const_table.borrow().is_empty());
+        assert!(ctx.sb.typeval_table.borrow().is_empty());
+        let second = ctx.const_value(neg).unwrap();
+        assert!(!std::ptr::eq(first, second));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn count_unimplemented() {
+        score_context!(sess, ctx);
+        assert!(sess.coverage_report().is_empty());
+        let tyc = TypeckContext::new(&ctx);
+        tyc.typeck(AttrDeclRef(NodeId::alloc()));
+        tyc.typeck(GroupDeclRef(NodeId::alloc()));
+        tyc.typeck(AttrDeclRef(NodeId::alloc()));
+        tyc.typeck(CfgRef(NodeId::alloc()));
+        assert!(!tyc.finish());
+        assert_eq!(
+            sess.coverage_report(),
+            vec![
+                ("AttrDeclRef".to_string(), 2),
+                ("CfgRef".to_string(), 1),
+                ("GroupDeclRef".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn cancel_typeck() {
+        score_context!(sess, ctx);
+        let tyc = TypeckContext::new(&ctx);
+        tyc.typeck(AttrDeclRef(NodeId::alloc()));
+        sess.cancel_token()
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(tyc.is_cancelled());
+        tyc.typeck(AttrDeclRef(NodeId::alloc()));
+        tyc.typeck_slice(&[AttrDeclRef(NodeId::alloc()), AttrDeclRef(NodeId::alloc())]);
+        assert_eq!(sess.coverage_report(), vec![("AttrDeclRef".to_string(), 1)]);
+    }
+}
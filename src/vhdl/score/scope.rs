@@ -26,7 +26,11 @@ impl_make_defs!(self, id: ScopeRef => {
         ScopeRef::Pkg(id)         => self.make(id),
         ScopeRef::PkgBody(id)     => self.make(id),
         ScopeRef::Arch(id)        => self.make(id),
+        ScopeRef::Block(id)       => self.make(id),
         ScopeRef::Process(id)     => self.make(id),
+        ScopeRef::ForGen(id)      => self.make(id),
+        ScopeRef::GenBody(id)     => self.make(id),
+        ScopeRef::Loop(id)        => self.make(id),
         ScopeRef::Subprog(id)     => self.make(id),
         ScopeRef::SubprogBody(id) => self.make(id),
     }
@@ -41,7 +45,11 @@ impl_make_scope!(self, id: ScopeRef => {
         ScopeRef::Pkg(id)         => self.make(id),
         ScopeRef::PkgBody(id)     => self.make(id),
         ScopeRef::Arch(id)        => self.make(id),
+        ScopeRef::Block(id)       => self.make(id),
         ScopeRef::Process(id)     => self.make(id),
+        ScopeRef::ForGen(id)      => self.make(id),
+        ScopeRef::GenBody(id)     => self.make(id),
+        ScopeRef::Loop(id)        => self.make(id),
         ScopeRef::Subprog(id)     => self.make(id),
         ScopeRef::SubprogBody(id) => self.make(id),
     }
@@ -146,10 +154,16 @@ impl_make_scope!(self, _id: CtxItemsRef => {
     }))
 });
 
-// Definitions in an entity.
-impl_make_defs!(self, _id: EntityRef => {
-    // TODO: Implement this.
-    Ok(self.sb.arenas.defs.alloc(HashMap::new()))
+// Definitions in an entity. The ports are visible in the architectures of
+// the entity. See IEEE 1076-2008 section 12.1.
+impl_make_defs!(self, id: EntityRef => {
+    let mut ctx = DefsContext::new(self);
+    let hir = self.hir(id)?;
+    for &port in &hir.ports {
+        let name = self.ast(port).3;
+        ctx.declare(Spanned::new(name.name, name.span).map_into(), Def::Signal(port.into()));
+    }
+    Ok(self.sb.arenas.defs.alloc(ctx.finish()?))
 });
 
 // Definitions in an architecture.
@@ -163,12 +177,12 @@ impl_make_defs!(self, id: ArchRef => {
 });
 
 // Definitions in a package declaration.
-impl_make_defs!(self, _id: PkgDeclRef => {
-    let ctx = DefsContext::new(self);
-    // let hir = self.hir(id)?;
-    // for &decl in &hir.decls {
-    //     ctx.declare_any_in_pkg(decl);
-    // }
+impl_make_defs!(self, id: PkgDeclRef => {
+    let mut ctx = DefsContext::new(self);
+    let hir = self.hir(id)?;
+    for &decl in &hir.decls {
+        ctx.declare_any_in_pkg(decl);
+    }
     Ok(self.sb.arenas.defs.alloc(ctx.finish()?))
 });
 
@@ -223,6 +237,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
         let (_, items) = self.ast(id);
         let mut defs = Vec::new();
         let mut explicit_defs = HashMap::new();
+        let mut has_fails = false;
         defs.push(id.into());
         for item in items {
             if let &ast::CtxItem::UseClause(Spanned {
@@ -232,7 +247,13 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 for name in names {
                     // TODO: This creates an infinite loop, since the name lookup requires the context items to be ready.
                     let (res_name, mut out_defs, valid_span, mut tail) =
-                        self.resolve_compound_name(name, id.into(), true)?;
+                        match self.resolve_compound_name(name, id.into(), true) {
+                            Ok(r) => r,
+                            Err(()) => {
+                                has_fails = true;
+                                continue;
+                            }
+                        };
 
                     // Resolve the optional `all`.
                     match tail.first() {
@@ -253,6 +274,7 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                                         ))
                                         .span(all_span),
                                     );
+                                    has_fails = true;
                                     continue;
                                 }
                                 None => unreachable!(),
@@ -270,18 +292,22 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                     if tail.len() > 0 {
                         let span = Span::union(valid_span.end().into(), name.span.end());
                         self.emit(DiagBuilder2::error("invalid name suffix").span(span));
+                        has_fails = true;
                         continue;
                     }
                 }
             }
         }
+        if has_fails {
+            return Err(());
+        }
         self.sb.scope_table.borrow_mut().insert(
             id.into(),
-            self.sb.arenas.scope.alloc(Scope {
+            Ok(self.sb.arenas.scope.alloc(Scope {
                 parent: Some(parent.unwrap_or(*ROOT_SCOPE_REF)),
                 defs: defs,
                 explicit_defs: explicit_defs,
-            }),
+            })),
         );
         Ok(id)
     }
@@ -316,8 +342,8 @@ impl_make_scope!(self, id: ArchRef => {
 // Populate the scope of a package declaration.
 impl_make_scope!(self, id: PkgDeclRef => {
     let hir = self.hir(id)?;
-    let defs = Vec::new();
-    // defs.push(id.into());
+    let mut defs = Vec::new();
+    defs.push(id.into());
     let parent = match hir.parent {
         ScopeRef::CtxItems(id) => self.make_ctx_items_scope(id, None)?.into(),
         others => others
@@ -397,6 +423,97 @@ impl_make_scope!(self, id: ProcessStmtRef => {
     }))
 });
 
+// Definitions in the region of a block statement. A guarded block implicitly
+// declares a signal `GUARD`. See IEEE 1076-2008 section 11.2.
+impl_make_defs!(self, id: BlockStmtRef => {
+    let mut ctx = DefsContext::new(self);
+    let hir = self.hir(id)?;
+    if let ast::BlockStmt { guard: Some(ref guard), .. } = self.ast(id).1.data {
+        let name = Spanned::new(get_name_table().intern("GUARD", false), guard.span);
+        ctx.declare(name.map_into(), Def::Signal(SignalRef::Guard(id)));
+    }
+    for &port in &hir.ports {
+        let name = self.ast(port).3;
+        ctx.declare(Spanned::new(name.name, name.span).map_into(), Def::Signal(port.into()));
+    }
+    for &decl in &hir.decls {
+        ctx.declare_any_in_block(decl);
+    }
+    Ok(self.sb.arenas.defs.alloc(ctx.finish()?))
+});
+
+impl_make_scope!(self, id: BlockStmtRef => {
+    let hir = self.existing_hir(id)?;
+    let mut defs = Vec::new();
+    defs.push(id.into());
+    Ok(self.sb.arenas.scope.alloc(Scope {
+        parent: Some(hir.parent),
+        defs: defs,
+        explicit_defs: HashMap::new(),
+    }))
+});
+
+// Definitions in the region generated by a for-generate statement. The
+// generate parameter is declared alongside the region's declarations.
+impl_make_defs!(self, id: ForGenStmtRef => {
+    let mut ctx = DefsContext::new(self);
+    let hir = self.hir(id)?;
+    ctx.declare(hir.param.map_into(), Def::GenParam(id));
+    for &decl in &hir.decls {
+        ctx.declare_any_in_block(decl);
+    }
+    Ok(self.sb.arenas.defs.alloc(ctx.finish()?))
+});
+
+impl_make_scope!(self, id: ForGenStmtRef => {
+    let hir = self.existing_hir(id)?;
+    let mut defs = Vec::new();
+    defs.push(id.into());
+    Ok(self.sb.arenas.scope.alloc(Scope {
+        parent: Some(hir.parent),
+        defs: defs,
+        explicit_defs: HashMap::new(),
+    }))
+});
+
+// Definitions in the region generated by a body of an if-generate or
+// case-generate statement.
+impl_make_defs!(self, id: GenBodyRef => {
+    let mut ctx = DefsContext::new(self);
+    let hir = self.hir(id)?;
+    for &decl in &hir.decls {
+        ctx.declare_any_in_block(decl);
+    }
+    Ok(self.sb.arenas.defs.alloc(ctx.finish()?))
+});
+
+impl_make_scope!(self, id: GenBodyRef => {
+    let hir = self.existing_hir(id)?;
+    let mut defs = Vec::new();
+    defs.push(id.into());
+    Ok(self.sb.arenas.scope.alloc(Scope {
+        parent: Some(hir.parent),
+        defs: defs,
+        explicit_defs: HashMap::new(),
+    }))
+});
+
+// Definitions in the region of a loop statement. The loop parameter is
+// declared in the revised scope table when the statement is added, so there
+// is nothing to gather here.
+impl_make_defs!(self, _id: LoopStmtRef => {
+    Ok(self.sb.arenas.defs.alloc(HashMap::new()))
+});
+
+impl_make_scope!(self, id: LoopStmtRef => {
+    let parent = self.with_scope(id.into(), |scope| Ok(scope.parent))?;
+    Ok(self.sb.arenas.scope.alloc(Scope {
+        parent: parent,
+        defs: Vec::new(),
+        explicit_defs: HashMap::new(),
+    }))
+});
+
 // DeclInPkgRef::Pkg(id) => vec![(self.ast(id).1.name.map_into(), Def::Pkg(id))],
 // DeclInPkgRef::PkgInst(id) => vec![(self.ast(id).1.name.map_into(), Def::PkgInst(id))],
 // DeclInPkgRef::Type(id) => {
@@ -845,7 +845,7 @@ pub enum BinaryOp {
     Pow,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Dir {
     To,
     Downto,
@@ -973,6 +973,7 @@ pub enum TypeData {
     AccessType(SubtypeInd),
     FileType(CompoundName),
     ProtectedType(Vec<DeclItem>),
+    ProtectedBody(Vec<DeclItem>),
 }
 
 impl HasDesc for TypeData {
@@ -985,6 +986,7 @@ impl HasDesc for TypeData {
             TypeData::AccessType(..) => "access type declaration",
             TypeData::FileType(..) => "file type declaration",
             TypeData::ProtectedType(..) => "protected type declaration",
+            TypeData::ProtectedBody(..) => "protected type body",
         }
     }
 }
@@ -14,19 +14,49 @@ use moore_common::grind::{self, Grinder};
 use moore_common::source::*;
 
 pub fn parse(src: Source) -> Result<Vec<ast::DesignUnit>, ()> {
+    parse_with(src, &StderrEmitter, |p| {
+        Ok(parser::rules::parse_design_file(p))
+    })
+}
+
+/// The parser that `parse_with` passes to the rule it applies.
+pub type SourceParser<'a> = parser::basic::BasicParser<
+    grind::Vent<
+        grind::Iter<std::iter::Cloned<std::slice::Iter<'a, u8>>>,
+        &'a dyn Fn(DiagBuilder2),
+        DiagBuilder2,
+    >,
+>;
+
+/// Parse an entire source with a grammar rule.
+///
+/// The rule is applied to a parser on the source, e.g. to parse a standalone
+/// expression with `|p| parser::rules::parse_expr(p)`, and must consume the
+/// entire source. Diagnostics are passed to `emitter`.
+pub fn parse_with<T, R>(src: Source, emitter: &dyn DiagEmitter, rule: R) -> Result<T, ()>
+where
+    R: for<'a> FnOnce(&mut SourceParser<'a>) -> parser::rules::ReportedResult<T>,
+{
+    use self::lexer::token::Token;
     use self::parser::token_stream::TokenStream;
 
     // Get a grinder on the bytes of the source file.
     let content = src.get_content();
-    let bytes = grind::from_iter(content.bytes().iter().map(|x| *x))
-        .vent(|err: DiagBuilder2| eprintln!("{}", err));
+    let vent = |err: DiagBuilder2| emitter.emit(err);
+    let bytes = grind::from_iter(content.bytes().iter().cloned()).vent(&vent as &dyn Fn(_));
 
     // Perform lexical analysis on the bytes.
     let tokens = lexer::Lexer::new(bytes, src);
 
-    // Parse the file.
+    // Apply the rule and make sure nothing trails it.
     let mut parser = parser::basic::BasicParser::new(tokens);
-    let ast = parser::rules::parse_design_file(&mut parser);
+    let ast = rule(&mut parser).map_err(|_| ())?;
+    let Spanned { value, span } = parser.peek(0);
+    if value != Token::Eof {
+        parser.emit(
+            DiagBuilder2::error(format!("expected end of input, found {}", value)).span(span),
+        );
+    }
 
     if parser.is_error() {
         Err(())
@@ -34,3 +64,12 @@ pub fn parse(src: Source) -> Result<Vec<ast::DesignUnit>, ()> {
         Ok(ast)
     }
 }
+
+/// Prints diagnostics to stderr.
+struct StderrEmitter;
+
+impl DiagEmitter for StderrEmitter {
+    fn emit(&self, diag: DiagBuilder2) {
+        eprintln!("{}", diag);
+    }
+}
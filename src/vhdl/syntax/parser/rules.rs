@@ -120,6 +120,8 @@ pub fn parse_design_unit<P: Parser>(p: &mut P) -> RecoveredResult<ast::DesignUni
         Keyword(Kw::Package) => {
             if p.peek(1).value == Keyword(Kw::Body) {
                 parse_package_body(p).map(|d| ast::DesignUnitData::PkgBody(d))
+            } else if p.peek(2).value == Keyword(Kw::Is) && p.peek(3).value == Keyword(Kw::New) {
+                parse_package_inst(p, true).map(|d| ast::DesignUnitData::PkgInst(d))
             } else {
                 parse_package_decl(p).map(|d| ast::DesignUnitData::PkgDecl(d))
             }
@@ -406,6 +408,38 @@ where
     }
 }
 
+/// Parse the optional label at the end of a statement.
+///
+/// Unlike other closing identifiers, a closing label that does not repeat the
+/// statement's label is an error rather than a warning.
+fn parse_optional_closing_label<P: Parser>(p: &mut P, label: Option<Spanned<Name>>, sec: &str) {
+    let closing = match try_ident(p) {
+        Some(n) => n,
+        None => return,
+    };
+    match label {
+        Some(label) if label.value != closing.value => p.emit(
+            DiagBuilder2::error(format!(
+                "statement label mismatch: opening `{}`, closing `{}`",
+                label.value, closing.value
+            ))
+            .span(closing.span)
+            .add_note("opening label given here:")
+            .span(label.span)
+            .add_note(format!("see IEEE 1076-2008 {}", sec)),
+        ),
+        Some(_) => (),
+        None => p.emit(
+            DiagBuilder2::error(format!(
+                "statement is not labeled but has closing label `{}`",
+                closing.value
+            ))
+            .span(closing.span)
+            .add_note(format!("see IEEE 1076-2008 {}", sec)),
+        ),
+    }
+}
+
 /// Parse a context declaration. IEEE 1076-2008 section 13.3.
 ///
 /// ```text
@@ -1643,7 +1677,11 @@ pub fn parse_type_decl<P: Parser>(
                     require(p, Keyword(Kw::Body))?;
                 }
                 parse_optional_matching_ident(p, name, "type", "section 5.6");
-                ast::ProtectedType(decl_items)
+                if body {
+                    ast::ProtectedBody(decl_items)
+                } else {
+                    ast::ProtectedType(decl_items)
+                }
             }
 
             // Emit an error for anything else.
@@ -2773,7 +2811,7 @@ pub fn parse_if_stmt<P: Parser>(
     // Parse the rest.
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::If))?;
-    parse_optional_matching_ident(p, label, "if statement", "section 10.8");
+    parse_optional_closing_label(p, label, "section 10.8");
     require(p, Semicolon)?;
     Ok(ast::IfStmt {
         conds: conds,
@@ -2823,7 +2861,7 @@ pub fn parse_case_stmt<P: Parser>(
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Case))?;
     let trail_qm = accept(p, Qmark); // TODO: Check if this matches.
-    parse_optional_matching_ident(p, label, "case statement", "section 10.9");
+    parse_optional_closing_label(p, label, "section 10.9");
     require(p, Semicolon)?;
     Ok(ast::CaseStmt {
         qm: has_qm,
@@ -2864,7 +2902,7 @@ pub fn parse_loop_stmt<P: Parser>(
     let stmts = repeat_until(p, Keyword(Kw::End), parse_stmt)?;
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Loop))?;
-    parse_optional_matching_ident(p, label, "loop statement", "section 10.10");
+    parse_optional_closing_label(p, label, "section 10.10");
     require(p, Semicolon)?;
     Ok(ast::LoopStmt {
         scheme: scheme,
@@ -2992,7 +3030,7 @@ pub fn parse_if_generate_stmt<P: Parser>(
     // Parse the rest.
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Generate))?;
-    parse_optional_matching_ident(p, label, "generate statement", "section 11.8");
+    parse_optional_closing_label(p, label, "section 11.8");
     require(p, Semicolon)?;
     Ok(ast::IfGenStmt {
         conds: conds,
@@ -3038,7 +3076,7 @@ pub fn parse_case_generate_stmt<P: Parser>(
     // Parse the rest.
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Generate))?;
-    parse_optional_matching_ident(p, label, "generate statement", "section 11.8");
+    parse_optional_closing_label(p, label, "section 11.8");
     require(p, Semicolon)?;
     Ok(ast::CaseGenStmt {
         switch: switch,
@@ -3063,7 +3101,7 @@ pub fn parse_for_generate_stmt<P: Parser>(
     let body = parse_generate_body(p, label, Keyword(Kw::End))?;
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Generate))?;
-    parse_optional_matching_ident(p, label, "generate statement", "section 11.8");
+    parse_optional_closing_label(p, label, "section 11.8");
     require(p, Semicolon)?;
     Ok(ast::ForGenStmt {
         param: param.into(),
@@ -3138,7 +3176,7 @@ where
     let has_end = if p.peek(0).value == Keyword(Kw::End) && p.peek(1).value != Keyword(Kw::Generate)
     {
         p.bump();
-        parse_optional_matching_ident(p, label, "generate body", "section 11.8");
+        parse_optional_closing_label(p, label, "section 11.8");
         require(p, Semicolon)?;
         true
     } else {
@@ -3172,7 +3210,7 @@ pub fn parse_block_stmt<P: Parser>(
     let stmts = repeat_until(p, Keyword(Kw::End), parse_stmt)?;
     require(p, Keyword(Kw::End))?;
     require(p, Keyword(Kw::Block))?;
-    parse_optional_matching_ident(p, label, "block", "section 11.2");
+    parse_optional_closing_label(p, label, "section 11.2");
     require(p, Semicolon)?;
     Ok(ast::BlockStmt {
         guard: guard,
@@ -3214,7 +3252,7 @@ pub fn parse_proc_stmt<P: Parser>(
     // Parse the rest.
     let postponed = accept(p, Keyword(Kw::Postponed));
     require(p, Keyword(Kw::Process))?;
-    parse_optional_matching_ident(p, label, "process", "section 11.3");
+    parse_optional_closing_label(p, label, "section 11.3");
     require(p, Semicolon)?;
     Ok(ast::ProcStmt {
         sensitivity: sensitivity,
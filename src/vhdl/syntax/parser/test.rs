@@ -96,6 +96,7 @@ fn design_unit() {
     parse!("entity foo is end;", parse_design_unit);
     // parse!("configuration foo is begin end;", parse_design_unit);
     parse!("package foo is end;", parse_design_unit);
+    parse!("package foo is new bar generic map (8);", parse_design_unit);
     parse!("context foo is end;", parse_design_unit);
 }
 
@@ -538,3 +539,24 @@ fn config_decl() {
         parse_config_decl
     );
 }
+
+#[test]
+fn parse_with() {
+    use moore_common::Session;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let sink = Rc::new(RefCell::new(Vec::new()));
+    let sess = Session::with_sink(sink.clone());
+    let src = get_source_manager().add_anonymous("integer range 0 to 7");
+    assert!(crate::parse_with(src, &sess, |p| parse_subtype_ind(p)).is_ok());
+    let src = get_source_manager().add_anonymous("2**8 - 1");
+    assert!(crate::parse_with(src, &sess, |p| parse_expr(p)).is_ok());
+    assert!(sink.borrow().is_empty());
+
+    let src = get_source_manager().add_anonymous("2**8 - 1;");
+    assert!(crate::parse_with(src, &sess, |p| parse_expr(p)).is_err());
+    let diags = sink.borrow();
+    assert_eq!(diags.len(), 1);
+    assert_eq!(diags[0].message, "expected end of input, found `;`");
+}
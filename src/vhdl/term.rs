@@ -10,7 +10,7 @@ use std::marker::PhantomData;
 use num::{BigInt, BigRational};
 
 use crate::common::errors::*;
-use crate::common::name::Name;
+use crate::common::name::{get_name_table, Name};
 use crate::common::score::Result;
 use crate::common::source::*;
 use crate::common::util::*;
@@ -19,7 +19,7 @@ use crate::common::SessionContext;
 use crate::add_ctx::AddContext;
 use crate::arenas::Alloc;
 use crate::hir;
-use crate::konst::ConstInt;
+use crate::konst::{ConstFloat, ConstInt};
 use crate::op::*;
 use crate::scope2::{Def2, ScopeData, TypeVariantDef};
 use crate::score::*;
@@ -63,6 +63,8 @@ pub enum Term<'t> {
     Enum(Vec<Spanned<EnumRef>>),
     /// A term that refers to an enum variant.
     Enum2(Vec<Spanned<Def2<'t>>>),
+    /// A term that refers to a set of overloaded subprograms.
+    Subprog(Vec<Spanned<Def>>),
     /// A term of the form `T.<name>`.
     Select(Subterm<'t>, Spanned<ResolvableName>),
     /// A term of the form `T.all`.
@@ -94,6 +96,8 @@ pub enum Term<'t> {
     Binary(Spanned<BinaryOp>, Subterm<'t>, Subterm<'t>),
     /// A term of the form `T'T`.
     Qual(Subterm<'t>, Subterm<'t>),
+    /// A term of the form `T'<name>`.
+    Attr(Subterm<'t>, Spanned<ResolvableName>),
     /// A term of the form `new T`.
     New(Subterm<'t>),
 }
@@ -350,6 +354,13 @@ where
                         //  let sp = Span::union(term.span, attr.span);
                         //  Spanned::new(Term::Attribute(Box::new(term), Spanned::new(id, span)), sp)
                         // }
+                        // Predefined attributes such as `'image` are not
+                        // declared anywhere and remain unresolved.
+                        Term::Unresolved(name) => {
+                            let sp = Span::union(term.span, attr.span);
+                            let name = Spanned::new(name, attr.span);
+                            Spanned::new(Term::Attr(Box::new(term), name), sp)
+                        }
                         Term::Ident(other) => {
                             self.emit(
                                 DiagBuilder2::error(format!(
@@ -362,7 +373,14 @@ where
                             );
                             return Err(());
                         }
-                        _ => unreachable!(),
+                        // A type or subprogram of the same name does not hide
+                        // a predefined attribute, e.g. `WORD'high` with a
+                        // subtype `HIGH` in scope.
+                        _ => {
+                            let sp = Span::union(term.span, attr.span);
+                            let name = Spanned::new(ident.name.into(), attr.span);
+                            Spanned::new(Term::Attr(Box::new(term), name), sp)
+                        }
                     }
                 }
                 ast::NamePart::Call(ref paren_elems) => {
@@ -578,7 +596,14 @@ impl<'t, C: DiagEmitter, S> DefSpecificTermContext<'t, Def> for TermContext<C, S
                 _ => false,
             }
         }
+        fn is_subprog(def: &Spanned<Def>) -> bool {
+            match def.value {
+                Def::Subprog(..) | Def::BuiltinOp(..) => true,
+                _ => false,
+            }
+        }
         let all_enum = defs.iter().all(is_enum);
+        let all_subprog = defs.iter().all(is_subprog);
 
         // Handle overloading. Basically if the definitions are all enum fields
         // or functions, that's fine. For everything else the name must be
@@ -595,7 +620,12 @@ impl<'t, C: DiagEmitter, S> DefSpecificTermContext<'t, Def> for TermContext<C, S
                 }
                 Term::Enum(ids)
             }
-            // TODO: Handle the function case.
+            // Overloaded subprograms are resolved once the call they appear
+            // in is type checked.
+            _ if all_subprog && !defs.is_empty() => {
+                defs.push(first_def);
+                Term::Subprog(defs)
+            }
             _ if !defs.is_empty() => {
                 let mut d =
                     DiagBuilder2::error(format!("`{}` is ambiguous", name.value)).span(name.span);
@@ -683,6 +713,8 @@ pub trait ScopeSpecificTermContext<'t, S, D> {
     ) -> Result<Spanned<Term<'t>>>;
 
     fn maybe_selectable_scope(&self, term: &Term<'t>) -> Option<S>;
+
+    fn resolve_type_mark(&self, name: Spanned<ResolvableName>) -> Result<Spanned<TypeMarkRef>>;
 }
 
 impl<'t, 'sbc, 'lazy, 'sb, 'ast, 'ctx> ScopeSpecificTermContext<'t, ScopeRef, Def>
@@ -699,7 +731,10 @@ where
         name: Spanned<ResolvableName>,
         scope: ScopeRef,
     ) -> Result<Spanned<Term<'t>>> {
-        let defs = self.ctx.resolve_name(name, scope, false, true)?;
+        // A name selected from a library or package only denotes declarations
+        // made therein, not everything that is visible from there.
+        let only_defs = scope != self.scope;
+        let defs = self.ctx.resolve_name(name, scope, only_defs, true)?;
         self.termify_defs(name, defs)
     }
 
@@ -715,6 +750,14 @@ where
             None
         }
     }
+
+    /// Resolve a name to a type mark within the term context's scope.
+    fn resolve_type_mark(&self, name: Spanned<ResolvableName>) -> Result<Spanned<TypeMarkRef>> {
+        match *self.ctx.resolve_type_mark(name, self.scope)? {
+            Ty::Named(_, tm) => Ok(Spanned::new(tm, name.span)),
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl<'t> ScopeSpecificTermContext<'t, &'t ScopeData<'t>, Def2<'t>>
@@ -741,6 +784,12 @@ impl<'t> ScopeSpecificTermContext<'t, &'t ScopeData<'t>, Def2<'t>>
             None
         }
     }
+
+    /// Resolve a name to a type mark within the term context's scope.
+    fn resolve_type_mark(&self, name: Spanned<ResolvableName>) -> Result<Spanned<TypeMarkRef>> {
+        self.emit(DiagBuilder2::error(format!("unknown type `{}`", name.value)).span(name.span));
+        Err(())
+    }
 }
 
 impl<'sbc, 'lazy, 'sb, 'ast, 'ctx>
@@ -771,6 +820,54 @@ where
         }
     }
 
+    /// Resolve a name to all the definitions it denotes.
+    ///
+    /// Unlike `termify_compound_name`, the name may denote multiple overloaded
+    /// subprograms. Apart from the primary name, it may only consist of
+    /// selections, the last of which is resolved in the selected scope.
+    pub fn resolve_overloaded_name(&self, ast: &ast::CompoundName) -> Result<Vec<Spanned<Def>>> {
+        let (last, prefix) = match ast.parts.split_last() {
+            Some(x) => x,
+            None => {
+                let name = ResolvableName::from_primary_name(&ast.primary, self.ctx)?;
+                return self.ctx.resolve_name(name, self.scope, false, false);
+            }
+        };
+        let name = match *last {
+            ast::NamePart::Select(ref primary) => {
+                ResolvableName::from_primary_name(primary, self.ctx)?
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a simple or selected name",
+                        ast.span.extract()
+                    ))
+                    .span(ast.span),
+                );
+                return Err(());
+            }
+        };
+        let prefix = ast::CompoundName {
+            parts: prefix.to_vec(),
+            ..ast.clone()
+        };
+        let term = self.termify_compound_name(&prefix)?;
+        match self.maybe_selectable_scope(&term.value) {
+            Some(scope) => self.ctx.resolve_name(name, scope, true, false),
+            None => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a library or package",
+                        term.span.extract()
+                    ))
+                    .span(term.span),
+                );
+                Err(())
+            }
+        }
+    }
+
     /// Map a term to an expression and schedule the necessary tasks.
     pub fn term_to_expr(&self, term: Spanned<Term>) -> Result<ExprRef> {
         let ctx = AddContext::new(self.ctx, self.scope);
@@ -792,6 +889,20 @@ where
                 return Err(());
             }
             Term::IntLit(value) => hir::ExprData::IntegerLiteral(ConstInt::new(None, value)),
+            Term::FloatLit(value) => hir::ExprData::FloatLiteral(ConstFloat::new(value)),
+            Term::PhysLit(value, unit) => match unit.value {
+                EitherUnit::Old(u) => hir::ExprData::PhysLiteral(value, Spanned::new(u, unit.span)),
+                EitherUnit::New(_) => {
+                    self.emit(
+                        DiagBuilder2::bug(format!(
+                            "physical literal `{}` not supported in this context",
+                            term_span.extract()
+                        ))
+                        .span(term_span),
+                    );
+                    return Err(());
+                }
+            },
             Term::StrLit(value) => {
                 // Create a set of characters used in the literal. Then resolve
                 // each as an individual bit literal. This yields multiple enums
@@ -895,6 +1006,10 @@ where
                 Def::Signal(id) => hir::ExprData::SignalName(id),
                 Def::Var(id) => hir::ExprData::VarName(id),
                 Def::File(id) => hir::ExprData::FileName(id),
+                Def::GenParam(id) => hir::ExprData::GenParamName(id),
+                Def::LoopParam(id) => hir::ExprData::LoopParamName(id),
+                Def::Param(id) => hir::ExprData::ParamName(id),
+                Def::Subprog(..) | Def::BuiltinOp(..) => hir::ExprData::OverloadedName(vec![def]),
                 _ => {
                     self.emit(
                         DiagBuilder2::error(format!(
@@ -909,6 +1024,10 @@ where
                 }
             },
             Term::Enum(defs) => hir::ExprData::EnumName(defs),
+            Term::Subprog(defs) => hir::ExprData::OverloadedName(defs),
+            Term::Range(dir, lb, rb) => {
+                hir::ExprData::Range(dir.value, self.term_to_expr(*lb)?, self.term_to_expr(*rb)?)
+            }
             Term::Select(term, name) => hir::ExprData::Select(self.term_to_expr(*term)?, name),
             Term::Paren(subterm) => {
                 // A parenthesis with only one element is just a parenthesized
@@ -924,13 +1043,12 @@ where
                 }
             }
             Term::Aggregate(..) => hir::ExprData::Aggregate(self.term_to_aggregate(term)?.value),
+            Term::Attr(prefix, name) => self.term_to_attr_expr(*prefix, name, None)?,
             Term::Qual(tm, term) => {
                 let tm = self.term_to_type_mark(*tm)?;
                 let expr = self.term_to_expr(*term)?;
-                self.ctx.set_type_context(
-                    expr,
-                    self.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value)),
-                );
+                self.ctx
+                    .set_type_context(expr, self.ctx.intern_named_ty(tm.value));
                 hir::ExprData::Qualified(tm, expr)
             }
 
@@ -943,10 +1061,8 @@ where
                     Term::Qual(tm, value) => {
                         let tm = self.term_to_type_mark(*tm)?;
                         let expr = self.term_to_expr(*value)?;
-                        self.ctx.set_type_context(
-                            expr,
-                            self.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value)),
-                        );
+                        self.ctx
+                            .set_type_context(expr, self.ctx.intern_named_ty(tm.value));
                         hir::ExprData::Allocator(tm, Some(expr))
                     }
                     other => hir::ExprData::Allocator(
@@ -963,45 +1079,15 @@ where
                 let args = self.term_to_assoc_list(*args)?;
                 match callee.value {
                     Term::TypeMark(tm) => {
-                        if args.value.len() != 1 {
-                            self.emit(
-                                DiagBuilder2::error(format!(
-                                    "cast `{}` must have exactly one argument",
-                                    term_span.extract()
-                                ))
-                                .span(args.span),
-                            );
-                            return Err(());
-                        }
-                        let arg = args.value.into_iter().next().unwrap();
-                        if let Some(formal) = arg.formal {
-                            self.emit(
-                                DiagBuilder2::error(format!(
-                                    "cast argument `{}` cannot have a formal part",
-                                    arg.span.extract()
-                                ))
-                                .span(formal.span),
-                            );
-                        }
-                        let arg = match arg.actual.value {
-                            hir::AssocActual::Expr(id) => id,
-                            _ => {
-                                self.emit(
-                                    DiagBuilder2::error(format!(
-                                        "`{}` is not a valid cast argument",
-                                        arg.actual.span.extract()
-                                    ))
-                                    .span(arg.actual.span),
-                                );
-                                return Err(());
-                            }
-                        };
-                        self.ctx.set_type_context(
-                            arg,
-                            self.ctx.intern_ty(Ty::Named(tm.span.into(), tm.value)),
-                        );
+                        let arg = self.assoc_list_to_single_expr(args, "cast", term_span)?;
+                        self.ctx
+                            .set_type_context(arg, self.ctx.intern_named_ty(tm.value));
                         hir::ExprData::Cast(tm, arg)
                     }
+                    Term::Attr(prefix, name) => {
+                        let arg = self.assoc_list_to_single_expr(args, "attribute", term_span)?;
+                        self.term_to_attr_expr(*prefix, name, Some(arg))?
+                    }
                     other => hir::ExprData::Call(
                         self.term_to_expr(Spanned::new(other, callee.span))?,
                         args,
@@ -1028,10 +1114,128 @@ where
         })
     }
 
+    /// Map an attribute name and its optional argument to an expression.
+    fn term_to_attr_expr(
+        &self,
+        prefix: Spanned<Term>,
+        name: Spanned<ResolvableName>,
+        arg: Option<ExprRef>,
+    ) -> Result<hir::ExprData> {
+        if is_base_attr(name.value) {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` denotes a type and cannot be used as an expression",
+                    Span::union(prefix.span, name.span).extract()
+                ))
+                .span(name.span)
+                .add_note("`'base` may only be used as the prefix of another attribute."),
+            );
+            return Err(());
+        }
+        let prefix = self.fold_base_attr(prefix)?;
+        match prefix.value {
+            Term::TypeMark(tm) => {
+                // The argument of `T'image(x)` is of the prefix type.
+                if let (Some(arg), ResolvableName::Ident(n)) = (arg, name.value) {
+                    if n == get_name_table().intern("IMAGE", false) {
+                        self.ctx
+                            .set_type_context(arg, self.ctx.intern_named_ty(tm.value));
+                    }
+                }
+                Ok(hir::ExprData::TypeAttr(tm, name, arg))
+            }
+            other => Ok(hir::ExprData::Attr(
+                self.term_to_expr(Spanned::new(other, prefix.span))?,
+                name,
+                arg,
+            )),
+        }
+    }
+
+    /// Replace a `T'base` term with the type mark of the base type of `T`.
+    ///
+    /// See IEEE 1076-2008 section 16.2.2.
+    fn fold_base_attr<'t>(&self, term: Spanned<Term<'t>>) -> Result<Spanned<Term<'t>>> {
+        match term.value {
+            Term::Attr(prefix, name) if is_base_attr(name.value) => {
+                let prefix = self.fold_base_attr(*prefix)?;
+                match prefix.value {
+                    Term::TypeMark(tm) => {
+                        let base = Spanned::new(self.ctx.base_type_mark(tm.value)?, term.span);
+                        Ok(Spanned::new(Term::TypeMark(base), term.span))
+                    }
+                    _ => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "`'base` requires a type or subtype prefix, but `{}` is not a \
+                                 type",
+                                prefix.span.extract()
+                            ))
+                            .span(prefix.span)
+                            .add_note("See IEEE 1076-2008 section 16.2.2."),
+                        );
+                        Err(())
+                    }
+                }
+            }
+            other => Ok(Spanned::new(other, term.span)),
+        }
+    }
+
+    /// Map an association list to the single expression it contains.
+    ///
+    /// This is used for casts and attributes, which take exactly one
+    /// positional argument. The `what` is used in diagnostics.
+    fn assoc_list_to_single_expr(
+        &self,
+        args: Spanned<hir::AssocList>,
+        what: &str,
+        span: Span,
+    ) -> Result<ExprRef> {
+        if args.value.len() != 1 {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "{} `{}` must have exactly one argument",
+                    what,
+                    span.extract()
+                ))
+                .span(args.span),
+            );
+            return Err(());
+        }
+        let arg = args.value.into_iter().next().unwrap();
+        if let Some(formal) = arg.formal {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "{} argument `{}` cannot have a formal part",
+                    what,
+                    arg.span.extract()
+                ))
+                .span(formal.span),
+            );
+        }
+        match arg.actual.value {
+            hir::AssocActual::Expr(id) => Ok(id),
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid {} argument",
+                        arg.actual.span.extract(),
+                        what
+                    ))
+                    .span(arg.actual.span),
+                );
+                Err(())
+            }
+        }
+    }
+
     /// Map a term to a type mark.
     pub fn term_to_type_mark(&self, term: Spanned<Term>) -> Result<Spanned<TypeMarkRef>> {
+        let term = self.fold_base_attr(term)?;
         match term.value {
             Term::TypeMark(tm) => Ok(tm),
+            Term::Unresolved(name) => self.resolve_type_mark(Spanned::new(name, term.span)),
             _ => {
                 self.emit(
                     DiagBuilder2::error(format!(
@@ -1053,11 +1257,12 @@ where
     /// subtype indication. This function performs certain precedence swaps and
     /// combines terms into higher level ones, e.g. `Term::SubtypeInd`.
     pub fn fold_term_as_type<'t>(&self, term: Spanned<Term<'t>>) -> Result<Spanned<Term<'t>>> {
+        let term = self.fold_base_attr(term)?;
         let (new, new_term) = match term.value {
-            Term::Unresolved(name) => {
-                self.emit(DiagBuilder2::error(format!("`{}` is unknown", name)).span(term.span));
-                return Err(());
-            }
+            Term::Unresolved(name) => (
+                true,
+                Term::TypeMark(self.resolve_type_mark(Spanned::new(name, term.span))?),
+            ),
             Term::RangeSuffix(subterm, range) => {
                 let subterm = self.fold_term_as_type(*subterm)?;
                 let range = self.fold_term_as_type(*range)?;
@@ -1097,6 +1302,18 @@ where
                     ),
                 }
             }
+            Term::PrefixParen(resol, subterm) => {
+                let subterm = self.fold_term_as_type(*subterm)?;
+                match subterm.value {
+                    // Fold `R TypeMark` to `SubtypeInd`.
+                    Term::TypeMark(tm) => (true, Term::SubtypeInd(tm, Some(resol), None)),
+                    // Fold `R SubtypeInd` to `SubtypeInd`.
+                    Term::SubtypeInd(tm, None, con) => {
+                        (true, Term::SubtypeInd(tm, Some(resol), con))
+                    }
+                    _ => (false, Term::PrefixParen(resol, Box::new(subterm))),
+                }
+            }
             others => (false, others),
         };
         let new_term = Spanned::new(new_term, term.span);
@@ -1125,7 +1342,7 @@ where
                 return Err(());
             }
         };
-        let _resol = match resol {
+        let resol = match resol {
             Some(x) => Some(self.term_to_resolution_indication(*x)?),
             None => None,
         };
@@ -1137,7 +1354,7 @@ where
             hir::SubtypeInd {
                 span: term.span,
                 type_mark: tm,
-                // TODO: Track resolution indication.
+                resolution: resol,
                 constraint: con,
             },
             term.span,
@@ -1148,7 +1365,31 @@ where
     }
 
     /// Map a term to a resolution indication.
-    pub fn term_to_resolution_indication(&self, term: Spanned<Term>) -> Result<Spanned<()>> {
+    ///
+    /// Only resolution function names are supported. Returns the definition of
+    /// the function.
+    pub fn term_to_resolution_indication(&self, term: Spanned<Term>) -> Result<Spanned<Def>> {
+        match term.value {
+            Term::Ident(def) => match def.value {
+                Def::Subprog(_) => return Ok(Spanned::new(def.value, term.span)),
+                Def::Alias(id) if self.ctx.is_subprog_alias(id) => {
+                    return Ok(Spanned::new(def.value, term.span))
+                }
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is not a resolution function",
+                            term.span.extract()
+                        ))
+                        .span(term.span)
+                        .add_note(format!("`{}` was declared here:", term.span.extract()))
+                        .span(def.span),
+                    );
+                    return Err(());
+                }
+            },
+            _ => (),
+        }
         self.emit(
             DiagBuilder2::bug(format!(
                 "interpretation of `{}` as a resolution indication not implemented",
@@ -1368,6 +1609,10 @@ where
                 Spanned::new(add_ctx.add_subtype_ind_hir(hir.value)?.into(), hir.span)
             }
             Term::Range(..) => self.term_to_range(term)?.map_into(),
+            Term::Attr(_, name) if is_range_attr(name.value) => {
+                let span = term.span;
+                Spanned::new(hir::Range::Attr(self.term_to_expr(term)?).into(), span)
+            }
             _ => {
                 self.emit(
                     DiagBuilder2::error(format!(
@@ -1517,9 +1762,12 @@ where
                 Term::SubtypeInd(..) | Term::TypeMark(..) | Term::Range(..) => {
                     hir::Choice::DiscreteRange(self.term_to_discrete_range(term)?.value)
                 }
-                Term::IntLit(..) | Term::Unary(..) | Term::Binary(..) => {
-                    hir::Choice::Expr(self.term_to_expr(term)?)
-                }
+                Term::IntLit(..)
+                | Term::StrLit(..)
+                | Term::Enum(..)
+                | Term::Ident(..)
+                | Term::Unary(..)
+                | Term::Binary(..) => hir::Choice::Expr(self.term_to_expr(term)?),
                 _ => {
                     self.emit(
                         DiagBuilder2::error(format!(
@@ -1596,39 +1844,34 @@ where
         let mut positional = Vec::new();
         let mut named = Vec::new();
         let mut others = None;
-        #[derive(PartialOrd, Ord, PartialEq, Eq)]
-        enum Mode {
-            Positional = 0,
-            Named = 1,
-            Others = 2,
-        }
-        let mut mode = Mode::Positional;
+        let mut first_named = None;
+        let mut others_span = None;
         for field in fields {
-            if mode == Mode::Others {
+            if let Some(others_span) = others_span {
                 self.emit(
-                    DiagBuilder2::error(format!(
-                        "aggregate element `{}` appears after `others`",
-                        field.span.extract()
-                    ))
-                    .span(field.span)
-                    .add_note(
-                        "The `others` element must be the last in an aggregate. See IEEE \
-                         1076-2008 section 9.3.3.1.",
-                    ),
+                    DiagBuilder2::error("`others` must be the last choice")
+                        .span(others_span)
+                        .add_note(format!(
+                            "`{}` follows the `others` element:",
+                            field.span.extract()
+                        ))
+                        .span(field.span)
+                        .add_note("See IEEE 1076-2008 section 9.3.3.1."),
                 );
                 return Err(());
             }
 
             // Handle positional elements.
             if field.value.0.is_empty() {
-                if mode > Mode::Positional {
+                if let Some(first_named) = first_named {
                     self.emit(
                         DiagBuilder2::error(format!(
-                            "positional element `{}` must appear before all named elements in \
-                             aggregate",
+                            "positional association `{}` after named association is illegal",
                             field.value.1.span.extract()
                         ))
                         .span(field.value.1.span)
+                        .add_note("The first named association is here:")
+                        .span(first_named)
                         .add_note("See IEEE 1076-2008 section 9.3.3.1."),
                     );
                     return Err(());
@@ -1646,12 +1889,12 @@ where
                     return Err(());
                 }
                 others = Some(field.value.1);
-                mode = Mode::Others;
+                others_span = Some(field.span);
             }
             // Handle named elements.
             else {
+                first_named.get_or_insert(field.span);
                 named.push(field);
-                mode = Mode::Named;
             }
         }
 
@@ -1798,6 +2041,14 @@ where
                 Term::Aggregate(fields) => fields
                     .into_iter()
                     .map(|(formal, actual)| {
+                        if formal.is_empty() {
+                            let actual = self.term_to_assoc_actual(actual)?;
+                            return Ok(hir::AssocElement {
+                                span: actual.span,
+                                formal: None,
+                                actual: actual,
+                            });
+                        }
                         if formal.len() != 1 {
                             self.emit(
                                 DiagBuilder2::error(
@@ -1809,13 +2060,12 @@ where
                             return Err(());
                         }
                         let formal = formal.into_iter().next().unwrap();
-                        let formal_span = formal.span;
                         let span = Span::union(formal.span, actual.span);
-                        let formal = self.term_to_expr(formal)?;
+                        let formal = self.term_to_formal_name(formal)?;
                         let actual = self.term_to_assoc_actual(actual)?;
                         Ok(hir::AssocElement {
                             span: span,
-                            formal: Some(Spanned::new(formal, formal_span)),
+                            formal: Some(formal),
                             actual: actual,
                         })
                     })
@@ -1839,6 +2089,33 @@ where
         ))
     }
 
+    /// Map a term to the name of a formal in an association list.
+    ///
+    /// The formal is declared by the called subprogram and is therefore not
+    /// visible where the association list appears. It is either unknown, or
+    /// refers to an unrelated declaration of the same name. Thus the name is
+    /// taken verbatim from the source.
+    pub fn term_to_formal_name(&self, term: Spanned<Term>) -> Result<Spanned<Name>> {
+        if let Term::Unresolved(ResolvableName::Ident(name)) = term.value {
+            return Ok(Spanned::new(name, term.span));
+        }
+        let text = term.span.extract();
+        let is_ident = text.starts_with(|c: char| c.is_ascii_alphabetic())
+            && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is not a valid formal", text))
+                    .span(term.span)
+                    .add_note("Only simple names are supported as formals."),
+            );
+            return Err(());
+        }
+        Ok(Spanned::new(
+            get_name_table().intern(&text, false),
+            term.span,
+        ))
+    }
+
     /// Map a term to an association actual.
     pub fn term_to_assoc_actual(&self, term: Spanned<Term>) -> Result<Spanned<hir::AssocActual>> {
         let term_span = term.span;
@@ -1915,3 +2192,15 @@ where
         }
     }
 }
+
+/// Check whether an attribute name refers to the predefined `'base` attribute.
+fn is_base_attr(name: ResolvableName) -> bool {
+    name == get_name_table().intern("BASE", false).into()
+}
+
+/// Check whether an attribute name is `'range` or `'reverse_range`.
+fn is_range_attr(name: ResolvableName) -> bool {
+    let table = get_name_table();
+    name == table.intern("RANGE", false).into()
+        || name == table.intern("REVERSE_RANGE", false).into()
+}
@@ -5,15 +5,17 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
-use num::{BigInt, One};
+use num::{BigInt, BigRational, One, Signed, ToPrimitive};
 
 use crate::common::name::Name;
+use crate::common::score::Result;
 use crate::common::source::Span;
 pub use crate::hir::Dir;
 use crate::score::*;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Ty {
     /// A named type. In a signal declaration for example, the source code
     /// mentions the type of the signal. This type name is resolved to its
@@ -32,6 +34,8 @@ pub enum Ty {
     /// evaluated at compile time, e.g. as part of a range expression. Cannot be
     /// mapped to LLHD.
     UnboundedInt,
+    /// A floating-point type.
+    Float(FloatTy),
     /// An enumeration type.
     Enum(EnumTy),
     /// A physical type.
@@ -44,8 +48,13 @@ pub enum Ty {
     File(Box<Ty>),
     /// A record type.
     Record(RecordTy),
+    /// A protected type.
+    Protected(ProtectedTy),
     /// A subprogram type.
     Subprog(SubprogTy),
+    /// A range type. This is the type of range expressions such as `0 to 7`
+    /// and range attributes such as `T'range`.
+    Range(RangeTy),
 }
 
 impl Ty {
@@ -57,13 +66,16 @@ impl Ty {
             Ty::Named(..) => "named type",
             Ty::Null => "null type",
             Ty::Int(_) | Ty::UnboundedInt | Ty::UniversalInt => "integer type",
+            Ty::Float(_) => "floating-point type",
             Ty::Enum(_) => "enumeration type",
             Ty::Physical(_) => "physical type",
             Ty::Access(_) => "access type",
             Ty::Array(_) => "array type",
             Ty::File(..) => "file type",
             Ty::Record(_) => "record type",
+            Ty::Protected(_) => "protected type",
             Ty::Subprog(_) => "subprogram type",
+            Ty::Range(_) => "range",
         }
     }
 
@@ -78,9 +90,244 @@ impl Ty {
     /// Check if this type is a real.
     pub fn is_real(&self) -> bool {
         match *self {
+            Ty::Float(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if this type is a scalar type.
+    ///
+    /// Integer, floating-point, enumeration, and physical types are scalar.
+    /// Named types are dereferenced first. See IEEE 1076-2008 section 5.2.
+    pub fn is_scalar<'a, 'ctx: 'a>(&'a self, ctx: &ScoreContext<'_, '_, '_, 'ctx>) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_scalar_flat())
+    }
+
+    /// Check if this type is a discrete type.
+    ///
+    /// Integer and enumeration types are discrete. Physical types are scalar
+    /// but not discrete. Named types are dereferenced first. See IEEE
+    /// 1076-2008 section 5.2.1.
+    pub fn is_discrete<'a, 'ctx: 'a>(
+        &'a self,
+        ctx: &ScoreContext<'_, '_, '_, 'ctx>,
+    ) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_discrete_flat())
+    }
+
+    /// Check if this type is a composite type.
+    ///
+    /// Array and record types are composite. Named types are dereferenced
+    /// first. See IEEE 1076-2008 section 5.3.
+    pub fn is_composite<'a, 'ctx: 'a>(
+        &'a self,
+        ctx: &ScoreContext<'_, '_, '_, 'ctx>,
+    ) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_composite_flat())
+    }
+
+    /// Check if this type is an array type, constrained or not.
+    ///
+    /// Named types are dereferenced first. See IEEE 1076-2008 section 5.3.2.
+    pub fn is_array<'a, 'ctx: 'a>(&'a self, ctx: &ScoreContext<'_, '_, '_, 'ctx>) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_array_flat())
+    }
+
+    /// Check if this type is an access type.
+    ///
+    /// Named types are dereferenced first. See IEEE 1076-2008 section 5.4.
+    pub fn is_access<'a, 'ctx: 'a>(&'a self, ctx: &ScoreContext<'_, '_, '_, 'ctx>) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_access_flat())
+    }
+
+    /// Check if this type is a file type.
+    ///
+    /// Named types are dereferenced first. See IEEE 1076-2008 section 5.5.
+    pub fn is_file<'a, 'ctx: 'a>(&'a self, ctx: &ScoreContext<'_, '_, '_, 'ctx>) -> Result<bool> {
+        Ok(ctx.deref_named_type(self)?.is_file_flat())
+    }
+
+    /// Determine the element type of this type.
+    ///
+    /// This is the element type of an array, or the type of the values in a
+    /// file. Returns `None` for all other types. Named types are dereferenced
+    /// first.
+    pub fn element_type<'a, 'ctx: 'a>(
+        &'a self,
+        ctx: &ScoreContext<'_, '_, '_, 'ctx>,
+    ) -> Result<Option<&'a Ty>> {
+        Ok(ctx.deref_named_type(self)?.element_type_flat())
+    }
+
+    /// Determine the element type, without dereferencing named types.
+    fn element_type_flat(&self) -> Option<&Ty> {
+        match *self {
+            Ty::Array(ref at) => Some(&at.element),
+            Ty::File(ref ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Check if this type is scalar, without dereferencing named types.
+    fn is_scalar_flat(&self) -> bool {
+        match *self {
+            Ty::Int(_)
+            | Ty::UniversalInt
+            | Ty::UnboundedInt
+            | Ty::Float(_)
+            | Ty::Enum(_)
+            | Ty::Physical(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if this type is discrete, without dereferencing named types.
+    fn is_discrete_flat(&self) -> bool {
+        match *self {
+            Ty::Int(_) | Ty::UniversalInt | Ty::UnboundedInt | Ty::Enum(_) => true,
             _ => false,
         }
     }
+
+    /// Check if this type is composite, without dereferencing named types.
+    fn is_composite_flat(&self) -> bool {
+        match *self {
+            Ty::Array(_) | Ty::Record(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if this type is an array, without dereferencing named types.
+    fn is_array_flat(&self) -> bool {
+        match *self {
+            Ty::Array(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if this type is an access type, without dereferencing named types.
+    fn is_access_flat(&self) -> bool {
+        match *self {
+            Ty::Access(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Check if this type is a file type, without dereferencing named types.
+    fn is_file_flat(&self) -> bool {
+        match *self {
+            Ty::File(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Render the type with ANSI colors for terminal diagnostics.
+    pub fn colored(&self) -> ColoredTy {
+        ColoredTy(self)
+    }
+
+    /// Render the type in a compact, canonical form.
+    ///
+    /// Unlike `Display`, which is meant for humans, this is meant to be stable
+    /// such that tests can compare against it. The format is as follows:
+    ///
+    /// - Named types are rendered as their lowercase simple name, with any
+    ///   library or package prefix stripped, e.g. `std_logic`.
+    /// - Integer and physical types are rendered as `range <l> <dir> <r>`,
+    ///   physical types followed by ` units (<u>, ...)` in declaration order.
+    /// - Universal and unbounded integers are `universal_integer` and
+    ///   `integer`, the null type is `null`.
+    /// - Arrays are `array (<index>, ...) of <elem>`, where unbounded indices
+    ///   are `<ty> range <>` and constrained indices are the index type.
+    /// - Records are `record <name>: <ty>; ... end record` on a single line,
+    ///   fields in declaration order.
+    /// - Ranges are `range <l> <dir> <r> of <ty>` if their bounds are static,
+    ///   and `range of <ty>` otherwise.
+    /// - Subprograms are `(<name>: <ty>, ...) return <ty>`.
+    /// - Enumerations and protected types are `enum` and `protected`.
+    /// - Access and file types are `access <ty>` and `file of <ty>`.
+    pub fn canonical_string(&self) -> String {
+        match *self {
+            Ty::Named(TyName::Name(name), _) => name.to_string().to_lowercase(),
+            Ty::Named(TyName::Span(span), _) => {
+                let text = span.extract();
+                text.rsplit('.').next().unwrap_or("").trim().to_lowercase()
+            }
+            Ty::Null => "null".into(),
+            Ty::Int(ref ty) => format!("range {}", ty),
+            Ty::UniversalInt => "universal_integer".into(),
+            Ty::UnboundedInt => "integer".into(),
+            Ty::Float(ref ty) => format!("range {}", ty),
+            Ty::Enum(_) => "enum".into(),
+            Ty::Physical(ref ty) => format!(
+                "range {} units ({})",
+                ty.base,
+                ty.units
+                    .iter()
+                    .map(|u| u.name.to_string().to_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Ty::Access(ref ty) => format!("access {}", ty.canonical_string()),
+            Ty::Array(ref ty) => format!(
+                "array ({}) of {}",
+                ty.indices
+                    .iter()
+                    .map(|index| match *index {
+                        ArrayIndex::Unbounded(ref ty) => {
+                            format!("{} range <>", ty.canonical_string())
+                        }
+                        ArrayIndex::Constrained(ref ty) => ty.canonical_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                ty.element.canonical_string()
+            ),
+            Ty::File(ref ty) => format!("file of {}", ty.canonical_string()),
+            Ty::Record(ref ty) => {
+                let mut out = String::from("record ");
+                for &(name, ref field) in &ty.fields {
+                    out.push_str(&format!(
+                        "{}: {}; ",
+                        name.to_string().to_lowercase(),
+                        field.canonical_string()
+                    ));
+                }
+                out.push_str("end record");
+                out
+            }
+            Ty::Protected(_) => "protected".into(),
+            Ty::Subprog(ref ty) => {
+                let args = ty
+                    .args
+                    .iter()
+                    .map(|arg| match arg.name {
+                        Some(name) => format!(
+                            "{}: {}",
+                            name.to_string().to_lowercase(),
+                            arg.ty.canonical_string()
+                        ),
+                        None => arg.ty.canonical_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match ty.ret {
+                    Some(ref ret) => format!("({}) return {}", args, ret.canonical_string()),
+                    None => format!("({})", args),
+                }
+            }
+            Ty::Range(ref ty) => match ty.static_bounds {
+                Some((ref l, ref r)) => format!(
+                    "range {} {} {} of {}",
+                    l,
+                    ty.dir,
+                    r,
+                    ty.bound.canonical_string()
+                ),
+                None => format!("range of {}", ty.bound.canonical_string()),
+            },
+        }
+    }
 }
 
 impl From<IntTy> for Ty {
@@ -89,6 +336,12 @@ impl From<IntTy> for Ty {
     }
 }
 
+impl From<FloatTy> for Ty {
+    fn from(t: FloatTy) -> Ty {
+        Ty::Float(t)
+    }
+}
+
 impl From<EnumTy> for Ty {
     fn from(t: EnumTy) -> Ty {
         Ty::Enum(t)
@@ -113,12 +366,24 @@ impl From<RecordTy> for Ty {
     }
 }
 
+impl From<ProtectedTy> for Ty {
+    fn from(t: ProtectedTy) -> Ty {
+        Ty::Protected(t)
+    }
+}
+
 impl From<SubprogTy> for Ty {
     fn from(t: SubprogTy) -> Ty {
         Ty::Subprog(t)
     }
 }
 
+impl From<RangeTy> for Ty {
+    fn from(t: RangeTy) -> Ty {
+        Ty::Range(t)
+    }
+}
+
 impl fmt::Display for Ty {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -127,13 +392,60 @@ impl fmt::Display for Ty {
             Ty::Int(ref ty) => write!(f, "{}", ty),
             Ty::UniversalInt => write!(f, "{{universal integer}}"),
             Ty::UnboundedInt => write!(f, "{{integer}}"),
+            Ty::Float(ref ty) => write!(f, "{}", ty),
             Ty::Enum(ref ty) => write!(f, "{}", ty),
             Ty::Physical(ref ty) => write!(f, "{}", ty),
             Ty::Access(ref ty) => write!(f, "access {}", ty),
             Ty::Array(ref ty) => write!(f, "{}", ty),
             Ty::File(ref ty) => write!(f, "file of {}", ty),
             Ty::Record(ref ty) => write!(f, "{}", ty),
+            Ty::Protected(ref ty) => write!(f, "{}", ty),
             Ty::Subprog(ref ty) => write!(f, "{}", ty),
+            Ty::Range(ref ty) => write!(f, "{}", ty),
+        }
+    }
+}
+
+/// The color of type names in terminal diagnostics.
+const NAME_COLOR: &str = "\x1B[32m";
+/// The color of constraints in terminal diagnostics.
+const CONSTRAINT_COLOR: &str = "\x1B[35m";
+/// Reset the foreground color, but keep any enclosing bold text.
+const RESET_COLOR: &str = "\x1B[39m";
+
+/// A type rendered with ANSI colors.
+///
+/// Type names and constraints are highlighted distinctly. Otherwise this reads
+/// the same as the `Display` implementation of `Ty`. The session strips the
+/// colors again if diagnostics do not go to a terminal.
+pub struct ColoredTy<'a>(pub &'a Ty);
+
+impl<'a> fmt::Display for ColoredTy<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.0 {
+            Ty::Named(name, _) => write!(f, "{}{}{}", NAME_COLOR, name, RESET_COLOR),
+            Ty::Int(ref ty) => write!(f, "{}{}{}", CONSTRAINT_COLOR, ty, RESET_COLOR),
+            Ty::Float(ref ty) => write!(f, "{}{}{}", CONSTRAINT_COLOR, ty, RESET_COLOR),
+            Ty::Access(ref ty) => write!(f, "access {}", ty.colored()),
+            Ty::File(ref ty) => write!(f, "file of {}", ty.colored()),
+            Ty::Array(ref ty) => {
+                write!(f, "array (")?;
+                for (i, index) in ty.indices.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match *index {
+                        ArrayIndex::Unbounded(ref ty) => write!(f, "{} range <>", ty.colored())?,
+                        ArrayIndex::Constrained(ref ty) => write!(f, "{}", ty.colored())?,
+                    }
+                }
+                write!(f, ") of {}", ty.element.colored())
+            }
+            Ty::Range(ref ty) => match ty.static_bounds {
+                Some(..) => write!(f, "{}{}{}", CONSTRAINT_COLOR, ty, RESET_COLOR),
+                None => write!(f, "range of {}", ty.bound.colored()),
+            },
+            ref other => write!(f, "{}", other),
         }
     }
 }
@@ -142,7 +454,7 @@ impl fmt::Display for Ty {
 ///
 /// Generally types are named by the source file. Builtin types on the other
 /// hand have no span, but rather have an explicit name.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 pub enum TyName {
     /// A type name given by a section of a source file.
     Span(Span),
@@ -172,7 +484,11 @@ impl From<Name> for TyName {
 }
 
 /// An integer type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality is sensitive to the direction of the range: `0 to 7` and `7 downto
+/// 0` are distinct subtypes, even though they contain the same values. Use
+/// `same_value_set` to compare only the values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IntTy {
     pub dir: Dir,
     pub left_bound: BigInt,
@@ -193,8 +509,8 @@ impl IntTy {
     /// if the range has a negative or zero length.
     pub fn maybe_null(self) -> Ty {
         match self.dir {
-            Dir::To if self.left_bound >= self.right_bound => Ty::Null,
-            Dir::Downto if self.left_bound <= self.right_bound => Ty::Null,
+            Dir::To if self.left_bound > self.right_bound => Ty::Null,
+            Dir::Downto if self.left_bound < self.right_bound => Ty::Null,
             _ => self.into(),
         }
     }
@@ -202,8 +518,28 @@ impl IntTy {
     /// The length of the range.
     pub fn len(&self) -> BigInt {
         match self.dir {
-            Dir::To => &self.left_bound + BigInt::one() - &self.right_bound,
-            Dir::Downto => &self.right_bound + BigInt::one() - &self.left_bound,
+            Dir::To => &self.right_bound + BigInt::one() - &self.left_bound,
+            Dir::Downto => &self.left_bound + BigInt::one() - &self.right_bound,
+        }
+    }
+
+    /// Check whether two ranges contain the same values, regardless of their
+    /// direction. All null ranges contain the same values.
+    pub fn same_value_set(&self, other: &IntTy) -> bool {
+        let (lo, hi) = self.low_high();
+        let (other_lo, other_hi) = other.low_high();
+        if lo > hi || other_lo > other_hi {
+            lo > hi && other_lo > other_hi
+        } else {
+            lo == other_lo && hi == other_hi
+        }
+    }
+
+    /// The lower and upper bound of the range.
+    fn low_high(&self) -> (&BigInt, &BigInt) {
+        match self.dir {
+            Dir::To => (&self.left_bound, &self.right_bound),
+            Dir::Downto => (&self.right_bound, &self.left_bound),
         }
     }
 }
@@ -214,18 +550,86 @@ impl fmt::Display for IntTy {
     }
 }
 
+/// A floating-point type.
+///
+/// The bounds are kept as the exact rational numbers written in the source.
+/// See IEEE 1076-2008 section 5.2.5.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FloatTy {
+    pub dir: Dir,
+    pub left_bound: BigRational,
+    pub right_bound: BigRational,
+}
+
+impl FloatTy {
+    /// Create a new floating-point type.
+    pub fn new(dir: Dir, left_bound: BigRational, right_bound: BigRational) -> FloatTy {
+        FloatTy {
+            dir: dir,
+            left_bound: left_bound,
+            right_bound: right_bound,
+        }
+    }
+}
+
+impl fmt::Display for FloatTy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {} {:?}",
+            rational_to_f64(&self.left_bound),
+            self.dir,
+            rational_to_f64(&self.right_bound)
+        )
+    }
+}
+
+/// Approximate a rational number as a float, for display purposes.
+///
+/// Values beyond the range of `f64` saturate to infinity or zero.
+pub fn rational_to_f64(value: &BigRational) -> f64 {
+    // Drop the low bits of large operands, such that their quotient can still
+    // be approximated if they do not fit into a float on their own.
+    let bits = std::cmp::max(value.numer().bits(), value.denom().bits());
+    let shift = bits.saturating_sub(f64::MAX_EXP as usize - 2);
+    let numer = (value.numer().abs() >> shift)
+        .to_f64()
+        .unwrap_or(f64::INFINITY);
+    let denom = (value.denom() >> shift).to_f64().unwrap_or(f64::INFINITY);
+    let approx = numer / denom;
+    if value.is_negative() {
+        -approx
+    } else {
+        approx
+    }
+}
+
 /// An enumeration type. Rather than keeping track of each enumeration value in
 /// here, we simply point at the type declaration.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EnumTy {
     /// The declaration of the enum.
     pub decl: TypeDeclRef,
+    /// The direction and the positions of the left and right bound literal,
+    /// if the type is a subtype constrained by a range.
+    pub range: Option<(Dir, usize, usize)>,
 }
 
 impl EnumTy {
     /// Create a new enumeration type.
     pub fn new(decl: TypeDeclRef) -> EnumTy {
-        EnumTy { decl: decl }
+        EnumTy {
+            decl: decl,
+            range: None,
+        }
+    }
+
+    /// Constrain the type to the literals in a range of positions.
+    pub fn with_range(self, dir: Dir, left: usize, right: usize) -> EnumTy {
+        EnumTy {
+            range: Some((dir, left, right)),
+            ..self
+        }
     }
 }
 
@@ -234,12 +638,15 @@ impl fmt::Display for EnumTy {
         // TODO: It should be possible somehow to print better information here.
         //       Maybe if we make `Ty` aware of its own internalization we could
         //       assign additional user-facing metadata, e.g. a variant list.
-        write!(f, "enum")
+        match self.range {
+            Some((dir, left, right)) => write!(f, "enum range #{} {} #{}", left, dir, right),
+            None => write!(f, "enum"),
+        }
     }
 }
 
 /// A physical type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PhysicalTy {
     /// The declaration of the physical type.
     pub decl: TypeDeclRef,
@@ -285,7 +692,7 @@ impl fmt::Display for PhysicalTy {
 }
 
 /// A unit of a physical type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PhysicalUnit {
     /// The name of the unit.
     pub name: Name,
@@ -307,8 +714,12 @@ impl PhysicalUnit {
 }
 
 /// An array type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ArrayTy {
+    /// The declaration of the array type, if any. Constrained subtypes retain
+    /// the declaration of their base type, such that two array types that
+    /// merely look the same, e.g. `signed` and `unsigned`, remain distinct.
+    pub decl: Option<TypeDeclRef>,
     /// The index types of the array, at least one.
     pub indices: Vec<ArrayIndex>,
     /// The type of the array element.
@@ -320,10 +731,16 @@ impl ArrayTy {
     pub fn new(indices: Vec<ArrayIndex>, element: Box<Ty>) -> ArrayTy {
         assert!(indices.len() > 0);
         ArrayTy {
+            decl: None,
             indices: indices,
             element: element,
         }
     }
+
+    /// Associate the array type with a declaration.
+    pub fn with_decl(self, decl: Option<TypeDeclRef>) -> ArrayTy {
+        ArrayTy { decl: decl, ..self }
+    }
 }
 
 impl fmt::Display for ArrayTy {
@@ -343,7 +760,7 @@ impl fmt::Display for ArrayTy {
 }
 
 /// An index type of an array type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ArrayIndex {
     /// An unbounded index of the form `<type_mark> range <>`.
     Unbounded(Box<Ty>),
@@ -394,6 +811,13 @@ impl RecordTy {
     }
 }
 
+// The lookup table is derived from the fields, so hashing the latter suffices.
+impl Hash for RecordTy {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.fields.hash(state);
+    }
+}
+
 impl fmt::Display for RecordTy {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "record")?;
@@ -410,11 +834,67 @@ impl fmt::Display for RecordTy {
     }
 }
 
+/// A protected type. Rather than keeping track of the methods in here, we
+/// simply point at the type declaration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProtectedTy {
+    /// The declaration of the protected type.
+    pub decl: TypeDeclRef,
+}
+
+impl ProtectedTy {
+    /// Create a new protected type.
+    pub fn new(decl: TypeDeclRef) -> ProtectedTy {
+        ProtectedTy { decl: decl }
+    }
+}
+
+impl fmt::Display for ProtectedTy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "protected")
+    }
+}
+
+/// A range type.
+///
+/// This is the type of range expressions and range attributes. It captures
+/// the direction of the range and the type of its bounds, and the bounds
+/// themselves if they are static integers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeTy {
+    /// The direction of the range.
+    pub dir: Dir,
+    /// The type of the bounds.
+    pub bound: Box<Ty>,
+    /// The left and right bound, if they are known statically.
+    pub static_bounds: Option<(BigInt, BigInt)>,
+}
+
+impl RangeTy {
+    /// Create a new range type.
+    pub fn new(dir: Dir, bound: Ty, static_bounds: Option<(BigInt, BigInt)>) -> RangeTy {
+        RangeTy {
+            dir: dir,
+            bound: Box::new(bound),
+            static_bounds: static_bounds,
+        }
+    }
+}
+
+impl fmt::Display for RangeTy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.static_bounds {
+            Some((ref left, ref right)) => write!(f, "{} {} {}", left, self.dir, right),
+            None => write!(f, "range of {}", self.bound),
+        }
+    }
+}
+
 /// A subprogram type.
 ///
 /// This is the type assigned to function and procedure declarations, as well as
 /// builtin operators.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubprogTy {
     /// The argument names and types.
     pub args: Vec<SubprogTyArg>,
@@ -452,7 +932,7 @@ impl fmt::Display for SubprogTy {
 }
 
 /// A subprogram argument type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubprogTyArg {
     /// The type of the argument.
     pub ty: Ty,
@@ -526,3 +1006,232 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::name::get_name_table;
+    use crate::common::score::NodeRef;
+
+    fn int() -> Ty {
+        IntTy::new(Dir::To, 0.into(), 7.into()).into()
+    }
+
+    fn enm() -> Ty {
+        EnumTy::new(TypeDeclRef::alloc()).into()
+    }
+
+    fn float() -> Ty {
+        let half = BigRational::new(1.into(), 2.into());
+        FloatTy::new(Dir::To, BigRational::from_integer(0.into()), half).into()
+    }
+
+    fn physical() -> Ty {
+        PhysicalTy::new(
+            TypeDeclRef::alloc(),
+            IntTy::new(Dir::To, 0.into(), 1000.into()),
+            vec![],
+            0,
+        )
+        .into()
+    }
+
+    fn array() -> Ty {
+        ArrayTy::new(
+            vec![ArrayIndex::Constrained(Box::new(int()))],
+            Box::new(enm()),
+        )
+        .into()
+    }
+
+    fn record() -> Ty {
+        RecordTy::new(vec![]).into()
+    }
+
+    #[test]
+    fn scalar_types() {
+        assert!(int().is_scalar_flat());
+        assert!(Ty::UniversalInt.is_scalar_flat());
+        assert!(Ty::UnboundedInt.is_scalar_flat());
+        assert!(float().is_scalar_flat());
+        assert!(enm().is_scalar_flat());
+        assert!(physical().is_scalar_flat());
+        assert!(!array().is_scalar_flat());
+        assert!(!record().is_scalar_flat());
+        assert!(!Ty::Access(Box::new(int())).is_scalar_flat());
+        assert!(!Ty::File(Box::new(int())).is_scalar_flat());
+        assert!(!Ty::Null.is_scalar_flat());
+    }
+
+    #[test]
+    fn discrete_types() {
+        assert!(int().is_discrete_flat());
+        assert!(Ty::UniversalInt.is_discrete_flat());
+        assert!(enm().is_discrete_flat());
+        assert!(!physical().is_discrete_flat());
+        assert!(!float().is_discrete_flat());
+        assert!(!array().is_discrete_flat());
+        assert!(!Ty::Null.is_discrete_flat());
+    }
+
+    #[test]
+    fn composite_types() {
+        assert!(array().is_composite_flat());
+        assert!(record().is_composite_flat());
+        assert!(!int().is_composite_flat());
+        assert!(!Ty::Access(Box::new(array())).is_composite_flat());
+        assert!(!Ty::File(Box::new(array())).is_composite_flat());
+    }
+
+    #[test]
+    fn array_types() {
+        assert!(array().is_array_flat());
+        assert!(!record().is_array_flat());
+        assert!(!int().is_array_flat());
+    }
+
+    #[test]
+    fn int_len() {
+        assert_eq!(IntTy::new(Dir::To, 0.into(), 7.into()).len(), 8.into());
+        assert_eq!(IntTy::new(Dir::Downto, 7.into(), 0.into()).len(), 8.into());
+        assert_eq!(IntTy::new(Dir::To, 1.into(), 1.into()).len(), 1.into());
+        assert_eq!(IntTy::new(Dir::To, 1.into(), 0.into()).len(), 0.into());
+    }
+
+    #[test]
+    fn int_maybe_null() {
+        let single = IntTy::new(Dir::To, 7.into(), 7.into());
+        assert_eq!(single.clone().maybe_null(), single.into());
+        assert_eq!(
+            IntTy::new(Dir::To, 1.into(), 0.into()).maybe_null(),
+            Ty::Null
+        );
+        assert_eq!(
+            IntTy::new(Dir::Downto, 0.into(), 1.into()).maybe_null(),
+            Ty::Null
+        );
+    }
+
+    #[test]
+    fn range_display() {
+        let range = RangeTy::new(Dir::To, int(), Some((0.into(), 7.into())));
+        assert_eq!(format!("{}", range), "0 to 7");
+        let range = RangeTy::new(Dir::Downto, int(), Some((7.into(), 0.into())));
+        assert_eq!(format!("{}", range), "7 downto 0");
+        let range: Ty = RangeTy::new(Dir::To, int(), None).into();
+        assert_eq!(format!("{}", range), "range of 0 to 7");
+        assert!(!range.is_scalar_flat());
+    }
+
+    #[test]
+    fn canonical_strings() {
+        let table = get_name_table();
+        assert_eq!(int().canonical_string(), "range 0 to 7");
+        assert_eq!(float().canonical_string(), "range 0.0 to 0.5");
+        assert_eq!(array().canonical_string(), "array (range 0 to 7) of enum");
+        let unbounded: Ty = ArrayTy::new(
+            vec![ArrayIndex::Unbounded(Box::new(Ty::Named(
+                table.intern("NATURAL", false).into(),
+                TypeDeclRef::alloc().into(),
+            )))],
+            Box::new(enm()),
+        )
+        .into();
+        assert_eq!(
+            unbounded.canonical_string(),
+            "array (natural range <>) of enum"
+        );
+        let rec: Ty = RecordTy::new(vec![
+            (table.intern("b", false), Box::new(int())),
+            (table.intern("a", false), Box::new(enm())),
+        ])
+        .into();
+        assert_eq!(
+            rec.canonical_string(),
+            "record b: range 0 to 7; a: enum; end record"
+        );
+        let range: Ty = RangeTy::new(Dir::Downto, int(), Some((7.into(), 0.into()))).into();
+        assert_eq!(range.canonical_string(), "range 7 downto 0 of range 0 to 7");
+        assert_eq!(
+            Ty::File(Box::new(int())).canonical_string(),
+            "file of range 0 to 7"
+        );
+    }
+
+    #[test]
+    fn int_equality_is_direction_sensitive() {
+        let up = IntTy::new(Dir::To, 0.into(), 7.into());
+        let down = IntTy::new(Dir::Downto, 7.into(), 0.into());
+        assert_ne!(up, down);
+        assert_ne!(Ty::from(up.clone()), Ty::from(down.clone()));
+        assert_eq!(up, IntTy::new(Dir::To, 0.into(), 7.into()));
+    }
+
+    #[test]
+    fn int_same_value_set() {
+        let up = IntTy::new(Dir::To, 0.into(), 7.into());
+        let down = IntTy::new(Dir::Downto, 7.into(), 0.into());
+        assert!(up.same_value_set(&down));
+        assert!(down.same_value_set(&up));
+        assert!(!up.same_value_set(&IntTy::new(Dir::Downto, 7.into(), 1.into())));
+        let null_up = IntTy::new(Dir::To, 1.into(), 0.into());
+        let null_down = IntTy::new(Dir::Downto, 3.into(), 5.into());
+        assert!(null_up.same_value_set(&null_down));
+        assert!(!null_up.same_value_set(&up));
+    }
+
+    #[test]
+    fn colored_types() {
+        let name: Ty = Ty::Named(
+            get_name_table().intern("BYTE", false).into(),
+            TypeDeclRef::alloc().into(),
+        );
+        assert_eq!(format!("{}", name.colored()), "\x1B[32mBYTE\x1B[39m");
+        assert_eq!(format!("{}", int().colored()), "\x1B[35m0 to 7\x1B[39m");
+        let file = Ty::File(Box::new(name));
+        assert_eq!(
+            crate::common::errors::strip_ansi(&format!("{}", file.colored())),
+            format!("{}", file)
+        );
+    }
+
+    #[test]
+    fn access_types() {
+        assert!(Ty::Access(Box::new(int())).is_access_flat());
+        assert!(!int().is_access_flat());
+        assert!(!Ty::File(Box::new(int())).is_access_flat());
+    }
+
+    #[test]
+    fn file_types() {
+        assert!(Ty::File(Box::new(int())).is_file_flat());
+        assert!(!int().is_file_flat());
+        assert!(!Ty::Access(Box::new(int())).is_file_flat());
+    }
+
+    #[test]
+    fn element_types() {
+        match array().element_type_flat() {
+            Some(&Ty::Enum(_)) => (),
+            other => panic!("unexpected element type {:?}", other),
+        }
+        assert_eq!(Ty::File(Box::new(int())).element_type_flat(), Some(&int()));
+        assert_eq!(int().element_type_flat(), None);
+        assert_eq!(record().element_type_flat(), None);
+        assert_eq!(Ty::Access(Box::new(int())).element_type_flat(), None);
+    }
+
+    #[test]
+    fn huge_float_bounds() {
+        let big: BigInt = num::pow(BigInt::from(10), 400);
+        let huge = BigRational::from_integer(big.clone());
+        assert_eq!(rational_to_f64(&huge), f64::INFINITY);
+        assert_eq!(rational_to_f64(&-huge), f64::NEG_INFINITY);
+        let tiny = BigRational::new(1.into(), big.clone());
+        assert_eq!(rational_to_f64(&tiny), 0.0);
+        let third = BigRational::new(big.clone() + BigInt::one(), big * BigInt::from(3));
+        assert!((rational_to_f64(&third) - 1.0 / 3.0).abs() < 1e-12);
+        let neg = BigRational::new((-3).into(), 4.into());
+        assert_eq!(rational_to_f64(&neg), -0.75);
+    }
+}
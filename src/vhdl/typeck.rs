@@ -2,18 +2,29 @@
 
 //! This module implements the type calculation of the scoreboard.
 
-use std::cell::Cell;
+use std::cell::{Cell, Ref, RefCell};
 use std::collections::HashMap;
 use std::fmt::Debug;
 
+use num::{pow, BigInt, One, ToPrimitive, Zero};
+
+use crate::add_ctx::AddContext;
 use crate::common::errors::*;
+use crate::common::name::{get_name_table, Name};
 use crate::common::score::{NodeMaker, NodeStorage, Result};
 use crate::common::source::{Span, Spanned, INVALID_SPAN};
-use crate::common::{NodeId, Verbosity};
+use crate::common::util::{HasDesc, HasSpan};
+use crate::common::{NodeId, Session, Verbosity};
 use crate::hir;
 use crate::konst::*;
 use crate::lazy::LazyNode;
+use crate::op::UnaryOp;
+use crate::overload_resolver::{
+    are_types_matching, reduce_overloads, OverloadReq, SignatureReq, TypeReq,
+};
 use crate::score::*;
+use crate::syntax::ast;
+use crate::term::TermContext;
 use crate::ty::*;
 
 /// A context to typecheck things in.
@@ -24,6 +35,55 @@ pub struct TypeckContext<'sbc, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb> {
     pub ctx: &'sbc ScoreContext<'lazy, 'sb, 'ast, 'ctx>,
     /// Whether any of the type checking failed.
     failed: Cell<bool>,
+    /// The state of the innermost region being type checked.
+    region: RefCell<TypeckRegion<'ctx>>,
+}
+
+/// A waveform element together with the types of its expressions.
+///
+/// Produced by `TypeckContext::typed_waveform`.
+#[derive(Clone, Copy, Debug)]
+pub struct TypedWaveElem<'ctx> {
+    /// The element itself.
+    pub elem: &'ctx hir::WaveElem,
+    /// The type of the value, or `None` if the element is `null`.
+    pub value_ty: Option<&'ctx Ty>,
+    /// The type of the `after` expression, or `None` if there is none.
+    pub after_ty: Option<&'ctx Ty>,
+}
+
+/// The state of a region being type checked.
+///
+/// Processes, subprograms, and loops introduce nested regions. Entering one
+/// via `TypeckContext::with_scope` modifies this state, and leaving it restores
+/// the state of the enclosing region.
+#[derive(Clone, Debug, Default)]
+pub struct TypeckRegion<'ctx> {
+    /// The subprogram whose body is being checked. Contains the return type
+    /// for functions and `None` for procedures.
+    pub subprog: Option<Option<&'ctx Ty>>,
+    /// Whether the statement being checked is in a process with a sensitivity
+    /// list.
+    pub sensitized: bool,
+    /// The loops enclosing the statement being checked, innermost last.
+    pub loops: Vec<LoopStmtRef>,
+    /// The type expected of expressions without a more specific context.
+    pub type_ctx: Option<&'ctx Ty>,
+}
+
+/// A guard that restores the enclosing region of a `TypeckContext` when
+/// dropped. Created by `TypeckContext::with_scope`.
+pub struct TypeckRegionGuard<'a, 'sbc: 'a, 'lazy: 'sbc, 'sb: 'lazy, 'ast: 'sb, 'ctx: 'sb> {
+    tyc: &'a TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>,
+    outer: Option<TypeckRegion<'ctx>>,
+}
+
+impl<'a, 'sbc, 'lazy, 'sb, 'ast, 'ctx> Drop
+    for TypeckRegionGuard<'a, 'sbc, 'lazy, 'sb, 'ast, 'ctx>
+{
+    fn drop(&mut self) {
+        *self.tyc.region.borrow_mut() = self.outer.take().unwrap();
+    }
 }
 
 impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
@@ -34,17 +94,60 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         TypeckContext {
             ctx: ctx,
             failed: Cell::new(false),
+            region: RefCell::new(TypeckRegion::default()),
         }
     }
 
+    /// Get the compiler session.
+    pub fn sess(&self) -> &'lazy Session {
+        self.ctx.sess
+    }
+
     /// Consume the context and return the result of the typeck.
+    ///
+    /// A cancelled typeck is not considered failed. Use `is_cancelled` to
+    /// tell whether the result covers all nodes.
     pub fn finish(self) -> bool {
         !self.failed.get()
     }
 
+    /// Check whether the typeck has been cancelled through the session.
+    ///
+    /// Once cancelled, no further nodes are checked.
+    pub fn is_cancelled(&self) -> bool {
+        self.ctx.sess.is_cancelled()
+    }
+
+    /// Enter a nested region.
+    ///
+    /// Calls `f` to derive the state of the nested region from the state of
+    /// the enclosing one. The enclosing state is restored when the returned
+    /// guard is dropped.
+    pub fn with_scope<'a, F>(&'a self, f: F) -> TypeckRegionGuard<'a, 'sbc, 'lazy, 'sb, 'ast, 'ctx>
+    where
+        F: FnOnce(&mut TypeckRegion<'ctx>),
+    {
+        let outer = self.region.borrow().clone();
+        f(&mut self.region.borrow_mut());
+        TypeckRegionGuard {
+            tyc: self,
+            outer: Some(outer),
+        }
+    }
+
+    /// Access the state of the innermost region.
+    pub fn region(&self) -> Ref<TypeckRegion<'ctx>> {
+        self.region.borrow()
+    }
+
     /// Emit a diagnostic message.
+    ///
+    /// Errors mark the type check as failed. If the session treats warnings as
+    /// errors, so do warnings.
     pub fn emit(&self, diag: DiagBuilder2) {
-        if diag.severity >= Severity::Error {
+        if diag.severity >= Severity::Error
+            || (diag.severity >= Severity::Warning && self.ctx.sess.opts.warnings_as_errors)
+        {
             self.failed.set(true);
         }
         self.ctx.sess.emit(diag)
@@ -157,152 +260,2850 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                     .span(span),
             );
         }
-        if exp == act {
-            return true;
-        }
         let (exp_flat, act_flat) = match (
+            self.types_match(exp, act),
             self.ctx.deref_named_type(exp),
             self.ctx.deref_named_type(act),
         ) {
-            (Ok(e), Ok(a)) => (e, a),
+            (Ok(true), ..) => return true,
+            (Ok(false), Ok(e), Ok(a)) => (e, a),
             _ => return false,
         };
-        match (exp_flat, act_flat) {
-            (e, a) if e == a => return true,
+        self.emit(
+            DiagBuilder2::error(format!(
+                "expected type {}, but `{}` has type {}",
+                exp.colored(),
+                span.snippet(),
+                act.colored()
+            ))
+            .span(span.first_line())
+            .add_note(format!("expected type: {}", exp_flat.colored()))
+            .add_note(format!("  actual type: {}", act_flat.colored())),
+        );
+        false
+    }
+
+    /// Check whether a value of type `act` can be used where a value of type
+    /// `exp` is expected, without emitting any diagnostics.
+    fn types_match(&self, exp: &'ctx Ty, act: &'ctx Ty) -> Result<bool> {
+        if exp == act {
+            return Ok(true);
+        }
+        let is_named = |ty: &Ty| match *ty {
+            Ty::Named(..) => true,
+            _ => false,
+        };
+        let exp_flat = self.ctx.deref_named_type(exp)?;
+        let act_flat = self.ctx.deref_named_type(act)?;
+        Ok(match (exp_flat, act_flat) {
+            (e, a) if e == a => true,
             // (e,a) if a.is_subtype_of(e) => return true,
-            (&Ty::Int(..), &Ty::UniversalInt) => return true,
-            _ => (),
+            (&Ty::Int(..), &Ty::UniversalInt) => true,
+            // Constrained array indices do not record the integer type they
+            // were derived from, so they match values of any integer type.
+            (&Ty::Int(..), &Ty::Int(..)) if !is_named(exp) || !is_named(act) => true,
+            // Enumeration literals are of the unconstrained type, and values
+            // of subtypes of the same type are interchangeable.
+            (&Ty::Enum(ref e), &Ty::Enum(ref a)) => e.decl == a.decl,
+            // Array values are implicitly converted to the expected subtype if
+            // their elements match and each dimension has the same length.
+            // Literals and aggregates carry no declaration. See IEEE 1076-2008
+            // section 10.6.2.1.
+            (&Ty::Array(ref e), &Ty::Array(ref a))
+                if e.indices.len() == a.indices.len() && (a.decl.is_none() || e.decl == a.decl) =>
+            {
+                if !self.types_match(&e.element, &a.element)? {
+                    return Ok(false);
+                }
+                for (ei, ai) in e.indices.iter().zip(a.indices.iter()) {
+                    let (et, at) = match (ei, ai) {
+                        (&ArrayIndex::Constrained(ref et), &ArrayIndex::Constrained(ref at)) => {
+                            (et, at)
+                        }
+                        _ => continue,
+                    };
+                    match (
+                        self.ctx.deref_named_type(et)?,
+                        self.ctx.deref_named_type(at)?,
+                    ) {
+                        (&Ty::Int(ref et), &Ty::Int(ref at)) if et.len() != at.len() => {
+                            return Ok(false);
+                        }
+                        _ => (),
+                    }
+                }
+                true
+            }
+            _ => false,
+        })
+    }
+
+    /// Ensure that a type is a one-dimensional array of `CHARACTER`.
+    ///
+    /// Report messages are not required to be of the exact `STRING` subtype,
+    /// such that concatenations and other string-like arrays are accepted.
+    /// See IEEE 1076-2008 section 10.3.
+    pub fn must_be_string(&self, act: &'ctx Ty, span: Span) -> Result<()> {
+        let act_flat = self.ctx.deref_named_type(act)?;
+        if let Ty::Array(ref at) = *act_flat {
+            let element = act.element_type(self.ctx)?.unwrap();
+            if at.indices.len() == 1 && self.is_character_type(element)? {
+                return Ok(());
+            }
         }
         self.emit(
             DiagBuilder2::error(format!(
-                "expected type {}, but `{}` has type {}",
-                exp,
+                "expected a string, but `{}` has type {}",
                 span.extract(),
                 act
             ))
             .span(span)
-            .add_note(format!("expected type: {}", exp_flat))
+            .add_note("expected type: one-dimensional array of CHARACTER")
             .add_note(format!("  actual type: {}", act_flat)),
         );
-        false
+        Err(())
     }
 
-    /// Ensure that one type can be cast into the other.
-    pub fn must_cast(&self, into: &'ctx Ty, from: &'ctx Ty, span: Span) -> bool {
-        self.must_match(into, from, span)
+    /// Type check the report message and severity of an assertion or report
+    /// statement.
+    ///
+    /// The message must be a string, and the severity of type
+    /// `SEVERITY_LEVEL`. See IEEE 1076-2008 sections 10.3 and 10.4.
+    pub fn typeck_report(&self, report: Option<ExprRef>, severity: Option<ExprRef>) -> Result<()> {
+        let report = match report {
+            Some(report) => self
+                .lazy_typeval(report)
+                .and_then(|ty| self.must_be_string(ty, self.ctx.span(report).unwrap())),
+            None => Ok(()),
+        };
+        let severity = match severity {
+            Some(severity) => {
+                self.typeck_assigned_value(severity, self.ctx.builtin_severity_type())
+            }
+            None => Ok(()),
+        };
+        report.and(severity)
     }
 
-    /// Type check the time expression in a delay mechanism.
-    pub fn typeck_delay_mechanism(&self, _node: &'ctx hir::DelayMechanism) {
-        // TODO: implement this
+    /// Check whether a type is the `CHARACTER` type.
+    ///
+    /// This is either the builtin type of package `STANDARD`, or a type that
+    /// has been declared with that name.
+    fn is_character_type(&self, ty: &Ty) -> Result<bool> {
+        match *self.ctx.deref_named_type(ty)? {
+            Ty::Enum(ref et) if et.decl == CHARACTER_TYPE.id => Ok(true),
+            Ty::Enum(ref et) if !is_builtin_type(et.decl) => {
+                let name = self.ctx.lazy_hir(et.decl)?.name.value;
+                Ok(name == get_name_table().intern("CHARACTER", false))
+            }
+            _ => Ok(false),
+        }
     }
 
-    /// Type check a waveform.
-    pub fn typeck_waveform(&self, node: &'ctx hir::Waveform, exp: &'ctx Ty) {
-        for elem in node {
-            self.typeck_wave_elem(elem, exp);
+    /// Check that the constants of a package body agree with the deferred
+    /// constants of the package they complete.
+    ///
+    /// Every deferred constant must have a full declaration in the package
+    /// body. Declaring it more than once is reported along with any other
+    /// duplicate declaration in the body. See IEEE 1076-2008 section 6.4.2.2.
+    pub fn typeck_deferred_consts(
+        &self,
+        pkg: PkgDeclRef,
+        decls: &[DeclInPkgBodyRef],
+    ) -> Result<()> {
+        let pkg_hir = self.ctx.hir(pkg)?;
+        let mut deferred = HashMap::new();
+        for &decl in &pkg_hir.decls {
+            if let DeclInPkgRef::Const(id) = decl {
+                let hir = self.ctx.lazy_hir(id)?;
+                if hir.decl.init.is_none() {
+                    deferred.insert(hir.name.value, id);
+                }
+            }
+        }
+        let mut failed = false;
+        for &decl in decls {
+            let id = match decl {
+                DeclInPkgBodyRef::Const(id) => id,
+                _ => continue,
+            };
+            let hir = self.ctx.lazy_hir(id)?;
+            let deferred_id = match deferred.remove(&hir.name.value) {
+                Some(id) => id,
+                None => continue,
+            };
+            let ty = self.lazy_typeval(id)?;
+            let deferred_ty = self.lazy_typeval(deferred_id)?;
+            if !are_types_matching(self.ctx, deferred_ty, ty) {
+                let deferred_hir = self.ctx.lazy_hir(deferred_id)?;
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "constant `{}` does not match its deferred declaration",
+                        hir.name.value
+                    ))
+                    .span(hir.name.span)
+                    .add_note(format!("deferred declaration has type {}", deferred_ty))
+                    .span(deferred_hir.name.span)
+                    .add_note(format!("full declaration has type {}", ty)),
+                );
+                failed = true;
+            }
+        }
+        let mut missing: Vec<_> = deferred.into_iter().map(|(_, id)| id).collect();
+        missing.sort();
+        for id in missing {
+            let hir = self.ctx.lazy_hir(id)?;
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "deferred constant `{}` has no full declaration in the package body",
+                    hir.name.value
+                ))
+                .span(hir.name.span)
+                .add_note(
+                    "A deferred constant must be declared again with a value in the package \
+                     body. See IEEE 1076-2008 section 4.8.",
+                ),
+            );
+            failed = true;
+        }
+        if failed {
+            Err(())
+        } else {
+            Ok(())
         }
     }
 
-    /// Type check a waveform element.
-    pub fn typeck_wave_elem(&self, node: &'ctx hir::WaveElem, _exp: &'ctx Ty) {
-        if let Some(_value) = node.value {
-            // TODO: type check value expression
-            // self.typeck_node(value, exp);
-            // let ty = self.lazy_typeval(value);
-            // self.must_match(exp, ty, node.span);
+    /// Check a generic map against the generics of the unit it instantiates.
+    ///
+    /// Associates each element of the map with its formal, either by position
+    /// or by name. The actual of a generic type must be a type mark, which is
+    /// recorded as the substitution for the formal within `inst`. Errors about
+    /// missing associations are reported at `span`.
+    ///
+    /// See IEEE 1076-2008 section 6.5.7.2.
+    pub fn typeck_generic_map(
+        &self,
+        inst: NodeId,
+        span: Span,
+        generics: &[GenericRef],
+        map: &[hir::GenericMapElem],
+    ) -> Result<()> {
+        let mut failed = false;
+        let mut assoc: Vec<Option<Span>> = vec![None; generics.len()];
+        for (index, elem) in map.iter().enumerate() {
+            let pos = match elem.formal {
+                Some(formal) => {
+                    match generics
+                        .iter()
+                        .position(|&g| self.generic_name(g) == Some(formal.value))
+                    {
+                        Some(pos) => pos,
+                        None => {
+                            self.emit(
+                                DiagBuilder2::error(format!("`{}` is not a generic", formal.value))
+                                    .span(formal.span),
+                            );
+                            failed = true;
+                            continue;
+                        }
+                    }
+                }
+                None if index < generics.len() => index,
+                None => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "too many generics; expected at most {}",
+                            generics.len()
+                        ))
+                        .span(elem.span),
+                    );
+                    failed = true;
+                    continue;
+                }
+            };
+            if let Some(previous) = assoc[pos] {
+                self.emit(
+                    DiagBuilder2::error("generic has already been associated")
+                        .span(elem.span)
+                        .add_note("previous association was here:")
+                        .span(previous),
+                );
+                failed = true;
+                continue;
+            }
+            assoc[pos] = Some(elem.span);
+            match generics[pos] {
+                GenericRef::Type(formal) => {
+                    let (scope, expr) = self.ctx.ast(elem.actual);
+                    let ctx = TermContext::new(self.ctx, scope);
+                    match ctx
+                        .termify_expr(expr)
+                        .and_then(|term| ctx.term_to_type_mark(term))
+                    {
+                        Ok(tm) => self.ctx.set_generic_type(inst, formal, tm),
+                        Err(()) => failed = true,
+                    }
+                }
+                GenericRef::Const(formal) => {
+                    failed |= self
+                        .typeck_generic_const_actual(formal, elem.actual)
+                        .is_err();
+                }
+                _ => (),
+            }
+        }
+        for (&generic, assoc) in generics.iter().zip(assoc.iter()) {
+            match (generic, assoc) {
+                (GenericRef::Type(formal), None) => {
+                    let name = self.ctx.ast(formal).1.name;
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "generic type `{}` is not associated",
+                            name.value
+                        ))
+                        .span(span)
+                        .add_note("generic type declared here:")
+                        .span(name.span),
+                    );
+                    failed = true;
+                }
+                (GenericRef::Const(formal), None) if self.ctx.ast(formal).1.default.is_none() => {
+                    let name = self.ctx.ast(formal).3;
+                    self.emit(
+                        DiagBuilder2::error(format!("generic `{}` is not associated", name.name))
+                            .span(span)
+                            .add_note("generic declared here:")
+                            .span(name.span),
+                    );
+                    failed = true;
+                }
+                _ => (),
+            }
         }
-        if let Some(_after) = node.after {
-            // TODO: type check time expression
-            // self.typeck_node(after, /* time type */);
+        if failed {
+            Err(())
+        } else {
+            Ok(())
         }
     }
 
-    /// Type check a subprogram specification.
-    pub fn typeck_subprog_spec(&self, node: &'ctx hir::SubprogSpec) {
-        self.typeck_slice(&node.generics);
-        // self.typeck_slice(&node.generic_map);
-        self.typeck_slice(&node.params);
-        if let Some(ref ty) = node.return_type {
-            self.typeck(ty.value);
+    /// Check the actual associated with a generic constant.
+    ///
+    /// The actual must match the type of the formal. It may only be `open` if
+    /// the formal declares a default value. See IEEE 1076-2008 section
+    /// 6.5.6.2.
+    fn typeck_generic_const_actual(
+        &self,
+        formal: IntfConstRef,
+        actual: GenericMapRef,
+    ) -> Result<()> {
+        let (scope, expr) = self.ctx.ast(actual);
+        let (_, decl, _, name) = self.ctx.ast(formal);
+        if let ast::OpenExpr = expr.data {
+            if decl.default.is_some() {
+                return Ok(());
+            }
+            self.emit(
+                DiagBuilder2::error(format!("generic `{}` cannot be left open", name.name))
+                    .span(expr.span)
+                    .add_note("A generic constant may only be left open if it has a default value.")
+                    .add_note("generic declared here:")
+                    .span(name.span),
+            );
+            return Err(());
         }
+        let ty = self.ctx.ty(formal)?;
+        let expr = AddContext::new(self.ctx, scope).add_expr(expr)?;
+        self.typeck_assigned_value(expr, ty)
     }
 
-    /// Type check any node that can have its type calculated.
-    pub fn typeck_node<I>(&self, id: I, exp: &'ctx Ty)
-    where
-        I: 'ctx + Copy + Debug + Into<NodeId>,
-        ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
-    {
-        if let Ok(act) = self.ctx.ty(id) {
-            if act != exp {
-                // TODO: We need some span information here!
-                self.emit(DiagBuilder2::error(format!(
-                    "typecheck failed, expected {:?}, got {:?}",
-                    exp, act
-                )));
+    /// Check a port map against the ports of the component it instantiates.
+    ///
+    /// Associates each element of the map with its formal, either by position
+    /// or by name, and checks each actual against its port. Ports which are
+    /// not associated are treated as if they were left open. Errors about
+    /// missing associations are reported at `span`.
+    ///
+    /// See IEEE 1076-2008 section 6.5.7.3.
+    pub fn typeck_port_map(
+        &self,
+        span: Span,
+        ports: &[IntfSignalRef],
+        map: &Spanned<hir::AssocList>,
+    ) -> Result<()> {
+        let mut failed = false;
+        let mut named = false;
+        let mut assoc: Vec<Option<Span>> = vec![None; ports.len()];
+        for (index, elem) in map.value.iter().enumerate() {
+            let pos = match elem.formal {
+                Some(formal) => {
+                    named = true;
+                    match ports
+                        .iter()
+                        .position(|&p| self.ctx.ast(p).3.name == formal.value)
+                    {
+                        Some(pos) => pos,
+                        None => {
+                            self.emit(
+                                DiagBuilder2::error(format!("`{}` is not a port", formal.value))
+                                    .span(formal.span),
+                            );
+                            failed = true;
+                            continue;
+                        }
+                    }
+                }
+                None if named => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "positional association `{}` follows a named association",
+                            elem.span.extract()
+                        ))
+                        .span(elem.span),
+                    );
+                    failed = true;
+                    continue;
+                }
+                None if index < ports.len() => index,
+                None => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "too many ports; expected at most {}",
+                            ports.len()
+                        ))
+                        .span(elem.span),
+                    );
+                    failed = true;
+                    continue;
+                }
+            };
+            if let Some(previous) = assoc[pos] {
+                self.emit(
+                    DiagBuilder2::error("port has already been associated")
+                        .span(elem.span)
+                        .add_note("previous association was here:")
+                        .span(previous),
+                );
+                failed = true;
+                continue;
+            }
+            assoc[pos] = Some(elem.span);
+            failed |= self.typeck_port_actual(ports[pos], &elem.actual).is_err();
+        }
+        for (&port, assoc) in ports.iter().zip(assoc.iter()) {
+            if assoc.is_none() {
+                let open = Spanned::new(hir::AssocActual::Open, span);
+                failed |= self.typeck_port_actual(port, &open).is_err();
             }
+        }
+        if failed {
+            Err(())
         } else {
-            self.failed.set(true);
+            Ok(())
         }
     }
 
-    /// Type check a slice of nodes.
-    pub fn typeck_slice<T, I>(&self, ids: T)
-    where
-        T: AsRef<[I]>,
-        I: Copy,
-        TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>: Typeck<I>,
-    {
-        for &id in ids.as_ref() {
-            self.typeck(id);
+    /// Check the actual associated with a port.
+    ///
+    /// The actual of a port of mode `in` may be any expression of the port's
+    /// type, and may only be left open if the port declares a default value.
+    /// The actual of any other port must be a signal. See IEEE 1076-2008
+    /// section 6.5.6.3.
+    fn typeck_port_actual(
+        &self,
+        port: IntfSignalRef,
+        actual: &Spanned<hir::AssocActual>,
+    ) -> Result<()> {
+        let hir = self.ctx.hir(port)?;
+        let mode = match hir.mode {
+            hir::IntfSignalMode::In => None,
+            hir::IntfSignalMode::Out => Some("out"),
+            hir::IntfSignalMode::Inout => Some("inout"),
+            hir::IntfSignalMode::Buffer => Some("buffer"),
+            hir::IntfSignalMode::Linkage => Some("linkage"),
+        };
+        let expr = match actual.value {
+            hir::AssocActual::Open if mode.is_some() || hir.init.is_some() => return Ok(()),
+            hir::AssocActual::Open => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "port `{}` of mode in must be associated",
+                        hir.name.value
+                    ))
+                    .span(actual.span)
+                    .add_note("A port of mode in may only be left open if it has a default value.")
+                    .add_note("port declared here:")
+                    .span(hir.name.span),
+                );
+                return Err(());
+            }
+            hir::AssocActual::Expr(expr) | hir::AssocActual::InertialExpr(expr) => expr,
+            hir::AssocActual::Subtype(..) => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid actual for port `{}`",
+                        actual.span.extract(),
+                        hir.name.value
+                    ))
+                    .span(actual.span),
+                );
+                return Err(());
+            }
+        };
+        if let Some(mode) = mode {
+            self.must_be_signal_actual(expr, hir.name, mode)?;
         }
+        let ty = self.ctx.ty(port)?;
+        self.typeck_assigned_value(expr, ty)
     }
 
-    /// Apply a range constraint to a type.
-    pub fn apply_range_constraint(&self, ty: &Ty, con: Spanned<&hir::Range>) -> Result<&'ctx Ty> {
-        // Determine the applied range.
-        let (dir, lb, rb) = match *con.value {
-            hir::Range::Immediate(dir, lb, rb) => (dir, lb, rb),
-        };
-        let lb = self.ctx.const_value(lb)?;
-        let rb = self.ctx.const_value(rb)?;
+    /// Ensure that the actual of a port of mode `mode` denotes a signal.
+    ///
+    /// The actual may be a signal, or an element, slice, or record element
+    /// thereof. See IEEE 1076-2008 section 6.5.6.3.
+    fn must_be_signal_actual(
+        &self,
+        actual: ExprRef,
+        port: Spanned<Name>,
+        mode: &str,
+    ) -> Result<()> {
+        let hir = self.ctx.lazy_hir(actual)?;
+        match hir.data {
+            hir::ExprData::SignalName(..) | hir::ExprData::Name(Def::Signal(..), _) => Ok(()),
+            hir::ExprData::Select(prefix, _) | hir::ExprData::Call(prefix, _) => {
+                self.must_be_signal_actual(prefix, port, mode)
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a signal and cannot be associated with port `{}` of mode {}",
+                        hir.span.extract(),
+                        port.value,
+                        mode
+                    ))
+                    .span(hir.span)
+                    .add_note("port declared here:")
+                    .span(port.span),
+                );
+                Err(())
+            }
+        }
+    }
 
-        // Determine the inner type to which the constraint shall be applied.
-        let ty = self.ctx.deref_named_type(ty)?;
-        match *ty {
-            Ty::Int(ref ty) => {
-                // Make sure we have an integer range.
-                let (lb, rb) = match (lb, rb) {
-                    (&Const::Int(ref lb), &Const::Int(ref rb)) => (lb, rb),
-                    _ => {
-                        self.emit(
-                            DiagBuilder2::error(format!(
-                                "non-integer range `{} {} {}` cannot constrain an integer type",
-                                lb, dir, rb
-                            ))
-                            .span(con.span),
-                        );
-                        return Err(());
-                    }
-                };
+    /// Determine the name of a generic, if it has a simple one.
+    fn generic_name(&self, generic: GenericRef) -> Option<Name> {
+        match generic {
+            GenericRef::Type(id) => Some(self.ctx.ast(id).1.name.value),
+            GenericRef::Pkg(id) => Some(self.ctx.ast(id).1.name.value),
+            GenericRef::Const(id) => Some(self.ctx.ast(id).3.name),
+            GenericRef::Subprog(id) => match self.ctx.ast(id).1.spec.name.kind {
+                ast::PrimaryNameKind::Ident(name) => Some(name),
+                _ => None,
+            },
+        }
+    }
 
-                // Make sure that this is actually a subtype.
-                if ty.dir != dir || ty.left_bound > lb.value || ty.right_bound < rb.value {
-                    self.emit(
-                        DiagBuilder2::error(format!(
-                            "`{} {} {}` is not a subrange of `{}`",
-                            lb, dir, rb, ty
-                        ))
-                        .span(con.span),
-                    );
-                    return Err(());
+    /// Ensure that the subtype of an object declaration is fully constrained.
+    ///
+    /// See IEEE 1076-2008 sections 6.4.2.2 to 6.4.2.4.
+    pub fn must_be_constrained(&self, ty: &'ctx Ty, span: Span) -> Result<()> {
+        if self.is_constrained(ty)? {
+            return Ok(());
+        }
+        self.emit(
+            DiagBuilder2::error("declaration requires a constrained subtype")
+                .span(span)
+                .add_note(format!("type {} is not fully constrained", ty))
+                .add_note(
+                    "Provide an index constraint for every array dimension, or an initial \
+                     value from which the constraint can be inferred.",
+                ),
+        );
+        Err(())
+    }
+
+    /// Check the default value of a port and determine the port's type.
+    ///
+    /// If the port is declared with an unconstrained array subtype, the index
+    /// constraint is taken from the default value. The element type of the
+    /// default must match the element type of the port. See IEEE 1076-2008
+    /// section 6.5.6.3.
+    pub fn port_default_ty(&self, ty: &'ctx Ty, init: ExprRef) -> Result<&'ctx Ty> {
+        let init_ty = self.lazy_typeval(init)?;
+        let span = self.ctx.span(init).unwrap();
+        if self.is_constrained(ty)? {
+            return if self.must_match(ty, init_ty, span) {
+                Ok(ty)
+            } else {
+                Err(())
+            };
+        }
+        let at = match *self.ctx.deref_named_type(ty)? {
+            Ty::Array(ref at) => at,
+            _ => return Ok(ty),
+        };
+        let init_at = match *self.ctx.deref_named_type(init_ty)? {
+            Ty::Array(ref init_at) if init_at.decl == at.decl => init_at,
+            _ => {
+                self.must_match(ty, init_ty, span);
+                return Err(());
+            }
+        };
+        if !self.must_match(&at.element, &init_at.element, span) {
+            return Err(());
+        }
+        Ok(self.ctx.intern_ty(
+            ArrayTy::new(init_at.indices.clone(), at.element.clone()).with_decl(at.decl),
+        ))
+    }
+
+    /// Check whether a type has no unbounded array indices.
+    pub fn is_constrained(&self, ty: &Ty) -> Result<bool> {
+        match *self.ctx.deref_named_type(ty)? {
+            Ty::Array(ref at) => {
+                for index in &at.indices {
+                    if let ArrayIndex::Unbounded(..) = *index {
+                        return Ok(false);
+                    }
+                }
+                self.is_constrained(&at.element)
+            }
+            Ty::Record(ref rt) => {
+                for &(_, ref field) in &rt.fields {
+                    if !self.is_constrained(field)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            _ => Ok(true),
+        }
+    }
+
+    /// Determine which of a set of candidate types can hold an integer value.
+    ///
+    /// Candidates that are not integer types are dropped.
+    pub fn int_types_holding(
+        &self,
+        value: &BigInt,
+        candidates: &[&'ctx Ty],
+    ) -> Result<Vec<(&'ctx Ty, BigInt)>> {
+        let mut fitting = Vec::new();
+        for &cand in candidates {
+            if let Ty::Int(ref it) = *self.ctx.deref_named_type(cand)? {
+                let (lo, hi) = match it.dir {
+                    Dir::To => (&it.left_bound, &it.right_bound),
+                    Dir::Downto => (&it.right_bound, &it.left_bound),
+                };
+                if lo <= value && value <= hi {
+                    fitting.push((cand, it.len()));
+                }
+            }
+        }
+        Ok(fitting)
+    }
+
+    /// Determine the type of an integer literal among a set of candidates.
+    ///
+    /// Picks the narrowest candidate integer type that can hold the value. If
+    /// there are no candidates the literal is a universal integer. Emits an
+    /// error listing the candidates if the value fits none of them, pointing
+    /// at `origin` as the reason the candidates are required if available.
+    pub fn typeval_int_literal(
+        &self,
+        value: &BigInt,
+        candidates: &[&'ctx Ty],
+        span: Span,
+        origin: Option<Span>,
+    ) -> Result<&'ctx Ty> {
+        if candidates.is_empty() {
+            return Ok(self.ctx.intern_ty(Ty::UniversalInt));
+        }
+        let fitting = self.int_types_holding(value, candidates)?;
+        if let Some(&(ty, _)) = fitting.iter().min_by(|a, b| a.1.cmp(&b.1)) {
+            return Ok(ty);
+        }
+        let mut diag = DiagBuilder2::error(format!(
+            "integer literal `{}` does not fit into {}",
+            span.snippet(),
+            if candidates.len() == 1 {
+                format!("type {}", candidates[0])
+            } else {
+                format!("any of the {} candidate types", candidates.len())
+            }
+        ))
+        .span(span.first_line());
+        for &cand in candidates {
+            if let Ty::Int(ref it) = *self.ctx.deref_named_type(cand)? {
+                diag = diag.add_note(format!(
+                    "{} holds values {} {} {}",
+                    cand, it.left_bound, it.dir, it.right_bound
+                ));
+            }
+        }
+        if let Some(origin) = origin {
+            diag = diag
+                .add_note(format!("type required by `{}` here:", origin.snippet()))
+                .span(origin.first_line());
+        }
+        self.emit(diag);
+        Err(())
+    }
+
+    /// Determine the type of a range `lb to rb` or `lb downto rb`.
+    ///
+    /// Both bounds must be of the same scalar type. If one of them is a
+    /// universal integer, the range assumes the type of the other bound. The
+    /// bounds are recorded in the type if both are integer literals.
+    ///
+    /// See IEEE 1076-2008 section 5.2.1.
+    pub fn typeval_range(
+        &self,
+        dir: Dir,
+        lb: ExprRef,
+        rb: ExprRef,
+        span: Span,
+    ) -> Result<&'ctx Ty> {
+        let lb_ty = self.lazy_typeval(lb)?;
+        let rb_ty = self.lazy_typeval(rb)?;
+        let (bound, other, other_id) = match *self.ctx.deref_named_type(lb_ty)? {
+            Ty::UniversalInt | Ty::UnboundedInt => (rb_ty, lb_ty, lb),
+            _ => (lb_ty, rb_ty, rb),
+        };
+        if !self.must_match(bound, other, self.ctx.span(other_id).unwrap()) {
+            return Err(());
+        }
+        if !bound.is_scalar(self.ctx)? {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "bounds of range `{}` must be scalar, but are of type {}",
+                    span.extract(),
+                    bound
+                ))
+                .span(span),
+            );
+            return Err(());
+        }
+        let static_bounds = if self.is_static_literal(lb)? && self.is_static_literal(rb)? {
+            match (self.ctx.const_value(lb)?, self.ctx.const_value(rb)?) {
+                (&Const::Int(ref l), &Const::Int(ref r)) => {
+                    Some((l.value.clone(), r.value.clone()))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+        if let Some((ref l, ref r)) = static_bounds {
+            self.warn_null_range(dir, l, r, span);
+        }
+        Ok(self
+            .ctx
+            .intern_ty(RangeTy::new(dir, bound.clone(), static_bounds)))
+    }
+
+    /// Emit a warning if a range with static bounds contains no values.
+    fn warn_null_range(&self, dir: Dir, lb: &BigInt, rb: &BigInt, span: Span) {
+        let (null, relation) = match dir {
+            Dir::To => (lb > rb, "greater"),
+            Dir::Downto => (lb < rb, "less"),
+        };
+        if null {
+            self.emit(
+                DiagBuilder2::warning(format!("`{}` is a null range", span.extract()))
+                    .span(span)
+                    .add_note(format!(
+                        "The range contains no values, since {} is {} than {}.",
+                        lb, relation, rb
+                    )),
+            );
+        }
+    }
+
+    /// Warn if a loop or generate statement iterates over a huge static range.
+    ///
+    /// Such ranges, e.g. `0 to 2**31`, are almost always a mistake and would
+    /// make elaboration blow up. The threshold is configured through the
+    /// session's `max_loop_iterations` option.
+    pub fn warn_huge_loop_range(&self, range: &Ty, span: Span) {
+        let (dir, l, r) = match *range {
+            Ty::Range(RangeTy {
+                dir,
+                static_bounds: Some((ref l, ref r)),
+                ..
+            }) => (dir, l, r),
+            _ => return,
+        };
+        let count = match dir {
+            Dir::To if l <= r => r - l + BigInt::one(),
+            Dir::Downto if l >= r => l - r + BigInt::one(),
+            _ => return,
+        };
+        let max = self.ctx.sess.opts.max_loop_iterations;
+        if count > BigInt::from(max) {
+            self.emit(
+                DiagBuilder2::warning(format!("`{}` iterates {} times", span.extract(), count))
+                    .span(span)
+                    .add_note(format!(
+                        "Loops with more than {} iterations are likely a mistake, and are \
+                         expensive to elaborate. The limit may be raised with \
+                         `--max-loop-iterations`.",
+                        max
+                    )),
+            );
+        }
+    }
+
+    /// Determine the type of a discrete range.
+    ///
+    /// Emits an error if the range is not discrete, e.g. because its bounds
+    /// are physical values.
+    ///
+    /// See IEEE 1076-2008 section 5.3.2.1.
+    pub fn typeval_discrete_range(
+        &self,
+        range: &hir::DiscreteRange,
+        span: Span,
+    ) -> Result<&'ctx Ty> {
+        let ty = match *range {
+            hir::DiscreteRange::Subtype(id) => {
+                let ty = self.lazy_typeval(id)?;
+                let (dir, static_bounds) = match *self.ctx.deref_named_type(ty)? {
+                    Ty::Int(ref it) => {
+                        let bounds = (it.left_bound.clone(), it.right_bound.clone());
+                        (it.dir, Some(bounds))
+                    }
+                    _ => (Dir::To, None),
+                };
+                self.ctx
+                    .intern_ty(RangeTy::new(dir, ty.clone(), static_bounds))
+            }
+            hir::DiscreteRange::Range(hir::Range::Immediate(dir, lb, rb)) => {
+                self.typeval_range(dir, lb, rb, span)?
+            }
+            hir::DiscreteRange::Range(hir::Range::Attr(attr)) => self.lazy_typeval(attr)?,
+        };
+        let bound = match *ty {
+            Ty::Range(ref rt) => &rt.bound,
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!("`{}` is not a range", span.extract()))
+                        .span(span)
+                        .add_note(format!("expression is of type {}", ty)),
+                );
+                return Err(());
+            }
+        };
+        if !bound.is_discrete(self.ctx)? {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is not a discrete range", span.extract()))
+                    .span(span)
+                    .add_note(format!("range is of type {}", bound)),
+            );
+            return Err(());
+        }
+        Ok(ty)
+    }
+
+    /// Determine the type of the values a discrete range iterates over.
+    ///
+    /// This is the type of the parameter of a for-generate statement or a for
+    /// loop. Ranges whose bounds are universal integers are implicitly
+    /// converted to `INTEGER`.
+    ///
+    /// See IEEE 1076-2008 sections 5.3.2.2, 10.10, and 11.8.
+    pub fn typeval_range_element(&self, range: Spanned<&hir::DiscreteRange>) -> Result<&'ctx Ty> {
+        let ty = self.typeval_discrete_range(range.value, range.span)?;
+        self.warn_huge_loop_range(ty, range.span);
+        let bound = match *ty {
+            Ty::Range(ref rt) => &rt.bound,
+            _ => unreachable!(),
+        };
+        match **bound {
+            Ty::UniversalInt | Ty::UnboundedInt => Ok(self.ctx.intern_ty(INTEGER_TYPE.named_ty())),
+            ref other => Ok(self.ctx.intern_ty(other.clone())),
+        }
+    }
+
+    /// Check that a resolution function can resolve a type.
+    ///
+    /// The function must take a single parameter which is a one-dimensional,
+    /// unconstrained array of `ty`, and return a value of `ty`.
+    ///
+    /// See IEEE 1076-2008 section 4.6.
+    pub fn check_resolution_func(&self, func: Spanned<Def>, ty: &'ctx Ty) -> Result<()> {
+        let sig = match func.value {
+            Def::Subprog(id) => self.lazy_typeval(id)?,
+            Def::Alias(id) => self.ctx.ty(id)?,
+            _ => unreachable!(),
+        };
+        let is_valid = match *sig {
+            Ty::Subprog(SubprogTy {
+                ref args,
+                ret: Some(ref ret),
+            }) if args.len() == 1 => {
+                let is_array_of_ty = match *self.ctx.deref_named_type(&args[0].ty)? {
+                    Ty::Array(ref at) if at.indices.len() == 1 => match at.indices[0] {
+                        ArrayIndex::Unbounded(..) => are_types_matching(self.ctx, &at.element, ty),
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                is_array_of_ty && are_types_matching(self.ctx, ret, ty)
+            }
+            _ => false,
+        };
+        if !is_valid {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` cannot resolve type {}",
+                    func.span.extract(),
+                    ty
+                ))
+                .span(func.span)
+                .add_note(format!("`{}` has profile `{}`", func.span.extract(), sig))
+                .add_note(format!(
+                    "A resolution function must take a single one-dimensional, unconstrained \
+                     array of {} and return {}. See IEEE 1076-2008 section 4.6.",
+                    ty, ty
+                )),
+            );
+            return Err(());
+        }
+        Ok(())
+    }
+
+    /// Check whether a subtype indication denotes a resolved subtype.
+    ///
+    /// This is the case if the indication itself names a resolution function,
+    /// or if its type mark refers to a subtype declaration which does.
+    pub fn is_resolved_subtype(&self, id: SubtypeIndRef) -> Result<bool> {
+        let hir = self.ctx.lazy_hir(id)?;
+        if hir.resolution.is_some() {
+            return Ok(true);
+        }
+        match hir.type_mark.value {
+            TypeMarkRef::Subtype(decl) => self.is_resolved_subtype(self.ctx.hir(decl)?.subty),
+            TypeMarkRef::Type(..) => Ok(false),
+        }
+    }
+
+    /// Ensure that a guarded signal has a resolved subtype.
+    ///
+    /// Signals of kind `register` or `bus` must be resolved. See IEEE
+    /// 1076-2008 section 6.4.2.3.
+    pub fn must_be_resolved_signal(
+        &self,
+        kind: hir::SignalKind,
+        ty: SubtypeIndRef,
+        span: Span,
+    ) -> Result<()> {
+        let kind_name = match kind {
+            hir::SignalKind::Normal => return Ok(()),
+            hir::SignalKind::Register => "register",
+            hir::SignalKind::Bus => "bus",
+        };
+        if self.is_resolved_subtype(ty)? {
+            return Ok(());
+        }
+        let mut diag = DiagBuilder2::error(format!(
+            "{} signal `{}` must have a resolved subtype",
+            kind_name,
+            span.extract()
+        ))
+        .span(span);
+        if let Some(ty_span) = self.ctx.span(ty) {
+            diag = diag.add_note(format!("`{}` is not resolved", ty_span.extract()));
+        }
+        self.emit(diag.add_note(
+            "Guarded signals of kind `register` or `bus` must be resolved. See IEEE 1076-2008 \
+             section 6.4.2.3.",
+        ));
+        Err(())
+    }
+
+    /// Ensure that one type can be cast into the other.
+    ///
+    /// The two types must be closely related. See IEEE 1076-2008 section
+    /// 9.3.6.
+    pub fn must_cast(&self, into: &'ctx Ty, from: &'ctx Ty, span: Span) -> bool {
+        match self.are_closely_related(into, from) {
+            Ok(true) => true,
+            Ok(false) => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` of type {} cannot be converted to type {}",
+                        span.extract(),
+                        from,
+                        into
+                    ))
+                    .span(span)
+                    .add_note(
+                        "Conversion is only possible between types with the same base type, \
+                         between integer types, and between array types with the same \
+                         dimensionality and closely related element types.",
+                    ),
+                );
+                false
+            }
+            Err(()) => false,
+        }
+    }
+
+    /// Check whether two types are closely related, i.e. whether a value of
+    /// one may be converted into the other.
+    fn are_closely_related(&self, a: &'ctx Ty, b: &'ctx Ty) -> Result<bool> {
+        if self.ctx.base_types_equal(a, b) {
+            return Ok(true);
+        }
+        let is_numeric = |ty: &Ty| match *ty {
+            Ty::Int(_) | Ty::UniversalInt | Ty::UnboundedInt => true,
+            _ => false,
+        };
+        let (a, b) = (self.ctx.deref_named_type(a)?, self.ctx.deref_named_type(b)?);
+        Ok(match (a, b) {
+            (a, b) if is_numeric(a) && is_numeric(b) => true,
+            (&Ty::Array(ref a), &Ty::Array(ref b)) => {
+                a.indices.len() == b.indices.len()
+                    && self.are_closely_related(&a.element, &b.element)?
+            }
+            _ => false,
+        })
+    }
+
+    /// Type check the time expression in a delay mechanism.
+    ///
+    /// The pulse rejection limit of a `reject ... inertial` delay mechanism
+    /// must be of type `TIME` and must not be negative. See IEEE 1076-2008
+    /// section 10.5.2.1.
+    pub fn typeck_delay_mechanism(&self, node: &'ctx hir::DelayMechanism) {
+        if let hir::DelayMechanism::RejectInertial(expr) = *node {
+            let _ = self.typeck_time_expr(expr, "pulse rejection limit");
+        }
+    }
+
+    /// Type check an expression which must be a non-negative time.
+    ///
+    /// As with `check_static_range`, only literals are checked for negative
+    /// values, since other values may only be known at runtime. The `what`
+    /// describes the expression in diagnostics.
+    pub fn typeck_time_expr(&self, expr: ExprRef, what: &str) -> Result<&'ctx Ty> {
+        let time = self.ctx.intern_ty(TIME_TYPE.named_ty());
+        let span = self.ctx.span(expr).unwrap();
+        self.ctx.set_type_context(expr, TypeCtx::Type(time));
+        let ty = self.lazy_typeval(expr)?;
+        if !self.must_match(time, ty, span) {
+            return Err(());
+        }
+        if self.is_static_literal(expr)? {
+            if let Const::Physical(ref c) = *self.ctx.const_value(expr)? {
+                if c.value < BigInt::zero() {
+                    self.emit(
+                        DiagBuilder2::error(format!("{} `{}` is negative", what, span.extract()))
+                            .span(span)
+                            .add_note("See IEEE 1076-2008 section 10.5.2.1."),
+                    );
+                    return Err(());
+                }
+            }
+        }
+        Ok(ty)
+    }
+
+    /// Type check a waveform.
+    ///
+    /// The `unaffected` waveform has no elements and trivially checks.
+    pub fn typeck_waveform(&self, node: &'ctx hir::Waveform, exp: &'ctx Ty) {
+        self.typed_waveform(node, exp);
+    }
+
+    /// Type check a waveform and determine the types of its elements.
+    ///
+    /// Each element is checked in order, and errors are emitted through this
+    /// context as with `typeck_waveform`. Elements which fail to check are
+    /// omitted from the result.
+    pub fn typed_waveform(
+        &self,
+        node: &'ctx hir::Waveform,
+        exp: &'ctx Ty,
+    ) -> Vec<TypedWaveElem<'ctx>> {
+        node.iter()
+            .filter_map(|elem| self.typeval_wave_elem(elem, exp).ok())
+            .collect()
+    }
+
+    /// Type check a waveform element.
+    pub fn typeck_wave_elem(&self, node: &'ctx hir::WaveElem, exp: &'ctx Ty) {
+        let _ = self.typeval_wave_elem(node, exp);
+    }
+
+    /// Type check a waveform element and determine the types of its value and
+    /// `after` expression.
+    ///
+    /// The value must match the type of the assigned signal, and the `after`
+    /// expression must be of type `TIME`. See IEEE 1076-2008 section 10.5.2.
+    pub fn typeval_wave_elem(
+        &self,
+        node: &'ctx hir::WaveElem,
+        exp: &'ctx Ty,
+    ) -> Result<TypedWaveElem<'ctx>> {
+        let value = match node.value {
+            Some(value) => match self.typeck_assigned_value(value, exp) {
+                Ok(()) => self.lazy_typeval(value).map(Some),
+                Err(()) => Err(()),
+            },
+            None => Ok(None),
+        };
+        let after = match node.after {
+            Some(after) => self.typeck_time_expr(after, "delay").map(Some),
+            None => Ok(None),
+        };
+        Ok(TypedWaveElem {
+            elem: node,
+            value_ty: value?,
+            after_ty: after?,
+        })
+    }
+
+    /// Ensure that an assignment does not target the parameter of an enclosing
+    /// `for` loop.
+    ///
+    /// Loop parameters are constants. If nested loops use the same parameter
+    /// name, the innermost one is reported. See IEEE 1076-2008 section 10.10.
+    pub fn must_not_assign_loop_param(&self, target: &Spanned<ast::AssignTarget>) -> Result<()> {
+        let name = match target.value {
+            ast::AssignTarget::Name(ref name) if name.parts.is_empty() => match name.primary.kind {
+                ast::PrimaryNameKind::Ident(name) => name,
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let loops = self.region().loops.clone();
+        for &id in loops.iter().rev() {
+            if let hir::LoopScheme::For(ref param, _) = self.ctx.lazy_hir(id)?.stmt.scheme {
+                if param.value == name {
+                    self.emit(
+                        DiagBuilder2::error(format!("cannot assign to loop parameter `{}`", name))
+                            .span(target.span)
+                            .add_note("The parameter of a `for` loop is a constant, declared here:")
+                            .span(param.span),
+                    );
+                    return Err(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensure that the target of a variable assignment denotes a variable.
+    ///
+    /// The target may be a variable, or an element, slice, or record element
+    /// thereof. See IEEE 1076-2008 section 10.6.2.1.
+    pub fn must_be_variable(&self, target: ExprRef) -> Result<()> {
+        let hir = self.ctx.lazy_hir(target)?;
+        let what = match hir.data {
+            hir::ExprData::VarName(..) | hir::ExprData::Name(Def::Var(..), _) => return Ok(()),
+            hir::ExprData::ParamName(IntfObjRef::Var(id)) => match self.ctx.ast(id).1.mode {
+                None | Some(ast::IntfMode::In) => "a parameter of mode `in`",
+                _ => return Ok(()),
+            },
+            hir::ExprData::Select(prefix, _) | hir::ExprData::Call(prefix, _) => {
+                return self.must_be_variable(prefix);
+            }
+            hir::ExprData::SignalName(..) | hir::ExprData::Name(Def::Signal(..), _) => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is a signal and cannot be assigned with `:=`",
+                        hir.span.extract()
+                    ))
+                    .span(hir.span)
+                    .add_note("Signals are assigned with `<=`. See IEEE 1076-2008 section 10.5."),
+                );
+                return Err(());
+            }
+            hir::ExprData::ConstName(..)
+            | hir::ExprData::GenParamName(..)
+            | hir::ExprData::LoopParamName(..)
+            | hir::ExprData::ParamName(IntfObjRef::Const(..))
+            | hir::ExprData::Name(Def::Const(..), _) => "a constant",
+            hir::ExprData::FileName(..)
+            | hir::ExprData::ParamName(IntfObjRef::File(..))
+            | hir::ExprData::Name(Def::File(..), _) => "a file",
+            _ => "not a variable",
+        };
+        self.emit(
+            DiagBuilder2::error(format!(
+                "`{}` is {} and cannot be assigned",
+                hir.span.extract(),
+                what
+            ))
+            .span(hir.span)
+            .add_note(
+                "The target of a variable assignment must be a variable. See IEEE 1076-2008 \
+                 section 10.6.2.1.",
+            ),
+        );
+        Err(())
+    }
+
+    /// Type check a value assigned to a signal or variable of type `exp`.
+    pub fn typeck_assigned_value(&self, value: ExprRef, exp: &'ctx Ty) -> Result<()> {
+        self.ctx.set_type_context(value, TypeCtx::Type(exp));
+        let ty = self.lazy_typeval(value)?;
+        if !self.must_match(exp, ty, self.ctx.span(value).unwrap()) {
+            return Err(());
+        }
+        self.check_static_range(value, exp)
+    }
+
+    /// Type check a condition, which must be of type `BOOLEAN`.
+    ///
+    /// A condition of another type is implicitly converted by the condition
+    /// operator `??`, if one is visible for that type, e.g. for `BIT`. See IEEE
+    /// 1076-2008 section 9.2.9.
+    pub fn typeck_condition(&self, cond: ExprRef) -> Result<()> {
+        let boolean = self.ctx.intern_ty(BOOLEAN_TYPE.named_ty());
+        self.ctx.set_type_context(cond, TypeCtx::Type(boolean));
+        let ty = self.lazy_typeval(cond)?;
+        let is_boolean = match *self.ctx.deref_named_type(ty)? {
+            Ty::Enum(ref et) => et.decl == BOOLEAN_TYPE.id,
+            _ => false,
+        };
+        if !is_boolean && self.has_condition_operator(cond, ty, boolean)? {
+            return Ok(());
+        }
+        self.typeck_assigned_value(cond, boolean)
+    }
+
+    /// Check whether a condition operator `??` converting `ty` to `BOOLEAN` is
+    /// visible at an expression.
+    fn has_condition_operator(
+        &self,
+        expr: ExprRef,
+        ty: &'ctx Ty,
+        boolean: &'ctx Ty,
+    ) -> Result<bool> {
+        let hir = self.ctx.lazy_hir(expr)?;
+        let name = Spanned::new(UnaryOp::Cond.into(), hir.span);
+        let defs = self.ctx.resolve_name(name, hir.parent, false, true)?;
+        let req = OverloadReq::Subprog(SignatureReq {
+            return_type: Some(TypeReq::One(boolean)),
+            positional: vec![TypeReq::One(ty)],
+            named: HashMap::new(),
+        });
+        Ok(!reduce_overloads(self.ctx, &defs, &req, hir.span)?.is_empty())
+    }
+
+    /// Check whether execution may reach the end of a sequence of statements.
+    ///
+    /// This is a conservative approximation. Only `return` statements, `if`
+    /// and `case` statements all of whose branches end in such statements,
+    /// and plain loops without an `exit` are considered to never complete.
+    fn may_complete(&self, stmts: &[SeqStmtRef]) -> Result<bool> {
+        for &stmt in stmts {
+            let completes = match stmt {
+                SeqStmtRef::Return(..) => false,
+                SeqStmtRef::If(id) => {
+                    let hir = self.ctx.lazy_hir(id)?;
+                    match hir.stmt.otherwise {
+                        Some(ref otherwise) => {
+                            let mut completes = self.may_complete(otherwise)?;
+                            for &(_, ref stmts) in &hir.stmt.branches {
+                                completes |= self.may_complete(stmts)?;
+                            }
+                            completes
+                        }
+                        None => true,
+                    }
+                }
+                SeqStmtRef::Case(id) => {
+                    let mut completes = false;
+                    for &(_, ref stmts) in &self.ctx.lazy_hir(id)?.stmt.cases {
+                        completes |= self.may_complete(stmts)?;
+                    }
+                    completes
+                }
+                SeqStmtRef::Loop(id) => {
+                    let hir = self.ctx.lazy_hir(id)?;
+                    match hir.stmt.scheme {
+                        hir::LoopScheme::Loop => self.contains_exit(&hir.stmt.stmts)?,
+                        _ => true,
+                    }
+                }
+                _ => true,
+            };
+            if !completes {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check whether a sequence of statements contains an `exit` statement.
+    fn contains_exit(&self, stmts: &[SeqStmtRef]) -> Result<bool> {
+        for &stmt in stmts {
+            let found = match stmt {
+                SeqStmtRef::Nexit(id) => self.ctx.lazy_hir(id)?.stmt.mode == hir::NexitMode::Exit,
+                SeqStmtRef::If(id) => {
+                    let hir = self.ctx.lazy_hir(id)?;
+                    let mut found = match hir.stmt.otherwise {
+                        Some(ref otherwise) => self.contains_exit(otherwise)?,
+                        None => false,
+                    };
+                    for &(_, ref stmts) in &hir.stmt.branches {
+                        found |= self.contains_exit(stmts)?;
+                    }
+                    found
+                }
+                SeqStmtRef::Case(id) => {
+                    let mut found = false;
+                    for &(_, ref stmts) in &self.ctx.lazy_hir(id)?.stmt.cases {
+                        found |= self.contains_exit(stmts)?;
+                    }
+                    found
+                }
+                SeqStmtRef::Loop(id) => self.contains_exit(&self.ctx.lazy_hir(id)?.stmt.stmts)?,
+                _ => false,
+            };
+            if found {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Check that a locally static value lies within the range of a subtype.
+    ///
+    /// Only integer subtypes are checked, whose bounds are always static.
+    /// Values are only considered if they are literals, possibly with a sign,
+    /// such that values which are only known at elaboration time never cause
+    /// a false positive.
+    pub fn check_static_range(&self, value: ExprRef, ty: &'ctx Ty) -> Result<()> {
+        let it = match *self.ctx.deref_named_type(ty)? {
+            Ty::Int(ref it) => it,
+            _ => return Ok(()),
+        };
+        if !self.is_static_literal(value)? {
+            return Ok(());
+        }
+        let v = match *self.ctx.const_value(value)? {
+            Const::Int(ref c) => &c.value,
+            _ => return Ok(()),
+        };
+        let span = self.ctx.span(value).unwrap();
+
+        // The value must be representable in the base type before it can be
+        // checked against the subtype's constraint.
+        if let Ty::Named(_, tm) = *ty {
+            let base = self.ctx.intern_named_ty(self.ctx.base_type_mark(tm)?);
+            if let Ty::Int(ref base) = *self.ctx.deref_named_type(base)? {
+                if !int_range_contains(base, v) {
+                    self.emit(
+                        DiagBuilder2::error("integer value out of range")
+                            .span(span)
+                            .add_note(format!(
+                                "`{}` does not fit into type {}, which covers {}",
+                                span.extract(),
+                                ty,
+                                base
+                            )),
+                    );
+                    return Err(());
+                }
+            }
+        }
+        if !int_range_contains(it, v) {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "value `{}` is outside the range {} of the target",
+                    span.extract(),
+                    it
+                ))
+                .span(span),
+            );
+            return Err(());
+        }
+        Ok(())
+    }
+
+    /// Check whether an expression is a literal, possibly with a sign.
+    fn is_static_literal(&self, expr: ExprRef) -> Result<bool> {
+        Ok(match self.ctx.lazy_hir(expr)?.data {
+            hir::ExprData::IntegerLiteral(..) | hir::ExprData::PhysLiteral(..) => true,
+            hir::ExprData::Unary(
+                Spanned {
+                    value: UnaryOp::Pos,
+                    ..
+                },
+                _,
+                arg,
+            )
+            | hir::ExprData::Unary(
+                Spanned {
+                    value: UnaryOp::Neg,
+                    ..
+                },
+                _,
+                arg,
+            ) => self.is_static_literal(arg)?,
+            _ => false,
+        })
+    }
+
+    /// Check that the constraints of a subtype declaration are globally static.
+    ///
+    /// This applies to subtypes declared in a package, which are elaborated
+    /// along with the package itself and thus cannot depend on signals,
+    /// variables, or files. See IEEE 1076-2008 sections 4.7 and 9.4.3.
+    pub fn check_subtype_decl_static(&self, id: SubtypeDeclRef) -> Result<()> {
+        let hir = self.ctx.hir(id)?;
+        self.check_subtype_ind_static(hir.subty)
+    }
+
+    /// Check that the constraint of a subtype indication is globally static.
+    fn check_subtype_ind_static(&self, id: SubtypeIndRef) -> Result<()> {
+        match self.ctx.lazy_hir(id)?.constraint {
+            Some(ref con) => match con.value {
+                hir::Constraint::Range(ref range) => self.check_range_static(range),
+                hir::Constraint::Array(ref ac) => self.check_array_constraint_static(ac),
+                hir::Constraint::Record(ref rc) => self.check_record_constraint_static(rc),
+            },
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the bounds of a range are globally static.
+    fn check_range_static(&self, range: &hir::Range) -> Result<()> {
+        match *range {
+            hir::Range::Immediate(_, lb, rb) => {
+                let lb = self.check_expr_static(lb);
+                let rb = self.check_expr_static(rb);
+                lb.and(rb)
+            }
+            hir::Range::Attr(attr) => self.check_expr_static(attr),
+        }
+    }
+
+    /// Check that an array constraint is globally static.
+    fn check_array_constraint_static(&self, ac: &hir::ArrayConstraint) -> Result<()> {
+        let mut result = Ok(());
+        for index in &ac.index {
+            let r = match index.value {
+                hir::DiscreteRange::Subtype(subty) => self.check_subtype_ind_static(subty),
+                hir::DiscreteRange::Range(ref range) => self.check_range_static(range),
+            };
+            result = result.and(r);
+        }
+        if let Some(ref elem) = ac.elem {
+            result = result.and(self.check_element_constraint_static(&elem.value));
+        }
+        result
+    }
+
+    /// Check that a record constraint is globally static.
+    fn check_record_constraint_static(&self, rc: &hir::RecordConstraint) -> Result<()> {
+        let mut result = Ok(());
+        for &(_, ref elem) in &rc.elems {
+            result = result.and(self.check_element_constraint_static(&elem.value));
+        }
+        result
+    }
+
+    /// Check that an element constraint is globally static.
+    fn check_element_constraint_static(&self, con: &hir::ElementConstraint) -> Result<()> {
+        match *con {
+            hir::ElementConstraint::Array(ref ac) => self.check_array_constraint_static(ac),
+            hir::ElementConstraint::Record(ref rc) => self.check_record_constraint_static(rc),
+        }
+    }
+
+    /// Check that an expression is globally static.
+    ///
+    /// This is a conservative approximation: an expression is rejected only if
+    /// it refers to a signal, variable, or file, or allocates an object.
+    fn check_expr_static(&self, expr: ExprRef) -> Result<()> {
+        let mut parts = Vec::new();
+        self.collect_nonstatic_parts(expr, &mut parts)?;
+        for &(span, what) in &parts {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is not globally static", span.extract()))
+                    .span(span)
+                    .add_note(format!(
+                        "A subtype declared in a package must have globally static \
+                         constraints, which cannot involve a {}. See IEEE 1076-2008 section \
+                         9.4.3.",
+                        what
+                    )),
+            );
+        }
+        if parts.is_empty() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Check that an expression controlling a generate statement is globally
+    /// static.
+    ///
+    /// The `what` describes the expression in diagnostics. See IEEE 1076-2008
+    /// section 11.8.
+    fn check_generate_static(&self, expr: ExprRef, what: &str) -> Result<()> {
+        let mut parts = Vec::new();
+        self.collect_nonstatic_parts(expr, &mut parts)?;
+        for &(span, part) in &parts {
+            self.emit(
+                DiagBuilder2::error(format!("`{}` is not globally static", span.extract()))
+                    .span(span)
+                    .add_note(format!(
+                        "The {} of a generate statement must be globally static, which cannot \
+                         involve a {}. See IEEE 1076-2008 section 11.8.",
+                        what, part
+                    )),
+            );
+        }
+        if parts.is_empty() {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Find the parts of an expression which are not globally static.
+    ///
+    /// Pushes the span of every signal, variable, file, and allocator in the
+    /// expression, together with a description of what it is.
+    fn collect_nonstatic_parts(
+        &self,
+        expr: ExprRef,
+        parts: &mut Vec<(Span, &'static str)>,
+    ) -> Result<()> {
+        let hir = self.ctx.lazy_hir(expr)?;
+        let what = match hir.data {
+            hir::ExprData::SignalName(..) | hir::ExprData::Name(Def::Signal(..), _) => "signal",
+            hir::ExprData::VarName(..)
+            | hir::ExprData::ParamName(IntfObjRef::Var(..))
+            | hir::ExprData::Name(Def::Var(..), _) => "variable",
+            hir::ExprData::FileName(..)
+            | hir::ExprData::ParamName(IntfObjRef::File(..))
+            | hir::ExprData::Name(Def::File(..), _) => "file",
+            hir::ExprData::Allocator(..) => "allocator",
+            hir::ExprData::Unary(_, _, arg)
+            | hir::ExprData::Qualified(_, arg)
+            | hir::ExprData::Cast(_, arg)
+            | hir::ExprData::TypeAttr(_, _, Some(arg)) => {
+                return self.collect_nonstatic_parts(arg, parts)
+            }
+            hir::ExprData::Binary(_, _, lhs, rhs) | hir::ExprData::Range(_, lhs, rhs) => {
+                let lhs = self.collect_nonstatic_parts(lhs, parts);
+                let rhs = self.collect_nonstatic_parts(rhs, parts);
+                return lhs.and(rhs);
+            }
+            hir::ExprData::Call(callee, ref args) => {
+                let mut result = self.collect_nonstatic_parts(callee, parts);
+                for arg in &args.value {
+                    if let hir::AssocActual::Expr(arg) = arg.actual.value {
+                        result = result.and(self.collect_nonstatic_parts(arg, parts));
+                    }
+                }
+                return result;
+            }
+            _ => return Ok(()),
+        };
+        parts.push((hir.span, what));
+        Ok(())
+    }
+
+    /// Apply a range constraint whose bounds are not static.
+    ///
+    /// Subtypes declared in a process or subprogram are elaborated each time
+    /// the region is entered, such that their constraints may depend on
+    /// variables and signals. The bounds are only known at runtime, so rather
+    /// than folding them, the constrained type is approximated by `ty` and the
+    /// user is told that the range is checked at runtime. Returns `None` if the
+    /// range is static after all, in which case `apply_range_constraint`
+    /// should be used. See IEEE 1076-2008 section 14.4.2.
+    pub fn apply_nonstatic_range_constraint(
+        &self,
+        ty: &'ctx Ty,
+        con: Spanned<&hir::Range>,
+    ) -> Result<Option<&'ctx Ty>> {
+        let (dir, lb_id, rb_id) = match *con.value {
+            hir::Range::Immediate(dir, lb, rb) => (dir, lb, rb),
+            hir::Range::Attr(..) => return Ok(None),
+        };
+        let mut parts = Vec::new();
+        self.collect_nonstatic_parts(lb_id, &mut parts)?;
+        self.collect_nonstatic_parts(rb_id, &mut parts)?;
+        if parts.is_empty() {
+            return Ok(None);
+        }
+
+        // Only integer types are supported for now. A range in the opposite
+        // direction is approximated by the reversed range of the type.
+        let int = match *self.ctx.deref_named_type(ty)? {
+            Ty::Int(ref it) => it,
+            _ => return Ok(None),
+        };
+        let ty = if int.dir != dir {
+            self.ctx.intern_ty(IntTy::new(
+                dir,
+                int.right_bound.clone(),
+                int.left_bound.clone(),
+            ))
+        } else {
+            ty
+        };
+        let lb = self.lazy_typeval(lb_id);
+        let rb = self.lazy_typeval(rb_id);
+        lb?;
+        rb?;
+        self.emit(
+            DiagBuilder2::note(format!(
+                "range `{}` is not static and will be checked at runtime",
+                con.span.extract()
+            ))
+            .span(con.span)
+            .add_note(format!(
+                "The range depends on the {} `{}`.",
+                parts[0].1,
+                parts[0].0.extract()
+            )),
+        );
+        Ok(Some(ty))
+    }
+
+    /// Check a guarded concurrent signal assignment.
+    ///
+    /// The assignment is controlled by the boolean signal `GUARD`, which is
+    /// usually declared implicitly by an enclosing block statement with a guard
+    /// expression. The target must be a guarded signal, i.e. one declared as
+    /// `bus` or `register`. See IEEE 1076-2008 section 11.6.
+    pub fn typeck_guarded_assign(&self, hir: &'ctx hir::SigAssignStmt) -> Result<()> {
+        let mut failed = false;
+
+        // Find the `GUARD` signal visible at the assignment.
+        let guard_name = Spanned::new(get_name_table().intern("GUARD", false).into(), hir.span);
+        let guard = self
+            .ctx
+            .resolve_name(guard_name, hir.parent, false, true)?
+            .into_iter()
+            .filter_map(|def| match def.value {
+                Def::Signal(sig) => Some(sig),
+                _ => None,
+            })
+            .next();
+        match guard {
+            Some(sig) => {
+                let ty = self.ctx.ty(sig)?;
+                let boolean = self.ctx.intern_ty(BOOLEAN_TYPE.named_ty());
+                failed |= !self.must_match(boolean, ty, self.ctx.span(sig).unwrap_or(hir.span));
+            }
+            None => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "guarded assignment `{}` is not within a guarded block",
+                        hir.span.extract()
+                    ))
+                    .span(hir.span)
+                    .add_note(
+                        "A guarded assignment requires a signal `GUARD`, usually declared \
+                         implicitly by a block statement with a guard expression. See IEEE \
+                         1076-2008 section 11.6.",
+                    ),
+                );
+                failed = true;
+            }
+        }
+
+        // The target must be a guarded signal.
+        let mut is_guarded = true;
+        for sig in hir.target.signals() {
+            is_guarded &= match sig {
+                SignalRef::Decl(id) => self.ctx.lazy_hir(id)?.decl.kind != hir::SignalKind::Normal,
+                SignalRef::Intf(id) => self.ctx.hir(id)?.bus,
+                SignalRef::Guard(_) => false,
+            };
+        }
+        if !is_guarded {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "target `{}` of guarded assignment is not a guarded signal",
+                    hir.target_span.extract()
+                ))
+                .span(hir.target_span)
+                .add_note(
+                    "Guarded signals are declared as `bus` or `register`. See IEEE 1076-2008 \
+                     section 6.4.2.3.",
+                ),
+            );
+            failed = true;
+        }
+
+        if failed {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Determine the type of the target of a signal assignment.
+    ///
+    /// An indexed target has the element type of the signal, and a sliced
+    /// target an array subtype constrained to the slice. See IEEE 1076-2008
+    /// sections 8.4 and 8.5. The type of an aggregate target is the type of
+    /// the assigned value. See IEEE 1076-2008 section 10.5.2.1.
+    pub fn typeval_sig_assign_target(&self, hir: &'ctx hir::SigAssignStmt) -> Result<&'ctx Ty> {
+        match hir.target {
+            hir::SigAssignTarget::Aggregate(ref elems) => {
+                let value = match self.first_assigned_value(hir)? {
+                    Some(value) => value,
+                    None => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "type of aggregate target `{}` cannot be determined",
+                                hir.target_span.extract()
+                            ))
+                            .span(hir.target_span)
+                            .add_note(
+                                "The type of an aggregate target is determined by the \
+                                 assigned value. See IEEE 1076-2008 section 10.5.2.1.",
+                            ),
+                        );
+                        return Err(());
+                    }
+                };
+                let ty = self.lazy_typeval(value)?;
+                self.typeck_aggregate_target(elems, ty, hir.target_span)?;
+                Ok(ty)
+            }
+            ref target => self.typeval_target(target, hir.target_span),
+        }
+    }
+
+    /// Find the first value assigned by a signal assignment, if any.
+    ///
+    /// Waveforms which are `unaffected` or consist of `null` elements only do
+    /// not assign a value and are skipped.
+    fn first_assigned_value(&self, hir: &'ctx hir::SigAssignStmt) -> Result<Option<ExprRef>> {
+        let waves: Vec<&'ctx hir::Waveform> = match hir.kind {
+            hir::SigAssignKind::SimpleWave(_, ref wave) => vec![wave],
+            hir::SigAssignKind::SelWave(_, ref sel) => sel.when.iter().map(|w| &w.0).collect(),
+            _ => {
+                self.emit(
+                    DiagBuilder2::bug(
+                        "aggregate targets of this kind of assignment not implemented",
+                    )
+                    .span(hir.target_span),
+                );
+                return Err(());
+            }
+        };
+        Ok(waves
+            .into_iter()
+            .flat_map(|wave| wave.iter())
+            .filter_map(|elem| elem.value)
+            .next())
+    }
+
+    /// Check the elements of an aggregate signal assignment target.
+    ///
+    /// The aggregate must be of a one-dimensional array or a record type. Each
+    /// element target must have the element type of the array, or the type of
+    /// the corresponding record field.
+    fn typeck_aggregate_target(
+        &self,
+        elems: &'ctx [Spanned<hir::SigAssignTarget>],
+        ty: &'ctx Ty,
+        span: Span,
+    ) -> Result<()> {
+        let elem_tys: Vec<&'ctx Ty> = match *self.ctx.deref_named_type(ty)? {
+            Ty::Array(ref at) if at.indices.len() == 1 => {
+                if let Ty::Int(ref it) = *self.ctx.deref_named_type(at.indices[0].ty())? {
+                    if it.len() != BigInt::from(elems.len()) {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "aggregate target `{}` has {} elements, but the assigned \
+                                 value has {}",
+                                span.extract(),
+                                elems.len(),
+                                it.len()
+                            ))
+                            .span(span),
+                        );
+                        return Err(());
+                    }
+                }
+                let element = self.ctx.intern_ty(at.element.as_ref().clone());
+                elems.iter().map(|_| element).collect()
+            }
+            Ty::Record(ref rt) => {
+                if rt.fields.len() != elems.len() {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "aggregate target `{}` has {} elements, but the record has {} \
+                             fields",
+                            span.extract(),
+                            elems.len(),
+                            rt.fields.len()
+                        ))
+                        .span(span),
+                    );
+                    return Err(());
+                }
+                rt.fields
+                    .iter()
+                    .map(|&(_, ref ty)| self.ctx.intern_ty(ty.as_ref().clone()))
+                    .collect()
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "aggregate target `{}` is assigned a value of type {}",
+                        span.extract(),
+                        ty.colored()
+                    ))
+                    .span(span)
+                    .add_note(
+                        "The value assigned to an aggregate target must be of a \
+                         one-dimensional array or a record type. See IEEE 1076-2008 section \
+                         10.5.2.1.",
+                    ),
+                );
+                return Err(());
+            }
+        };
+        let mut failed = false;
+        for (elem, elem_ty) in elems.iter().zip(elem_tys) {
+            failed |= match elem.value {
+                hir::SigAssignTarget::Aggregate(ref elems) => self
+                    .typeck_aggregate_target(elems, elem_ty, elem.span)
+                    .is_err(),
+                ref target => match self.typeval_target(target, elem.span) {
+                    Ok(ty) => !self.must_match(elem_ty, ty, elem.span),
+                    Err(()) => true,
+                },
+            };
+        }
+        if failed {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Determine the type of a signal assignment target other than an
+    /// aggregate.
+    fn typeval_target(&self, target: &'ctx hir::SigAssignTarget, span: Span) -> Result<&'ctx Ty> {
+        let ty = match *target {
+            hir::SigAssignTarget::Name(sig) => return self.ctx.ty(sig),
+            hir::SigAssignTarget::Index(sig, _) | hir::SigAssignTarget::Slice(sig, _) => {
+                self.ctx.ty(sig)?
+            }
+            hir::SigAssignTarget::Aggregate(..) => unreachable!(),
+        };
+        let at = match *self.ctx.deref_named_type(ty)? {
+            Ty::Array(ref at) => at,
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` indexes a signal which is not an array",
+                        span.extract()
+                    ))
+                    .span(span)
+                    .add_note(format!("The signal has type {}.", ty.colored())),
+                );
+                return Err(());
+            }
+        };
+        match *target {
+            hir::SigAssignTarget::Index(_, ref indices) => {
+                if indices.len() != at.indices.len() {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` has {} indices, but the array has {} dimensions",
+                            span.extract(),
+                            indices.len(),
+                            at.indices.len()
+                        ))
+                        .span(span),
+                    );
+                    return Err(());
+                }
+                let mut failed = false;
+                for (&index, dim) in indices.iter().zip(at.indices.iter()) {
+                    let index_ty = self.ctx.intern_ty(dim.ty().clone());
+                    failed |= self.typeck_assigned_value(index, index_ty).is_err();
+                }
+                if failed {
+                    return Err(());
+                }
+                let element = ty.element_type(self.ctx)?.unwrap();
+                Ok(self.ctx.intern_ty(element.clone()))
+            }
+            hir::SigAssignTarget::Slice(_, ref range) => {
+                if at.indices.len() != 1 {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` slices an array with {} dimensions",
+                            span.extract(),
+                            at.indices.len()
+                        ))
+                        .span(span)
+                        .add_note("Only one-dimensional arrays can be sliced."),
+                    );
+                    return Err(());
+                }
+                let index = self.type_from_discrete_range(range.as_ref())?;
+                Ok(self.ctx.intern_ty(
+                    ArrayTy::new(
+                        vec![ArrayIndex::Constrained(Box::new(index.clone()))],
+                        at.element.clone(),
+                    )
+                    .with_decl(at.decl),
+                ))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Type check a signal assignment statement.
+    pub fn typeck_sig_assign_stmt(&self, hir: &'ctx hir::SigAssignStmt) -> Result<()> {
+        for sig in hir.target.signals() {
+            if let SignalRef::Guard(_) = sig {
+                self.emit(
+                    DiagBuilder2::error("implicit signal `GUARD` cannot be assigned")
+                        .span(hir.target_span)
+                        .add_note(
+                            "`GUARD` is declared by the guard condition of a block statement. \
+                             See IEEE 1076-2008 section 11.2.",
+                        ),
+                );
+                return Err(());
+            }
+            self.ctx.record_signal_write(sig);
+        }
+        let lhs_ty = self.typeval_sig_assign_target(hir)?;
+        match hir.kind {
+            hir::SigAssignKind::SimpleWave(ref dm, ref wave) => {
+                self.typeck_delay_mechanism(dm);
+                self.typeck_waveform(wave, lhs_ty);
+            }
+            hir::SigAssignKind::SimpleForce(_, _expr) => {
+                // self.typeck_node(expr, lhs_ty)?;
+            }
+            hir::SigAssignKind::SimpleRelease(_) => (),
+            hir::SigAssignKind::CondWave(ref dm, ref cond) => {
+                self.typeck_delay_mechanism(dm);
+                for &(ref wave, cond) in &cond.when {
+                    self.typeck_waveform(wave, lhs_ty);
+                    let _ = self.typeck_condition(cond);
+                }
+                if let Some(ref wave) = cond.other {
+                    self.typeck_waveform(wave, lhs_ty);
+                }
+            }
+            hir::SigAssignKind::CondForce(_, ref _cond) => {
+                // self.typeck_node(cond, lhs_ty)?;
+            }
+            hir::SigAssignKind::SelWave(ref dm, ref sel) => {
+                self.typeck_delay_mechanism(dm);
+                for &(ref wave, _) in &sel.when {
+                    self.typeck_waveform(wave, lhs_ty);
+                }
+                self.typeck_choices(
+                    sel.disc,
+                    sel.matching,
+                    sel.when.iter().map(|&(_, ref c)| c),
+                    hir.span,
+                )?;
+            }
+            hir::SigAssignKind::SelForce(_, ref sel) => {
+                // self.typeck_node(sel, lhs_ty)?;
+                self.typeck_choices(
+                    sel.disc,
+                    sel.matching,
+                    sel.when.iter().map(|&(_, ref c)| c),
+                    hir.span,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Warn about the signals of an architecture which are never read or never
+    /// assigned.
+    ///
+    /// Relies on the reads and writes recorded while type checking the
+    /// architecture's statements, and must therefore run afterwards. Ports are
+    /// not considered. To avoid false positives, nothing is reported if the
+    /// architecture contains statements whose signal uses are not recorded,
+    /// such as component instantiations or conditional assignments.
+    pub fn lint_signals(&self, hir: &hir::Arch) -> Result<()> {
+        for &stmt in &hir.stmts {
+            let recorded = match stmt {
+                ConcStmtRef::Process(..) => true,
+                ConcStmtRef::ConcSigAssign(id) => match self.ctx.hir(id)?.kind {
+                    hir::SigAssignKind::SimpleWave(..) | hir::SigAssignKind::SelWave(..) => true,
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !recorded {
+                return Ok(());
+            }
+        }
+        for &decl in &hir.decls {
+            let id = match decl {
+                DeclInBlockRef::Signal(id) => id,
+                _ => continue,
+            };
+            let decl = self.ctx.lazy_hir(id)?;
+            let read = self.ctx.is_signal_read(id);
+            let written = self.ctx.is_signal_written(id);
+            let msg = match (read, written) {
+                (true, true) => continue,
+                (false, false) => "is never used",
+                (false, true) => "is assigned but never read",
+                (true, false) => "is read but never assigned",
+            };
+            let mut diag = DiagBuilder2::warning(format!("signal `{}` {}", decl.name.value, msg))
+                .span(decl.name.span);
+            if read && decl.decl.init.is_some() {
+                diag = diag.add_note("The signal keeps its initial value. Use a constant instead.");
+            }
+            self.emit(diag);
+        }
+        Ok(())
+    }
+
+    /// Type check the choices of a case statement or selected assignment.
+    ///
+    /// Each choice must be a locally static value or range of the selector's
+    /// type. No value may be covered by more than one choice, and all values
+    /// must be covered unless an `others` choice is present. See IEEE
+    /// 1076-2008 sections 10.5.4 and 10.9.
+    pub fn typeck_choices<I>(
+        &self,
+        disc: ExprRef,
+        matching: bool,
+        choices: I,
+        span: Span,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = &'ctx Spanned<hir::Choices>>,
+    {
+        if matching {
+            return self.typeck_matching_choices(disc, choices, span);
+        }
+        let disc_ty = self.lazy_typeval(disc)?;
+        let domain = self.choice_domain(disc, self.choice_domain_type(disc, disc_ty)?)?;
+
+        // Map each choice to the interval of values it covers.
+        let mut had_fails = false;
+        let mut others: Option<Span> = None;
+        let mut covered = Vec::new();
+        for choice in choices.into_iter().flat_map(|c| c.value.iter()) {
+            if let Some(others) = others {
+                self.emit(
+                    DiagBuilder2::error("`others` must be the last choice")
+                        .span(others)
+                        .add_note(format!("`{}` follows here:", choice.span.extract()))
+                        .span(choice.span),
+                );
+                had_fails = true;
+            }
+            match choice.value {
+                hir::Choice::Others => others = Some(choice.span),
+                _ => match self.choice_interval(&domain, disc_ty, choice) {
+                    Ok(Some(iv)) => covered.push((iv, choice.span)),
+                    Ok(None) => (),
+                    Err(()) => had_fails = true,
+                },
+            }
+        }
+        if had_fails {
+            return Err(());
+        }
+
+        // Report values that are covered by more than one choice.
+        covered.sort_by(|a, b| (a.0).0.cmp(&(b.0).0));
+        let mut prev: Option<(&BigInt, Span)> = None;
+        for &((ref lo, ref hi), sp) in &covered {
+            if let Some((prev_hi, prev_sp)) = prev {
+                if lo <= prev_hi {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "choice `{}` overlaps with `{}`",
+                            sp.extract(),
+                            prev_sp.extract()
+                        ))
+                        .span(sp)
+                        .add_note("Each value must be covered by exactly one choice.")
+                        .add_note("Previous choice here:")
+                        .span(prev_sp),
+                    );
+                    had_fails = true;
+                }
+            }
+            if prev.map(|(prev_hi, _)| hi > prev_hi).unwrap_or(true) {
+                prev = Some((hi, sp));
+            }
+        }
+
+        // Unless there is an `others` choice, look for the first value that is
+        // not covered.
+        if others.is_none() {
+            let (dom_lo, dom_hi) = domain.bounds();
+            let mut next = dom_lo;
+            for &((ref lo, ref hi), _) in &covered {
+                if *lo > next {
+                    break;
+                }
+                if *hi >= next {
+                    next = hi + BigInt::one();
+                }
+            }
+            if next <= dom_hi {
+                let disc_span = self.ctx.span(disc).unwrap();
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "choices do not cover all values of `{}`",
+                        disc_span.extract()
+                    ))
+                    .span(span)
+                    .add_note(format!(
+                        "{} is not covered; add the missing choices or an `others` choice",
+                        self.choice_value_desc(&domain, &next)?
+                    )),
+                );
+                had_fails = true;
+            }
+        }
+
+        if had_fails {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Type check the choices of a matching case statement or selected
+    /// assignment (indicated by `?`).
+    ///
+    /// The selector must be of type `std_ulogic` or a one-dimensional array
+    /// thereof. Choices are compared using the matching equality operator,
+    /// such that a `'-'` matches any value, `'0'` also matches `'L'`, and `'1'`
+    /// also matches `'H'`. Again no value may be matched by more than one
+    /// choice, and all values must be matched unless an `others` choice is
+    /// present. See IEEE 1076-2008 sections 10.5.4 and 10.9.
+    fn typeck_matching_choices<I>(&self, disc: ExprRef, choices: I, span: Span) -> Result<()>
+    where
+        I: IntoIterator<Item = &'ctx Spanned<hir::Choices>>,
+    {
+        let disc_ty = self.lazy_typeval(disc)?;
+        let (decl, len) = self.matching_choice_domain(disc, disc_ty)?;
+
+        // Map each choice to the set of values it matches, one mask of
+        // literals per element.
+        let mut had_fails = false;
+        let mut others: Option<Span> = None;
+        let mut covered: Vec<(Vec<u16>, Span)> = Vec::new();
+        for choice in choices.into_iter().flat_map(|c| c.value.iter()) {
+            if let Some(others) = others {
+                self.emit(
+                    DiagBuilder2::error("`others` must be the last choice")
+                        .span(others)
+                        .add_note(format!("`{}` follows here:", choice.span.extract()))
+                        .span(choice.span),
+                );
+                had_fails = true;
+            }
+            match choice.value {
+                hir::Choice::Others => others = Some(choice.span),
+                hir::Choice::Expr(expr) => {
+                    match self.matching_choice_value(decl, len, disc_ty, expr) {
+                        Ok(masks) => covered.push((masks, choice.span)),
+                        Err(()) => had_fails = true,
+                    }
+                }
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is not a valid choice for a matching selector of type {}",
+                            choice.span.extract(),
+                            disc_ty
+                        ))
+                        .span(choice.span),
+                    );
+                    had_fails = true;
+                }
+            }
+        }
+        if had_fails {
+            return Err(());
+        }
+
+        // Report values that are matched by more than one choice.
+        for (i, &(ref masks, sp)) in covered.iter().enumerate() {
+            let prev = covered[..i]
+                .iter()
+                .find(|&&(ref prev, _)| prev.iter().zip(masks).all(|(a, b)| a & b != 0));
+            if let Some(&(_, prev_sp)) = prev {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "choice `{}` overlaps with `{}`",
+                        sp.extract(),
+                        prev_sp.extract()
+                    ))
+                    .span(sp)
+                    .add_note("Each value must be matched by exactly one choice.")
+                    .add_note("Previous choice here:")
+                    .span(prev_sp),
+                );
+                had_fails = true;
+            }
+        }
+
+        // Unless there is an `others` choice, look for the first value that is
+        // not matched.
+        if others.is_none() {
+            let all: Vec<&[u16]> = covered.iter().map(|&(ref m, _)| m.as_slice()).collect();
+            if let Some(value) = uncovered_matching_value(&all, len.unwrap_or(1)) {
+                let lits = self.enum_literals(decl)?;
+                let desc = match len {
+                    None => format!("`{}`", lits[value[0]]),
+                    Some(_) => format!(
+                        "`\"{}\"`",
+                        value
+                            .iter()
+                            .map(|&v| lits[v].trim_matches('\''))
+                            .collect::<String>()
+                    ),
+                };
+                let disc_span = self.ctx.span(disc).unwrap();
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "choices do not cover all values of `{}`",
+                        disc_span.extract()
+                    ))
+                    .span(span)
+                    .add_note(format!(
+                        "{} is not matched; add the missing choices or an `others` choice",
+                        desc
+                    )),
+                );
+                had_fails = true;
+            }
+        }
+
+        if had_fails {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Determine the `std_ulogic` type of a matching selector, and the number
+    /// of elements if it is an array.
+    fn matching_choice_domain(
+        &self,
+        disc: ExprRef,
+        disc_ty: &'ctx Ty,
+    ) -> Result<(TypeDeclRef, Option<usize>)> {
+        match *self.ctx.deref_named_type(disc_ty)? {
+            Ty::Enum(ref et) if self.is_std_ulogic(et.decl)? => return Ok((et.decl, None)),
+            Ty::Array(ref at) if at.indices.len() == 1 => {
+                let len = match at.indices[0] {
+                    ArrayIndex::Constrained(ref ty) => match *self.ctx.deref_named_type(ty)? {
+                        Ty::Int(ref it) => it.len().to_usize(),
+                        _ => None,
+                    },
+                    ArrayIndex::Unbounded(_) => None,
+                };
+                if let (Some(len), &Ty::Enum(ref et)) =
+                    (len, self.ctx.deref_named_type(&at.element)?)
+                {
+                    if self.is_std_ulogic(et.decl)? {
+                        return Ok((et.decl, Some(len)));
+                    }
+                }
+            }
+            _ => (),
+        }
+        let disc_span = self.ctx.span(disc).unwrap();
+        self.emit(
+            DiagBuilder2::error(format!(
+                "`{}` of type {} cannot be used in a matching selection",
+                disc_span.extract(),
+                disc_ty
+            ))
+            .span(disc_span)
+            .add_note(
+                "The selector of a matching case statement or selected assignment must be of \
+                 type std_ulogic or a one-dimensional array of std_ulogic with a locally static \
+                 length. See IEEE 1076-2008 section 10.9.",
+            ),
+        );
+        Err(())
+    }
+
+    /// Determine the values matched by a choice of a matching selection.
+    ///
+    /// Returns a mask of matched `std_ulogic` literals for each element.
+    fn matching_choice_value(
+        &self,
+        decl: TypeDeclRef,
+        len: Option<usize>,
+        disc_ty: &'ctx Ty,
+        expr: ExprRef,
+    ) -> Result<Vec<u16>> {
+        let span = self.ctx.span(expr).unwrap();
+        self.ctx.set_type_context(expr, TypeCtx::Type(disc_ty));
+        let hir = self.ctx.lazy_hir(expr)?;
+        let indices = match (len, &hir.data) {
+            (None, &hir::ExprData::EnumName(ref defs)) => defs
+                .iter()
+                .find(|def| def.value.0 == decl)
+                .map(|def| vec![def.value.1]),
+            (Some(len), &hir::ExprData::StringLiteral(ref maps)) => {
+                match maps.iter().find(|&&(d, _)| d == decl) {
+                    Some(&(_, ref indices)) if indices.len() == len => Some(indices.clone()),
+                    Some(&(_, ref indices)) => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "choice `{}` has {} elements, but the selector has {}",
+                                span.extract(),
+                                indices.len(),
+                                len
+                            ))
+                            .span(span),
+                        );
+                        return Err(());
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        match indices {
+            Some(indices) => Ok(indices.into_iter().map(std_ulogic_match_mask).collect()),
+            None => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "choice `{}` is not a locally static value of type {}",
+                        span.extract(),
+                        disc_ty
+                    ))
+                    .span(span),
+                );
+                Err(())
+            }
+        }
+    }
+
+    /// Check whether an enumeration type is `std_ulogic`, i.e. has the nine
+    /// literals defined in IEEE 1164.
+    fn is_std_ulogic(&self, decl: TypeDeclRef) -> Result<bool> {
+        Ok(self
+            .enum_literals(decl)?
+            .iter()
+            .map(|lit| lit.as_str())
+            .eq(STD_ULOGIC_LITERALS.iter().cloned()))
+    }
+
+    /// Determine the type whose values the choices for a selector must cover.
+    ///
+    /// This is the subtype of the selector if it is the name of an object, a
+    /// qualified expression, or a type conversion. Otherwise, the choices must
+    /// cover all values of the selector's base type. Only integer subtypes are
+    /// affected, since the choices for an enumeration selector always cover
+    /// all literals of its type. See IEEE 1076-2008 section 10.9.
+    fn choice_domain_type(&self, disc: ExprRef, disc_ty: &'ctx Ty) -> Result<&'ctx Ty> {
+        match self.ctx.lazy_hir(disc)?.data {
+            hir::ExprData::ConstName(..)
+            | hir::ExprData::SignalName(..)
+            | hir::ExprData::VarName(..)
+            | hir::ExprData::GenParamName(..)
+            | hir::ExprData::LoopParamName(..)
+            | hir::ExprData::ParamName(..)
+            | hir::ExprData::Name(..)
+            | hir::ExprData::Qualified(..)
+            | hir::ExprData::Cast(..) => return Ok(disc_ty),
+            _ => (),
+        }
+        if let Ty::Named(_, tm) = *disc_ty {
+            let base = self.ctx.intern_named_ty(self.ctx.base_type_mark(tm)?);
+            if let Ty::Int(..) = *self.ctx.deref_named_type(base)? {
+                return Ok(base);
+            }
+        }
+        Ok(disc_ty)
+    }
+
+    /// Determine the set of values a selector can take.
+    fn choice_domain(&self, disc: ExprRef, disc_ty: &'ctx Ty) -> Result<ChoiceDomain> {
+        match *self.ctx.deref_named_type(disc_ty)? {
+            Ty::Int(ref it) => {
+                return Ok(match it.dir {
+                    Dir::To => ChoiceDomain::Int(it.left_bound.clone(), it.right_bound.clone()),
+                    Dir::Downto => ChoiceDomain::Int(it.right_bound.clone(), it.left_bound.clone()),
+                });
+            }
+            Ty::Enum(ref et) => {
+                return Ok(ChoiceDomain::Enum(
+                    et.decl,
+                    self.enum_literals(et.decl)?.len(),
+                ));
+            }
+            Ty::Array(ref at) if at.indices.len() == 1 => {
+                let len = match at.indices[0] {
+                    ArrayIndex::Constrained(ref ty) => match *self.ctx.deref_named_type(ty)? {
+                        Ty::Int(ref it) => it.len().to_usize(),
+                        _ => None,
+                    },
+                    ArrayIndex::Unbounded(_) => None,
+                };
+                if let (Some(len), &Ty::Enum(ref et)) =
+                    (len, self.ctx.deref_named_type(&at.element)?)
+                {
+                    return Ok(ChoiceDomain::Array(
+                        et.decl,
+                        self.enum_literals(et.decl)?.len(),
+                        len,
+                    ));
+                }
+            }
+            _ => (),
+        }
+        let disc_span = self.ctx.span(disc).unwrap();
+        self.emit(
+            DiagBuilder2::error(format!(
+                "`{}` of type {} cannot be used to select among choices",
+                disc_span.extract(),
+                disc_ty
+            ))
+            .span(disc_span)
+            .add_note(
+                "The selector must be of a discrete type or a one-dimensional array of \
+                 characters with a locally static length. See IEEE 1076-2008 section 10.9.",
+            ),
+        );
+        Err(())
+    }
+
+    /// Determine the interval of values covered by a choice.
+    ///
+    /// Returns `None` if the choice covers no values, e.g. a null range.
+    fn choice_interval(
+        &self,
+        domain: &ChoiceDomain,
+        disc_ty: &'ctx Ty,
+        choice: &'ctx Spanned<hir::Choice>,
+    ) -> Result<Option<(BigInt, BigInt)>> {
+        let (lo, hi) = match choice.value {
+            hir::Choice::Expr(expr) => {
+                let value = self.choice_value(domain, disc_ty, expr)?;
+                (value.clone(), value)
+            }
+            hir::Choice::DiscreteRange(hir::DiscreteRange::Range(hir::Range::Immediate(
+                dir,
+                lb,
+                rb,
+            ))) if domain.is_discrete() => {
+                let lb = self.choice_value(domain, disc_ty, lb);
+                let rb = self.choice_value(domain, disc_ty, rb);
+                match dir {
+                    Dir::To => (lb?, rb?),
+                    Dir::Downto => (rb?, lb?),
+                }
+            }
+            hir::Choice::DiscreteRange(hir::DiscreteRange::Subtype(subty))
+                if domain.is_discrete() =>
+            {
+                match *self.ctx.deref_named_type(self.ctx.ty(subty)?)? {
+                    Ty::Int(ref it) => match it.dir {
+                        Dir::To => (it.left_bound.clone(), it.right_bound.clone()),
+                        Dir::Downto => (it.right_bound.clone(), it.left_bound.clone()),
+                    },
+                    _ => {
+                        self.emit(
+                            DiagBuilder2::bug(format!(
+                                "subtype choice `{}` not implemented",
+                                choice.span.extract()
+                            ))
+                            .span(choice.span),
+                        );
+                        return Err(());
+                    }
+                }
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid choice for a selector of type {}",
+                        choice.span.extract(),
+                        disc_ty
+                    ))
+                    .span(choice.span),
+                );
+                return Err(());
+            }
+        };
+        if lo > hi {
+            return Ok(None);
+        }
+        let (dom_lo, dom_hi) = domain.bounds();
+        if lo < dom_lo || hi > dom_hi {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "choice `{}` is outside the range of the selector type {}",
+                    choice.span.extract(),
+                    disc_ty
+                ))
+                .span(choice.span),
+            );
+            return Err(());
+        }
+        Ok(Some((lo, hi)))
+    }
+
+    /// Determine the value of a choice, mapped into the selector's domain.
+    fn choice_value(
+        &self,
+        domain: &ChoiceDomain,
+        disc_ty: &'ctx Ty,
+        expr: ExprRef,
+    ) -> Result<BigInt> {
+        let span = self.ctx.span(expr).unwrap();
+        self.ctx.set_type_context(expr, TypeCtx::Type(disc_ty));
+        let hir = self.ctx.lazy_hir(expr)?;
+        let value = match (domain, &hir.data) {
+            (&ChoiceDomain::Int(..), _) => {
+                if !self.must_match(disc_ty, self.lazy_typeval(expr)?, span) {
+                    return Err(());
+                }
+                match *self.ctx.const_value(expr)? {
+                    Const::Int(ref c) => Some(c.value.clone()),
+                    _ => None,
+                }
+            }
+            (&ChoiceDomain::Enum(decl, _), &hir::ExprData::EnumName(ref defs)) => defs
+                .iter()
+                .find(|def| def.value.0 == decl)
+                .map(|def| BigInt::from(def.value.1)),
+            (&ChoiceDomain::Array(decl, num, len), &hir::ExprData::StringLiteral(ref maps)) => {
+                match maps.iter().find(|&&(d, _)| d == decl) {
+                    Some(&(_, ref indices)) if indices.len() == len => {
+                        Some(indices.iter().fold(BigInt::zero(), |acc, &index| {
+                            acc * BigInt::from(num) + BigInt::from(index)
+                        }))
+                    }
+                    Some(&(_, ref indices)) => {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "choice `{}` has {} elements, but the selector has {}",
+                                span.extract(),
+                                indices.len(),
+                                len
+                            ))
+                            .span(span),
+                        );
+                        return Err(());
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+        match value {
+            Some(v) => Ok(v),
+            None => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "choice `{}` is not a locally static value of type {}",
+                        span.extract(),
+                        disc_ty
+                    ))
+                    .span(span),
+                );
+                Err(())
+            }
+        }
+    }
+
+    /// Describe a value of a selector's domain for use in diagnostics.
+    fn choice_value_desc(&self, domain: &ChoiceDomain, value: &BigInt) -> Result<String> {
+        Ok(match *domain {
+            ChoiceDomain::Int(..) => format!("`{}`", value),
+            ChoiceDomain::Enum(decl, _) => {
+                let lits = self.enum_literals(decl)?;
+                format!("`{}`", lits[value.to_usize().unwrap()])
+            }
+            ChoiceDomain::Array(decl, num, len) => {
+                let lits = self.enum_literals(decl)?;
+                let num = BigInt::from(num);
+                let mut rest = value.clone();
+                let mut digits = Vec::with_capacity(len);
+                for _ in 0..len {
+                    digits.push((&rest % &num).to_usize().unwrap());
+                    rest = rest / &num;
+                }
+                let elems: Vec<_> = digits.into_iter().rev().map(|d| lits[d].clone()).collect();
+                format!("({})", elems.join(", "))
+            }
+        })
+    }
+
+    /// Determine the literals of an enumeration type.
+    fn enum_literals(&self, decl: TypeDeclRef) -> Result<Vec<String>> {
+        if is_builtin_type(decl) {
+            return Ok(builtin_enum_literals(decl)
+                .into_iter()
+                .map(|name| match name {
+                    ResolvableName::Bit(c) => format!("'{}'", c),
+                    other => other.to_string(),
+                })
+                .collect());
+        }
+        match self.ctx.lazy_hir(decl)?.data {
+            Some(Spanned {
+                value: hir::TypeData::Enum(ref lits),
+                ..
+            }) => Ok(lits
+                .iter()
+                .map(|lit| match *lit {
+                    hir::EnumLit::Ident(n) => n.value.to_string(),
+                    hir::EnumLit::Char(c) => format!("'{}'", c.value),
+                })
+                .collect()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Type check a subprogram specification.
+    pub fn typeck_subprog_spec(&self, node: &'ctx hir::SubprogSpec) {
+        self.typeck_slice(&node.generics);
+        // self.typeck_slice(&node.generic_map);
+        self.typeck_slice(&node.params);
+        if let Some(ref ty) = node.return_type {
+            self.typeck(ty.value);
+        }
+    }
+
+    /// Type check any node that can have its type calculated.
+    ///
+    /// A mismatch is reported at the span of the node, if it has one.
+    pub fn typeck_node<I>(&self, id: I, exp: &'ctx Ty)
+    where
+        I: 'ctx + Copy + Debug + Into<NodeId>,
+        ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
+    {
+        self.typeck_node_at(id, exp, self.ctx.span(id))
+    }
+
+    /// Type check any node that can have its type calculated, reporting a
+    /// mismatch at `span`.
+    ///
+    /// Callers which know where the node appears, e.g. as an operand, should
+    /// prefer this over `typeck_node`.
+    pub fn typeck_node_spanned<I>(&self, id: I, exp: &'ctx Ty, span: Span)
+    where
+        I: 'ctx + Copy + Debug + Into<NodeId>,
+        ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
+    {
+        self.typeck_node_at(id, exp, Some(span))
+    }
+
+    fn typeck_node_at<I>(&self, id: I, exp: &'ctx Ty, span: Option<Span>)
+    where
+        I: 'ctx + Copy + Debug + Into<NodeId>,
+        ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
+    {
+        if let Ok(act) = self.ctx.ty(id) {
+            // Types compare direction-sensitively, so `0 to 7` does not match
+            // `7 downto 0`.
+            if act != exp {
+                self.emit(self.ctx.type_mismatch_diag_at(span, exp, act));
+            }
+        } else {
+            self.failed.set(true);
+        }
+    }
+
+    /// Type check a slice of nodes.
+    pub fn typeck_slice<T, I>(&self, ids: T)
+    where
+        T: AsRef<[I]>,
+        I: Copy,
+        TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx>: Typeck<I>,
+    {
+        for &id in ids.as_ref() {
+            if self.is_cancelled() {
+                return;
+            }
+            self.typeck(id);
+        }
+    }
+
+    /// Apply a range constraint to a type.
+    pub fn apply_range_constraint(&self, ty: &Ty, con: Spanned<&hir::Range>) -> Result<&'ctx Ty> {
+        // Determine the applied range.
+        let (dir, lb_id, rb_id) = match *con.value {
+            hir::Range::Immediate(dir, lb, rb) => (dir, lb, rb),
+            hir::Range::Attr(..) => {
+                self.emit(
+                    DiagBuilder2::bug(format!(
+                        "range attribute constraint `{}` not implemented",
+                        con.span.extract()
+                    ))
+                    .span(con.span),
+                );
+                return Err(());
+            }
+        };
+
+        // Determine the inner type to which the constraint shall be applied.
+        let ty = self.ctx.deref_named_type(ty)?;
+        match *ty {
+            Ty::Int(ref ty) => {
+                let lb = self.ctx.const_value(lb_id)?;
+                let rb = self.ctx.const_value(rb_id)?;
+
+                // Make sure we have an integer range.
+                let (lb, rb) = match (lb, rb) {
+                    (&Const::Int(ref lb), &Const::Int(ref rb)) => (lb, rb),
+                    _ => {
+                        let mut diag = DiagBuilder2::error(format!(
+                            "non-integer range `{} {} {}` cannot constrain an integer type",
+                            lb, dir, rb
+                        ));
+                        for &(id, value) in &[(lb_id, lb), (rb_id, rb)] {
+                            if let Const::Int(_) = *value {
+                                continue;
+                            }
+                            diag = diag
+                                .span(self.bound_span(id, con.span))
+                                .add_note(format!("`{}` is not an integer", value));
+                        }
+                        self.emit(diag);
+                        return Err(());
+                    }
+                };
+
+                // Make sure that each bound lies within the type.
+                let (lo, hi) = match ty.dir {
+                    Dir::To => (&ty.left_bound, &ty.right_bound),
+                    Dir::Downto => (&ty.right_bound, &ty.left_bound),
+                };
+                let mut failed = false;
+                for &(id, value, side) in &[(lb_id, lb, "left"), (rb_id, rb, "right")] {
+                    if value.value < *lo || value.value > *hi {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "{} bound `{}` is out of range of `{}`",
+                                side, value, ty
+                            ))
+                            .span(self.bound_span(id, con.span)),
+                        );
+                        failed = true;
+                    }
+                }
+                if failed {
+                    return Err(());
+                }
+                self.warn_null_range(dir, &lb.value, &rb.value, con.span);
+
+                // Create the new type.
+                Ok(self
+                    .ctx
+                    .intern_ty(IntTy::new(dir, lb.value.clone(), rb.value.clone()).maybe_null()))
+            }
+
+            Ty::Enum(ref ty) => {
+                // Determine the positions of the bound literals.
+                let lb = self.enum_bound(lb_id, ty);
+                let rb = self.enum_bound(rb_id, ty);
+                let (lb, rb) = (lb?, rb?);
+
+                // Make sure that the bounds lie within a constrained type.
+                if let Some((_, left, right)) = ty.range {
+                    let (lo, hi) = (std::cmp::min(left, right), std::cmp::max(left, right));
+                    let mut failed = false;
+                    for &(id, value, side) in &[(lb_id, lb, "left"), (rb_id, rb, "right")] {
+                        if value < lo || value > hi {
+                            self.emit(
+                                DiagBuilder2::error(format!(
+                                    "{} bound `{}` is out of range of the constrained type",
+                                    side,
+                                    self.bound_span(id, con.span).extract()
+                                ))
+                                .span(self.bound_span(id, con.span)),
+                            );
+                            failed = true;
+                        }
+                    }
+                    if failed {
+                        return Err(());
+                    }
                 }
+                self.warn_null_range(dir, &lb.into(), &rb.into(), con.span);
 
                 // Create the new type.
                 Ok(self
                     .ctx
-                    .intern_ty(IntTy::new(ty.dir, lb.value.clone(), rb.value.clone()).maybe_null()))
+                    .intern_ty(EnumTy::new(ty.decl).with_range(dir, lb, rb)))
+            }
+
+            Ty::Physical(ref ty) => {
+                // Evaluate the bounds as multiples of the primary unit.
+                let lb = self.physical_bound(lb_id, ty);
+                let rb = self.physical_bound(rb_id, ty);
+                let (lb, rb) = (lb?, rb?);
+
+                // Make sure that this is actually a subtype.
+                let (base_lo, base_hi) = match ty.base.dir {
+                    Dir::To => (&ty.base.left_bound, &ty.base.right_bound),
+                    Dir::Downto => (&ty.base.right_bound, &ty.base.left_bound),
+                };
+                let (lo, hi) = match dir {
+                    Dir::To => (&lb, &rb),
+                    Dir::Downto => (&rb, &lb),
+                };
+                if lo <= hi {
+                    let primary = ty.units[ty.primary].name;
+                    let mut failed = false;
+                    for &(id, value, side) in &[(lb_id, &lb, "left"), (rb_id, &rb, "right")] {
+                        if value < base_lo || value > base_hi {
+                            self.emit(
+                                DiagBuilder2::error(format!(
+                                    "{} bound `{}` is out of range of `{}`",
+                                    side,
+                                    self.bound_span(id, con.span).extract(),
+                                    ty
+                                ))
+                                .span(self.bound_span(id, con.span))
+                                .add_note(format!(
+                                    "Bounds are compared in multiples of the primary unit `{}`: \
+                                     `{}` versus `{}`.",
+                                    primary, value, ty.base
+                                )),
+                            );
+                            failed = true;
+                        }
+                    }
+                    if failed {
+                        return Err(());
+                    }
+                }
+
+                // Create the new type.
+                Ok(self.ctx.intern_ty(PhysicalTy::new(
+                    ty.decl,
+                    IntTy::new(dir, lb, rb),
+                    ty.units.clone(),
+                    ty.primary,
+                )))
             }
 
             // All other types we simply cannot constrain by range.
@@ -319,6 +3120,102 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         }
     }
 
+    /// Determine the span of a range constraint bound.
+    ///
+    /// Falls back to `span` if the bound's HIR is unavailable.
+    fn bound_span(&self, id: ExprRef, span: Span) -> Span {
+        self.ctx
+            .lazy_hir::<_, hir::Expr>(id)
+            .map(|hir| hir.span)
+            .unwrap_or(span)
+    }
+
+    /// Evaluate the bound of an enumeration range constraint.
+    ///
+    /// The bound must be a literal of the given enumeration type. Returns the
+    /// position of the literal.
+    fn enum_bound(&self, id: ExprRef, ty: &EnumTy) -> Result<usize> {
+        let hir = self.ctx.lazy_hir(id)?;
+        let found = match hir.data {
+            hir::ExprData::EnumName(ref defs) => defs
+                .iter()
+                .map(|def| def.value)
+                .find(|&EnumRef(decl, _)| decl == ty.decl),
+            hir::ExprData::Name(Def::Enum(EnumRef(decl, index)), _) => {
+                Some(EnumRef(decl, index)).filter(|_| decl == ty.decl)
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not an enumeration literal",
+                        hir.span.extract()
+                    ))
+                    .span(hir.span)
+                    .add_note("The bounds of an enumeration subtype must be literals of the type."),
+                );
+                return Err(());
+            }
+        };
+        match found {
+            Some(EnumRef(_, index)) => Ok(index),
+            None => {
+                let mut diag = DiagBuilder2::error(format!(
+                    "`{}` is not a literal of the constrained type",
+                    hir.span.extract()
+                ))
+                .span(hir.span);
+                if let Some(span) = self.ctx.span(ty.decl) {
+                    diag = diag.add_note("The type is declared here:").span(span);
+                }
+                self.emit(diag);
+                Err(())
+            }
+        }
+    }
+
+    /// Evaluate the bound of a physical range constraint.
+    ///
+    /// The bound must be a physical literal of the given type, optionally
+    /// preceded by a sign. Returns the value of the bound expressed in
+    /// multiples of the primary unit.
+    fn physical_bound(&self, id: ExprRef, ty: &PhysicalTy) -> Result<BigInt> {
+        let hir = self.ctx.lazy_hir(id)?;
+        match hir.data {
+            hir::ExprData::PhysLiteral(ref value, unit) if unit.value.0 == ty.decl => {
+                Ok(value * &ty.units[unit.value.1].abs)
+            }
+            hir::ExprData::PhysLiteral(_, unit) => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a unit of physical type `{}`",
+                        unit.span.extract(),
+                        ty
+                    ))
+                    .span(unit.span),
+                );
+                Err(())
+            }
+            hir::ExprData::Unary(op, _, arg) if op.value == UnaryOp::Pos => {
+                self.physical_bound(arg, ty)
+            }
+            hir::ExprData::Unary(op, _, arg) if op.value == UnaryOp::Neg => {
+                Ok(-self.physical_bound(arg, ty)?)
+            }
+            _ => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "`{}` is not a valid bound for physical type `{}`",
+                        hir.span.extract(),
+                        ty
+                    ))
+                    .span(hir.span)
+                    .add_note("Range constraints on physical types must be physical literals."),
+                );
+                Err(())
+            }
+        }
+    }
+
     /// Apply an array constraint to a type.
     pub fn apply_array_constraint(
         &self,
@@ -370,7 +3267,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 };
                 Ok(self
                     .ctx
-                    .intern_ty(ArrayTy::new(indices, Box::new(element.clone()))))
+                    .intern_ty(ArrayTy::new(indices, Box::new(element.clone())).with_decl(ty.decl)))
             }
             _ => {
                 self.emit(
@@ -403,6 +3300,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                     .map(|&(name, ref ty)| (name, ty.as_ref()))
                     .collect();
                 let mut had_fails = false;
+                let mut seen = HashMap::new();
                 for &(name, ref con) in &con.value.elems {
                     // Find the field that we're supposed to constrain.
                     let idx = match ty.lookup.get(&name.value) {
@@ -421,14 +3319,35 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                         }
                     };
 
+                    // Each element may only be constrained once. See IEEE
+                    // 1076-2008 section 5.3.3.
+                    if let Some(prev) = seen.insert(idx, name.span) {
+                        self.emit(
+                            DiagBuilder2::error(format!(
+                                "element `{}` is constrained more than once",
+                                name.value
+                            ))
+                            .span(name.span)
+                            .add_note("Previous constraint was here:")
+                            .span(prev),
+                        );
+                        had_fails = true;
+                        continue;
+                    }
+
                     // Constrain the field.
-                    fields[idx].1 = match con.value {
+                    let constrained = match con.value {
                         hir::ElementConstraint::Array(ref ac) => {
-                            self.apply_array_constraint(&fields[idx].1, Spanned::new(ac, con.span))?
+                            self.apply_array_constraint(&fields[idx].1, Spanned::new(ac, con.span))
+                        }
+                        hir::ElementConstraint::Record(ref rc) => {
+                            self.apply_record_constraint(&fields[idx].1, Spanned::new(rc, con.span))
                         }
-                        hir::ElementConstraint::Record(ref rc) => self
-                            .apply_record_constraint(&fields[idx].1, Spanned::new(rc, con.span))?,
                     };
+                    match constrained {
+                        Ok(ty) => fields[idx].1 = ty,
+                        Err(()) => had_fails = true,
+                    }
                 }
                 if had_fails {
                     return Err(());
@@ -442,7 +3361,7 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             _ => {
                 self.emit(
                     DiagBuilder2::error(format!(
-                        "array constraint `{}` does not apply to {}",
+                        "record constraint `{}` does not apply to {}",
                         con.span.extract(),
                         ty.kind_desc()
                     ))
@@ -459,6 +3378,16 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         index: &'ctx ArrayIndex,
         con: Spanned<&hir::DiscreteRange>,
     ) -> Result<ArrayIndex> {
+        // The bounds of an enumeration index are literals, which cannot be
+        // evaluated as an integer range. Constrain the index type directly.
+        let ty = index.ty();
+        if let (&Ty::Enum(..), &hir::DiscreteRange::Range(ref range)) =
+            (self.ctx.deref_named_type(ty)?, con.value)
+        {
+            let index_ty = self.apply_range_constraint(ty, Spanned::new(range, con.span))?;
+            return Ok(ArrayIndex::Constrained(Box::new(index_ty.clone())));
+        }
+
         // Convert the discrete range applied as constraint into a type.
         let con_ty = Spanned::new(
             self.ctx
@@ -482,30 +3411,17 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
         let span = subty.span;
         match (deref, self.ctx.deref_named_type(subty.value)?) {
             (&Ty::Int(ref ty), &Ty::Int(ref subty)) => {
+                // The subtype may have a different direction than the type,
+                // as in `bit_vector(3 downto 0)`, so compare the bounds
+                // independent of the direction.
                 use std::cmp::{max, min};
-                if ty.dir != subty.dir {
-                    self.emit(
-                        DiagBuilder2::error(format!(
-                            "directions disagree; `{}` and `{}`",
-                            subty, ty
-                        ))
-                        .span(span),
-                    );
-                    return Err(());
-                }
-                let (ty_lo, ty_hi, subty_lo, subty_hi) = match ty.dir {
-                    Dir::To => (
-                        &ty.left_bound,
-                        &ty.right_bound,
-                        &subty.left_bound,
-                        &subty.right_bound,
-                    ),
-                    Dir::Downto => (
-                        &ty.right_bound,
-                        &ty.left_bound,
-                        &subty.right_bound,
-                        &subty.left_bound,
-                    ),
+                let (ty_lo, ty_hi) = match ty.dir {
+                    Dir::To => (&ty.left_bound, &ty.right_bound),
+                    Dir::Downto => (&ty.right_bound, &ty.left_bound),
+                };
+                let (subty_lo, subty_hi) = match subty.dir {
+                    Dir::To => (&subty.left_bound, &subty.right_bound),
+                    Dir::Downto => (&subty.right_bound, &subty.left_bound),
                 };
                 if ty_lo > subty_lo || ty_hi < subty_hi {
                     self.emit(
@@ -519,17 +3435,22 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                 }
                 let lo = max(ty_lo, subty_lo);
                 let hi = min(ty_hi, subty_hi);
-                let (lb, rb) = match ty.dir {
+                let (lb, rb) = match subty.dir {
                     Dir::To => (lo, hi),
                     Dir::Downto => (hi, lo),
                 };
-                let new_ty: Ty = IntTy::new(ty.dir, lb.clone(), rb.clone()).into();
+                let new_ty: Ty = IntTy::new(subty.dir, lb.clone(), rb.clone()).into();
                 if &new_ty == deref {
                     Ok(orig_ty)
                 } else {
                     Ok(self.ctx.intern_ty(new_ty))
                 }
             }
+            // An enumeration index may be constrained by a subtype of the same
+            // enumeration type, e.g. `BITMAP(BIT)`.
+            (&Ty::Enum(ref ty), &Ty::Enum(ref subty)) if ty.decl == subty.decl => {
+                Ok(self.ctx.intern_ty(subty.clone()))
+            }
             _ => {
                 self.emit(
                     DiagBuilder2::error(format!(
@@ -577,6 +3498,111 @@ impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
                     }
                 }
             }
+            hir::Range::Attr(attr) => match *self.lazy_typeval(attr)? {
+                Ty::Range(RangeTy {
+                    dir,
+                    static_bounds: Some((ref lb, ref rb)),
+                    ..
+                }) => Ok(self.ctx.intern_ty(IntTy::new(dir, lb.clone(), rb.clone()))),
+                Ty::Range(ref rt) => Ok(self.ctx.intern_ty((*rt.bound).clone())),
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "`{}` is not a valid range",
+                            range.span.extract()
+                        ))
+                        .span(range.span),
+                    );
+                    Err(())
+                }
+            },
+        }
+    }
+}
+
+/// The literals of `std_ulogic`, in order.
+const STD_ULOGIC_LITERALS: [&str; 9] = [
+    "'U'", "'X'", "'0'", "'1'", "'Z'", "'W'", "'L'", "'H'", "'-'",
+];
+
+/// The number of values a matching selector element can take. The don't-care
+/// `'-'` is excluded since a selector containing it is an error.
+const STD_ULOGIC_VALUES: usize = 8;
+
+/// Determine the selector values a `std_ulogic` choice literal matches, as a
+/// mask with one bit per literal.
+fn std_ulogic_match_mask(index: usize) -> u16 {
+    match index {
+        // '0' and 'L' match each other.
+        2 | 6 => 1 << 2 | 1 << 6,
+        // '1' and 'H' match each other.
+        3 | 7 => 1 << 3 | 1 << 7,
+        // '-' matches everything.
+        8 => (1 << STD_ULOGIC_VALUES) - 1,
+        // The remaining metavalues never match.
+        _ => 0,
+    }
+}
+
+/// Find a selector value that is not matched by any of the given choices.
+///
+/// Each choice is given as a mask of matched literals for each element. The
+/// values are searched element by element, only descending into the choices
+/// that still match.
+fn uncovered_matching_value(choices: &[&[u16]], len: usize) -> Option<Vec<usize>> {
+    if choices.is_empty() {
+        return Some(vec![0; len]);
+    }
+    if len == 0 {
+        return None;
+    }
+    let mut seen: Vec<Vec<usize>> = Vec::new();
+    for value in 0..STD_ULOGIC_VALUES {
+        let which: Vec<usize> = (0..choices.len())
+            .filter(|&i| choices[i][0] & (1 << value) != 0)
+            .collect();
+        if seen.contains(&which) {
+            continue;
+        }
+        let rest: Vec<&[u16]> = which.iter().map(|&i| &choices[i][1..]).collect();
+        if let Some(mut uncovered) = uncovered_matching_value(&rest, len - 1) {
+            uncovered.insert(0, value);
+            return Some(uncovered);
+        }
+        seen.push(which);
+    }
+    None
+}
+
+/// The set of values a selector can take, mapped to an interval of integers.
+enum ChoiceDomain {
+    /// An integer type with the given lower and upper bound.
+    Int(BigInt, BigInt),
+    /// An enumeration type with the given number of literals.
+    Enum(TypeDeclRef, usize),
+    /// A one-dimensional array of a fixed length, of an enumeration type with
+    /// the given number of literals. Each array value is mapped to an integer
+    /// by treating its elements as the digits of a number.
+    Array(TypeDeclRef, usize, usize),
+}
+
+impl ChoiceDomain {
+    /// Whether this domain is discrete, i.e. ranges are valid choices.
+    fn is_discrete(&self) -> bool {
+        match *self {
+            ChoiceDomain::Array(..) => false,
+            _ => true,
+        }
+    }
+
+    /// The lowest and highest value of the domain.
+    fn bounds(&self) -> (BigInt, BigInt) {
+        match *self {
+            ChoiceDomain::Int(ref lo, ref hi) => (lo.clone(), hi.clone()),
+            ChoiceDomain::Enum(_, num) => (BigInt::zero(), BigInt::from(num) - BigInt::one()),
+            ChoiceDomain::Array(_, num, len) => {
+                (BigInt::zero(), pow(BigInt::from(num), len) - BigInt::one())
+            }
         }
     }
 }
@@ -600,7 +3626,12 @@ pub trait Typeck<I> {
 macro_rules! impl_typeck {
     ($slf:tt, $id:ident: $id_ty:ty => $blk:block) => {
         impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> Typeck<$id_ty> for TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
-            fn typeck(&$slf, $id: $id_ty) $blk
+            fn typeck(&$slf, $id: $id_ty) {
+                if $slf.is_cancelled() {
+                    return;
+                }
+                $blk
+            }
         }
     }
 }
@@ -611,6 +3642,9 @@ macro_rules! impl_typeck_err {
         impl<'sbc, 'lazy, 'sb, 'ast, 'ctx> Typeck<$id_ty> for TypeckContext<'sbc, 'lazy, 'sb, 'ast, 'ctx> {
             fn typeck(&$slf, $id: $id_ty) {
                 use std;
+                if $slf.is_cancelled() {
+                    return;
+                }
                 let res = (move || -> Result<()> { $blk })();
                 std::mem::forget(res);
             }
@@ -625,6 +3659,9 @@ where
     ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
 {
     fn typeck(&self, id: I) {
+        if self.is_cancelled() {
+            return;
+        }
         match ScoreContext::make(self.ctx, id) {
             Ok(_) => (),
             Err(()) => self.failed.set(true),
@@ -635,21 +3672,31 @@ where
 /// Checks whether a node is of a given type.
 pub trait TypeckNode<'ctx, I> {
     fn typeck_node(&self, id: I, expected: &'ctx Ty) -> Result<()>;
+    /// Same as `typeck_node`, but reports a mismatch at `span`.
+    fn typeck_node_spanned(&self, id: I, expected: &'ctx Ty, span: Span) -> Result<()>;
 }
 
 // Implement the `TypeckNode` trait for everything that supports type
 // calculation.
 impl<'lazy, 'sb, 'ast, 'ctx, I> TypeckNode<'ctx, I> for ScoreContext<'lazy, 'sb, 'ast, 'ctx>
 where
+    I: Copy + Into<NodeId>,
     ScoreContext<'lazy, 'sb, 'ast, 'ctx>: NodeMaker<I, &'ctx Ty>,
 {
     fn typeck_node(&self, id: I, expected: &'ctx Ty) -> Result<()> {
         let actual = self.make(id)?;
         if actual != expected {
-            self.emit(DiagBuilder2::error(format!(
-                "typecheck failed, expected {:?}, got {:?}",
-                expected, actual
-            )));
+            self.emit(self.type_mismatch_diag(id, expected, actual));
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn typeck_node_spanned(&self, id: I, expected: &'ctx Ty, span: Span) -> Result<()> {
+        let actual = self.make(id)?;
+        if actual != expected {
+            self.emit(self.type_mismatch_diag_at(Some(span), expected, actual));
             Err(())
         } else {
             Ok(())
@@ -657,30 +3704,24 @@ where
     }
 }
 
-macro_rules! unimp {
-    ($slf:tt, $id:expr) => {{
-        $slf.emit(DiagBuilder2::bug(format!(
-            "typeck of {:?} not implemented",
-            $id
-        )));
-        return;
-    }};
+/// Determine the kind of node reported by the `unimp` macros, e.g.
+/// `BlockStmtRef` for `BlockStmtRef(NodeId(42))`.
+fn unimp_kind<I: Debug>(id: I) -> String {
+    let desc = format!("{:?}", id);
+    match desc.find('(') {
+        Some(i) => desc[..i].to_string(),
+        None => desc,
+    }
 }
 
-macro_rules! unimp_err {
+macro_rules! unimp {
     ($slf:tt, $id:expr) => {{
+        $slf.sess().record_unimplemented(unimp_kind($id));
         $slf.emit(DiagBuilder2::bug(format!(
             "typeck of {:?} not implemented",
             $id
         )));
-        return Err(());
-    }};
-}
-
-macro_rules! unimpmsg {
-    ($slf:tt, $span:expr, $msg:expr) => {{
-        $slf.emit(DiagBuilder2::bug(format!("{} not implemented", $msg)).span($span));
-        return Err(());
+        return;
     }};
 }
 
@@ -697,6 +3738,7 @@ impl_typeck_err!(self, id: LibRef => {
 });
 
 impl_typeck_err!(self, id: PkgDeclRef => {
+    self.ctx.scope(id.into())?;
     let hir = self.ctx.hir(id)?;
     self.typeck_slice(&hir.generics);
     self.typeck_slice(&hir.decls);
@@ -704,15 +3746,44 @@ impl_typeck_err!(self, id: PkgDeclRef => {
 });
 
 impl_typeck_err!(self, id: PkgBodyRef => {
+    self.ctx.scope(id.into())?;
     let hir = self.ctx.hir(id)?;
+    let pkg = self.ctx.hir(hir.pkg.value)?;
+    match pkg.value {
+        PkgRef::Decl(decl) => self.typeck_deferred_consts(decl, &hir.decls)?,
+        PkgRef::Inst(_) => {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is an instantiated package and cannot have a body",
+                    pkg.span.extract()
+                ))
+                .span(pkg.span),
+            );
+            return Err(());
+        }
+    }
     self.typeck_slice(&hir.decls);
     Ok(())
 });
 
 impl_typeck_err!(self, id: PkgInstRef => {
-    let _hir = self.ctx.hir(id)?;
-    // self.typeck_slice(&hir.generic_map);
-    Ok(())
+    let hir = self.ctx.hir(id)?;
+    let pkg = self.ctx.hir(hir.pkg.value)?;
+    let decl = match pkg.value {
+        PkgRef::Decl(decl) => decl,
+        PkgRef::Inst(_) => {
+            self.emit(
+                DiagBuilder2::error(format!(
+                    "`{}` is an instantiated package and cannot be instantiated again",
+                    pkg.span.extract()
+                ))
+                .span(pkg.span),
+            );
+            return Err(());
+        }
+    };
+    let generics = &self.ctx.hir(decl)?.generics;
+    self.typeck_generic_map(id.into(), hir.name.span, generics, &hir.generic_map)
 });
 
 impl_typeck!(self, id: CtxRef => {
@@ -724,6 +3795,9 @@ impl_typeck!(self, id: CfgRef => {
 });
 
 impl_typeck_err!(self, id: EntityRef => {
+    // Resolve the context clause first, such that a bad `use` clause is
+    // reported once rather than for every name in the unit.
+    self.ctx.scope(id.into())?;
     let hir = self.ctx.hir(id)?;
     for &generic in &hir.generics {
         self.typeck(generic);
@@ -731,42 +3805,284 @@ impl_typeck_err!(self, id: EntityRef => {
     for &port in &hir.ports {
         self.typeck(port);
     }
-    Ok(())
-});
+    Ok(())
+});
+
+impl_typeck_err!(self, id: ArchRef => {
+    self.ctx.scope(id.into())?;
+    let hir = self.ctx.hir(id)?;
+    self.typeck(hir.entity);
+    for &decl in &hir.decls {
+        self.typeck(decl);
+    }
+    for &stmt in &hir.stmts {
+        self.typeck(stmt);
+    }
+    if self.ctx.sess.opts.lint_signals && !self.failed.get() {
+        self.lint_signals(hir)?;
+    }
+    Ok(())
+});
+
+impl_typeck!(self, id: GenericRef => {
+    match id {
+        GenericRef::Type(id)    => self.typeck(id),
+        GenericRef::Subprog(id) => self.typeck(id),
+        GenericRef::Pkg(id)     => self.typeck(id),
+        GenericRef::Const(id)   => self.typeck(id),
+    }
+});
+
+// impl_typeck!(self, id: IntfSignalRef => {
+//     self.typeck(self.hir(id)?.ty)
+// });
+
+impl_typeck!(self, _id: IntfTypeRef => {
+    // An interface type declaration is incomplete; its actual is checked as
+    // part of the generic map of the instantiation.
+});
+
+impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
+    /// Determine the signature of a subprogram specification.
+    ///
+    /// The parameter and return types are resolved in `scope_id`, which is the
+    /// scope the subprogram is declared in.
+    pub fn subprog_spec_ty(
+        &self,
+        scope_id: ScopeRef,
+        ast: &'ast ast::SubprogSpec,
+    ) -> Result<&'ctx Ty> {
+        let ctx = AddContext::new(self, scope_id);
+        let mut had_fails = false;
+
+        // Determine the type of each parameter.
+        let mut args = Vec::new();
+        for decl in ast.params.iter().flat_map(|p| p.iter()) {
+            match *decl {
+                ast::IntfDecl::ObjDecl(ref decl) => {
+                    let ty = match ctx
+                        .add_subtype_ind(&decl.ty)
+                        .and_then(|ty| self.lazy_typeval(ty))
+                    {
+                        Ok(ty) => ty,
+                        Err(()) => {
+                            had_fails = true;
+                            continue;
+                        }
+                    };
+                    for name in &decl.names {
+                        args.push(SubprogTyArg::named(ty.clone(), name.name));
+                    }
+                }
+                ref wrong => {
+                    self.emit(
+                        DiagBuilder2::error(format!(
+                            "a {} cannot appear in a parameter list",
+                            wrong.desc()
+                        ))
+                        .span(wrong.human_span()),
+                    );
+                    had_fails = true;
+                }
+            }
+        }
+
+        // Determine the return type.
+        let ret = match ast.retty {
+            Some(ref name) => {
+                let tm = self.unpack_type_mark(name.into(), scope_id)?;
+                match self.ty(tm.value) {
+                    Ok(ty) => Some(ty.clone()),
+                    Err(()) => {
+                        had_fails = true;
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        if had_fails {
+            Err(())
+        } else {
+            Ok(self.intern_ty(SubprogTy::new(args, ret)))
+        }
+    }
+
+    /// Check whether an alias declaration denotes a subprogram.
+    ///
+    /// This is the case if the aliased name carries a signature.
+    pub fn is_subprog_alias(&self, id: AliasDeclRef) -> bool {
+        match self.ast(id).1.target.parts.last() {
+            Some(&ast::NamePart::Signature(..)) => true,
+            _ => false,
+        }
+    }
+
+    /// Resolve the subprogram denoted by an alias declaration.
+    ///
+    /// The aliased name must carry a signature, and exactly one of the
+    /// subprograms it denotes must match the signature's profile.
+    ///
+    /// See IEEE 1076-2008 sections 4.5.3 and 6.6.3.
+    pub fn alias_subprog(&self, id: AliasDeclRef) -> Result<Spanned<Def>> {
+        let (scope_id, ast) = self.ast(id);
+        let (sig, parts) = match ast.target.parts.split_last() {
+            Some((&ast::NamePart::Signature(ref sig), parts)) => (sig, parts),
+            _ => {
+                self.emit(
+                    DiagBuilder2::bug("aliases of objects and types not implemented")
+                        .span(ast.span),
+                );
+                return Err(());
+            }
+        };
+        if let Some(ref subty) = ast.subtype {
+            self.emit(
+                DiagBuilder2::error("alias of a subprogram cannot have a subtype indication")
+                    .span(subty.span),
+            );
+            return Err(());
+        }
+
+        // Determine the profile given by the signature.
+        let mut had_fails = false;
+        let mut args = Vec::new();
+        for arg in &sig.args {
+            match self
+                .unpack_type_mark(arg.into(), scope_id)
+                .and_then(|tm| self.hir(tm.value))
+            {
+                Ok(tm) => args.push(SubprogTyArg::positional(Ty::Named(
+                    tm.span.into(),
+                    tm.value,
+                ))),
+                Err(()) => had_fails = true,
+            }
+        }
+        let ret = match sig.retty {
+            Some(ref name) => match self
+                .unpack_type_mark(name.into(), scope_id)
+                .and_then(|tm| self.hir(tm.value))
+            {
+                Ok(tm) => Some(Ty::Named(tm.span.into(), tm.value)),
+                Err(()) => {
+                    had_fails = true;
+                    None
+                }
+            },
+            None => None,
+        };
+        if had_fails {
+            return Err(());
+        }
+        let profile = SubprogTy::new(args, ret);
+
+        // Find the subprograms that match the profile.
+        let name = ast::CompoundName {
+            span: match parts.last() {
+                Some(&ast::NamePart::Select(ref last)) => {
+                    Span::union(ast.target.primary.span, last.span)
+                }
+                _ => ast.target.primary.span,
+            },
+            parts: parts.to_vec(),
+            ..ast.target.clone()
+        };
+        let defs = TermContext::new(self, scope_id).resolve_overloaded_name(&name)?;
+        let mut matched = Vec::new();
+        for def in defs {
+            let ty = match def.value {
+                Def::Subprog(id) => self.lazy_typeval(id)?,
+                Def::BuiltinOp(id) => self.lazy_typeval(id)?,
+                Def::Alias(other) if other != id && self.is_subprog_alias(other) => {
+                    self.ty(other)?
+                }
+                _ => continue,
+            };
+            let is_match = match *ty {
+                Ty::Subprog(ref ty) => {
+                    ty.args.len() == profile.args.len()
+                        && ty
+                            .args
+                            .iter()
+                            .zip(profile.args.iter())
+                            .all(|(a, b)| are_types_matching(self, &a.ty, &b.ty))
+                        && match (&ty.ret, &profile.ret) {
+                            (&Some(ref a), &Some(ref b)) => are_types_matching(self, a, b),
+                            (&None, &None) => true,
+                            _ => false,
+                        }
+                }
+                _ => false,
+            };
+            if is_match {
+                matched.push(def);
+            }
+        }
+        match matched.len() {
+            1 => Ok(matched[0]),
+            0 => {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "no subprogram `{}` with profile `{}`",
+                        name.span.extract(),
+                        profile
+                    ))
+                    .span(ast.target.span),
+                );
+                Err(())
+            }
+            _ => {
+                let mut d = DiagBuilder2::error(format!(
+                    "subprogram `{}` with profile `{}` is ambiguous",
+                    name.span.extract(),
+                    profile
+                ))
+                .span(ast.target.span)
+                .add_note("Found the following subprograms:");
+                for def in matched {
+                    if def.span != INVALID_SPAN {
+                        d = d.span(def.span);
+                    }
+                }
+                self.emit(d);
+                Err(())
+            }
+        }
+    }
+}
 
-impl_typeck_err!(self, id: ArchRef => {
-    let hir = self.ctx.hir(id)?;
-    self.typeck(hir.entity);
-    for &decl in &hir.decls {
-        self.typeck(decl);
-    }
-    for &stmt in &hir.stmts {
-        self.typeck(stmt);
+/// Determine the type of an alias declaration.
+///
+/// Only aliases of subprograms are supported, in which case the type is the
+/// signature of the aliased subprogram.
+impl_make!(self, id: AliasDeclRef => &Ty {
+    let def = self.alias_subprog(id)?;
+    match def.value {
+        Def::Subprog(id) => self.lazy_typeval(id),
+        Def::BuiltinOp(id) => self.lazy_typeval(id),
+        Def::Alias(id) => self.ty(id),
+        _ => unreachable!(),
     }
-    Ok(())
 });
 
-impl_typeck!(self, id: GenericRef => {
-    match id {
-        GenericRef::Type(id)    => self.typeck(id),
-        GenericRef::Subprog(id) => self.typeck(id),
-        GenericRef::Pkg(id)     => self.typeck(id),
-        GenericRef::Const(id)   => self.typeck(id),
+/// Determine the signature of an interface subprogram.
+///
+/// See IEEE 1076-2008 section 6.5.4.
+impl_make!(self, id: IntfSubprogRef => &Ty {
+    let (scope_id, ast) = self.ast(id);
+    if ast.spec.kind == ast::SubprogKind::Func && ast.spec.retty.is_none() {
+        self.emit(
+            DiagBuilder2::error(format!(
+                "generic function `{}` has no return type",
+                ast.spec.name.span.extract()
+            ))
+            .span(ast.spec.name.span),
+        );
+        return Err(());
     }
-});
-
-// impl_typeck!(self, id: IntfSignalRef => {
-//     self.typeck(self.hir(id)?.ty)
-// });
-
-impl_typeck!(self, id: IntfTypeRef => {
-    unimp!(self, id)
-    // self.typeck(self.hir(id)?.ty)
-});
-
-impl_typeck!(self, id: IntfSubprogRef => {
-    unimp!(self, id)
-    // self.typeck(self.hir(id)?.ty)
+    self.subprog_spec_ty(scope_id, &ast.spec)
 });
 
 impl_typeck!(self, id: IntfPkgRef => {
@@ -775,23 +4091,56 @@ impl_typeck!(self, id: IntfPkgRef => {
 });
 
 impl_make!(self, id: IntfConstRef => &Ty {
-    // TODO: Implement this.
-    unimp_err!(self, id)
+    let (scope_id, decl, subty_id, _) = self.ast(id);
+    let ty = self.ty(subty_id)?;
+    let init = match decl.default {
+        Some(ref e) => AddContext::new(self, scope_id).add_expr(e)?,
+        None => return Ok(ty),
+    };
+    let tyc = TypeckContext::new(self);
+    let result = tyc.typeck_assigned_value(init, ty);
+    if tyc.finish() && result.is_ok() {
+        Ok(ty)
+    } else {
+        Err(())
+    }
 });
 
 impl_make!(self, id: IntfVarRef => &Ty {
-    // TODO: Implement this.
-    unimp_err!(self, id)
+    let (scope_id, decl, subty_id, _) = self.ast(id);
+    let ty = self.ty(subty_id)?;
+    let init = match decl.default {
+        Some(ref e) => AddContext::new(self, scope_id).add_expr(e)?,
+        None => return Ok(ty),
+    };
+    let tyc = TypeckContext::new(self);
+    let result = tyc.typeck_assigned_value(init, ty);
+    if tyc.finish() && result.is_ok() {
+        Ok(ty)
+    } else {
+        Err(())
+    }
 });
 
 impl_make!(self, id: IntfSignalRef => &Ty {
     let hir = self.hir(id)?;
-    self.ty(hir.ty)
+    let ty = self.ty(hir.ty)?;
+    let init = match hir.init {
+        Some(init) => init,
+        None => return Ok(ty),
+    };
+    let tyc = TypeckContext::new(self);
+    let result = tyc.port_default_ty(ty, init);
+    if tyc.finish() {
+        result
+    } else {
+        Err(())
+    }
 });
 
 impl_make!(self, id: IntfFileRef => &Ty {
-    // TODO: Implement this.
-    unimp_err!(self, id)
+    let (_, _, subty_id, _) = self.ast(id);
+    self.ty(subty_id)
 });
 
 impl_typeck!(self, id: DeclInPkgRef => {
@@ -801,7 +4150,11 @@ impl_typeck!(self, id: DeclInPkgRef => {
         DeclInPkgRef::Pkg(id)         => self.typeck(id),
         DeclInPkgRef::PkgInst(id)     => self.typeck(id),
         DeclInPkgRef::Type(id)        => self.typeck(id),
-        DeclInPkgRef::Subtype(id)     => self.typeck(id),
+        DeclInPkgRef::Subtype(id)     => {
+            if self.check_subtype_decl_static(id).is_ok() {
+                self.typeck(id)
+            }
+        }
         DeclInPkgRef::Const(id)       => self.typeck(id),
         DeclInPkgRef::Signal(id)      => self.typeck(id),
         DeclInPkgRef::Var(id)         => self.typeck(id),
@@ -944,8 +4297,31 @@ impl_typeck_err!(self, id: SubprogDeclRef => {
 impl_typeck_err!(self, id: SubprogBodyRef => {
     let hir = self.ctx.hir(id)?;
     self.typeck_subprog_spec(&hir.spec);
+    let ret = match hir.spec.return_type {
+        Some(ref ty) => Some(self.ctx.ty(ty.value)?),
+        None => None,
+    };
+    let _region = self.with_scope(|r| {
+        *r = TypeckRegion {
+            subprog: Some(ret),
+            ..Default::default()
+        }
+    });
     self.typeck_slice(&hir.decls);
     self.typeck_slice(&hir.stmts);
+
+    // A function must not complete without returning a value. See IEEE
+    // 1076-2008 section 4.3.
+    if ret.is_some() && self.may_complete(&hir.stmts)? {
+        self.emit(
+            DiagBuilder2::warning(format!(
+                "function `{}` may reach its end without returning a value",
+                hir.spec.name.value
+            ))
+            .span(hir.spec.name.span)
+            .add_note("Falling off the end of a function is an error at runtime."),
+        );
+    }
     Ok(())
 });
 
@@ -975,12 +4351,15 @@ impl_typeck_err!(self, id: FileDeclRef => {
     Ok(())
 });
 
-impl_typeck!(self, id: AliasDeclRef => {
-    unimp!(self, id)
-});
-
-impl_typeck!(self, id: CompDeclRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: CompDeclRef => {
+    let hir = self.ctx.hir(id)?;
+    for &generic in &hir.generics {
+        self.typeck(generic);
+    }
+    for &port in &hir.ports {
+        self.typeck(port);
+    }
+    Ok(())
 });
 
 impl_typeck!(self, id: AttrDeclRef => {
@@ -1007,12 +4386,51 @@ impl_typeck!(self, id: GroupDeclRef => {
     unimp!(self, id)
 });
 
-impl_typeck!(self, id: BlockStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: BlockStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    self.ctx.scope(id.into())?;
+    let mut result = Ok(());
+    if let Some(guard) = hir.guard {
+        result = result.and(self.typeck_condition(guard));
+    }
+    for &generic in &hir.generics {
+        self.typeck(generic);
+    }
+    for &port in &hir.ports {
+        self.typeck(port);
+    }
+    let span = hir.label.map(|l| l.span).unwrap_or(hir.span);
+    result = result.and(self.typeck_generic_map(id.into(), span, &hir.generics, &hir.generic_map));
+    result = result.and(self.typeck_port_map(span, &hir.ports, &hir.port_map));
+    for &decl in &hir.decls {
+        self.typeck(decl);
+    }
+    for &stmt in &hir.stmts {
+        self.typeck(stmt);
+    }
+    result
 });
 
 impl_typeck_err!(self, id: ProcessStmtRef => {
     let hir = self.ctx.hir(id)?;
+    let sensitized = match hir.sensitivity {
+        hir::ProcessSensitivity::None => false,
+        hir::ProcessSensitivity::All | hir::ProcessSensitivity::List(..) => true,
+    };
+    let _region = self.with_scope(|r| {
+        *r = TypeckRegion {
+            sensitized: sensitized,
+            ..Default::default()
+        }
+    });
+    // Signals in the sensitivity list are read whenever the process resumes.
+    if let hir::ProcessSensitivity::List(ref defs) = hir.sensitivity {
+        for def in defs {
+            if let Def::Signal(sig) = *def {
+                self.ctx.record_signal_read(sig);
+            }
+        }
+    }
     for &decl in &hir.decls {
         self.typeck(decl);
     }
@@ -1022,89 +4440,325 @@ impl_typeck_err!(self, id: ProcessStmtRef => {
     Ok(())
 });
 
-impl_typeck!(self, id: ConcCallStmtRef => {
-    unimp!(self, id)
+// Concurrent procedure calls and assertions are checked as their equivalent
+// sequential statements.
+impl_typeck_err!(self, id: ConcCallStmtRef => {
+    let stmt = self.ctx.hir(id)?;
+    self.lazy_typeck(stmt);
+    Ok(())
 });
 
-impl_typeck!(self, id: ConcAssertStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: ConcAssertStmtRef => {
+    let stmt = self.ctx.hir(id)?;
+    self.lazy_typeck(stmt);
+    Ok(())
 });
 
-impl_typeck!(self, id: ConcSigAssignStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: ConcSigAssignStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    let guard = if hir.guarded { self.typeck_guarded_assign(hir) } else { Ok(()) };
+    self.typeck_sig_assign_stmt(hir).and(guard)
 });
 
-impl_typeck!(self, id: CompInstStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: CompInstStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    let comp = self.ctx.hir(hir.comp.value)?;
+    let generics =
+        self.typeck_generic_map(id.into(), hir.comp.span, &comp.generics, &hir.generic_map);
+    let ports = self.typeck_port_map(hir.comp.span, &comp.ports, &hir.port_map);
+    generics.and(ports)
 });
 
-impl_typeck!(self, id: ForGenStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: ForGenStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    self.ctx.scope(id.into())?;
+    let param = self.lazy_typeval(id);
+    let range = match hir.range.value {
+        // Only explicit ranges are checked for now.
+        hir::DiscreteRange::Subtype(_) => Ok(()),
+        hir::DiscreteRange::Range(hir::Range::Immediate(_, lb, rb)) => {
+            let lb = self.check_generate_static(lb, "range");
+            let rb = self.check_generate_static(rb, "range");
+            lb.and(rb)
+        }
+        hir::DiscreteRange::Range(hir::Range::Attr(attr)) => {
+            self.check_generate_static(attr, "range")
+        }
+    };
+    for &decl in &hir.decls {
+        self.typeck(decl);
+    }
+    for &stmt in &hir.stmts {
+        self.typeck(stmt);
+    }
+    param.and(range).map(|_| ())
 });
 
-impl_typeck!(self, id: IfGenStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: IfGenStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    let mut result = Ok(());
+    for &(cond, body) in &hir.conds {
+        let cond = self.typeck_condition(cond).and(self.check_generate_static(cond, "condition"));
+        result = result.and(cond);
+        self.typeck(body);
+    }
+    if let Some(body) = hir.alt {
+        self.typeck(body);
+    }
+    result
 });
 
-impl_typeck!(self, id: CaseGenStmtRef => {
-    unimp!(self, id)
+impl_typeck_err!(self, id: CaseGenStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    let choices = self.typeck_choices(
+        hir.switch,
+        false,
+        hir.cases.iter().map(|&(ref choices, _)| choices),
+        hir.span,
+    );
+    let switch = self.check_generate_static(hir.switch, "expression");
+    for &(_, body) in &hir.cases {
+        self.typeck(body);
+    }
+    choices.and(switch)
 });
 
-impl_typeck_err!(self, id: SigAssignStmtRef => {
+impl_typeck_err!(self, id: GenBodyRef => {
     let hir = self.ctx.hir(id)?;
-    let lhs_ty = match hir.target {
-        hir::SigAssignTarget::Name(sig) => self.ctx.ty(sig)?,
-        hir::SigAssignTarget::Aggregate => unimpmsg!(self, hir.target_span, "assignment to aggregate signal"),
-    };
-    // let mut ctx = TypeckContext::new(self);
-    // let typeck_dm = |dm| match dm {
-    //     // TODO: typeck time expression
-    //     // &hir::DelayMechanism::RejectInertial(expr) => self.typeck_node(expr, self.intern_ty(/* time type */))?,
-    //     _ => Ok(()),
-    // };
-    match hir.kind {
-        hir::SigAssignKind::SimpleWave(ref dm, ref wave) => {
-            self.typeck_delay_mechanism(dm);
-            self.typeck_waveform(wave, lhs_ty);
-        }
-        hir::SigAssignKind::SimpleForce(_, _expr) => {
-            // self.typeck_node(expr, lhs_ty)?;
-        }
-        hir::SigAssignKind::SimpleRelease(_) => (),
-        hir::SigAssignKind::CondWave(ref dm, ref _cond) => {
-            self.typeck_delay_mechanism(dm);
-            // self.typeck_node(cond, lhs_ty)?;
-        }
-        hir::SigAssignKind::CondForce(_, ref _cond) => {
-            // self.typeck_node(cond, lhs_ty)?;
-        }
-        hir::SigAssignKind::SelWave(ref dm, ref _sel) => {
-            self.typeck_delay_mechanism(dm);
-            // self.typeck_node(sel, lhs_ty)?;
-        }
-        hir::SigAssignKind::SelForce(_, ref _sel) => {
-            // self.typeck_node(sel, lhs_ty)?;
-        }
+    self.ctx.scope(id.into())?;
+    for &decl in &hir.decls {
+        self.typeck(decl);
+    }
+    for &stmt in &hir.stmts {
+        self.typeck(stmt);
     }
     Ok(())
 });
 
+impl_typeck_err!(self, id: SigAssignStmtRef => {
+    let hir = self.ctx.hir(id)?;
+    self.typeck_sig_assign_stmt(hir)
+});
+
+/// Check whether a value lies within the range of an integer type.
+fn int_range_contains(ty: &IntTy, value: &BigInt) -> bool {
+    let (lo, hi) = match ty.dir {
+        Dir::To => (&ty.left_bound, &ty.right_bound),
+        Dir::Downto => (&ty.right_bound, &ty.left_bound),
+    };
+    value >= lo && value <= hi
+}
+
 impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
     /// Replace `Ty::Named` by the actual type definition recursively.
+    ///
+    /// Gives up with an error if more than `max_type_nesting` names have to be
+    /// followed, rather than overflowing the stack on pathological inputs.
     pub fn deref_named_type<'a>(&self, ty: &'a Ty) -> Result<&'a Ty>
     where
         'ctx: 'a,
     {
-        match ty {
-            &Ty::Named(_, tmr) => {
-                let inner = self.ty(tmr)?;
-                self.deref_named_type(inner)
+        let mut current = ty;
+        let mut depth = 0;
+        while let Ty::Named(_, tmr) = *current {
+            if depth >= self.sess.opts.max_type_nesting {
+                let mut diag = DiagBuilder2::error(format!(
+                    "type alias nesting too deep while resolving {}",
+                    ty
+                ));
+                if let Some(span) = self.type_decl_span(ty) {
+                    diag = diag.span(span);
+                }
+                self.emit(diag.add_note(format!(
+                    "gave up after following {} type names; the limit can be raised with \
+                     `--max-type-nesting`",
+                    depth
+                )));
+                return Err(());
+            }
+            current = self.ty(tmr)?;
+            depth += 1;
+        }
+        Ok(current)
+    }
+
+    /// Assemble the diagnostic for a node whose type does not match.
+    ///
+    /// Points at the node, and at the declaration of the expected type if it
+    /// is known.
+    pub fn type_mismatch_diag<I>(&self, id: I, exp: &Ty, act: &Ty) -> DiagBuilder2
+    where
+        I: Into<NodeId>,
+    {
+        self.type_mismatch_diag_at(self.span(id), exp, act)
+    }
+
+    /// Assemble the diagnostic for a value whose type does not match.
+    ///
+    /// Same as `type_mismatch_diag`, but points at `span` rather than looking
+    /// up the span of a node.
+    pub fn type_mismatch_diag_at(&self, span: Option<Span>, exp: &Ty, act: &Ty) -> DiagBuilder2 {
+        let mut diag = DiagBuilder2::error(format!(
+            "expected type {}, but got {}",
+            exp.colored(),
+            act.colored()
+        ));
+        if let Some(span) = span {
+            diag = diag.label(
+                span,
+                format!("value of type {} originates here", act.colored()),
+            );
+        }
+        if let Some(span) = self.type_decl_span(exp) {
+            diag = diag.label(
+                span,
+                format!("expected type {} declared here", exp.colored()),
+            );
+        }
+        if let Some(diff) = self.explain_type_diff(exp, act) {
+            diag = diag.add_note(diff);
+        }
+        diag
+    }
+
+    /// Determine the span of the declaration of a named type.
+    ///
+    /// Returns `None` for anonymous and builtin types.
+    pub fn type_decl_span(&self, ty: &Ty) -> Option<Span> {
+        match *ty {
+            Ty::Named(_, TypeMarkRef::Type(id)) => self.span(id),
+            Ty::Named(_, TypeMarkRef::Subtype(id)) => self.span(id),
+            _ => None,
+        }
+    }
+
+    /// Determine the base type of a type mark.
+    ///
+    /// Follows subtype declarations to the type they constrain. See IEEE
+    /// 1076-2008 section 5.1.
+    pub fn base_type_mark(&self, tm: TypeMarkRef) -> Result<TypeMarkRef> {
+        match tm {
+            TypeMarkRef::Type(id) => Ok(builtin_base_type(id).into()),
+            TypeMarkRef::Subtype(id) => {
+                let hir = self.hir(id)?;
+                self.base_type_mark(self.lazy_hir(hir.subty)?.type_mark.value)
+            }
+        }
+    }
+
+    /// Check whether two types have the same base type.
+    ///
+    /// Named types are followed to the type they constrain, and range and
+    /// index constraints are ignored. Two differently constrained subtypes of
+    /// `INTEGER` are thus base-equal. This differs from `==`, which also
+    /// compares the constraints and the name by which a type was referred to,
+    /// and from a subtype check, which is directional: `NATURAL` is a subtype
+    /// of `INTEGER` but not vice versa, yet the two are base-equal. See IEEE
+    /// 1076-2008 section 5.1.
+    pub fn base_types_equal(&self, a: &Ty, b: &Ty) -> bool {
+        if let (&Ty::Named(_, ta), &Ty::Named(_, tb)) = (a, b) {
+            match (self.base_type_mark(ta), self.base_type_mark(tb)) {
+                (Ok(ta), Ok(tb)) => return ta == tb,
+                _ => return false,
+            }
+        }
+        let (a, b) = match (self.deref_named_type(a), self.deref_named_type(b)) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => return false,
+        };
+        match (a, b) {
+            // TODO: Anonymous integer types carry no declaration, so they
+            // cannot be told apart and are all considered base-equal.
+            (&Ty::Int(_), &Ty::Int(_)) => true,
+            (&Ty::Enum(ref a), &Ty::Enum(ref b)) => a.decl == b.decl,
+            (&Ty::Physical(ref a), &Ty::Physical(ref b)) => a.decl == b.decl,
+            (&Ty::Protected(ref a), &Ty::Protected(ref b)) => a.decl == b.decl,
+            (&Ty::Array(ref a), &Ty::Array(ref b)) => match (a.decl, b.decl) {
+                (Some(da), Some(db)) => da == db,
+                _ => {
+                    a.indices.len() == b.indices.len()
+                        && self.base_types_equal(&a.element, &b.element)
+                }
+            },
+            (&Ty::Record(ref a), &Ty::Record(ref b)) => {
+                a.fields.len() == b.fields.len()
+                    && a.fields
+                        .iter()
+                        .zip(b.fields.iter())
+                        .all(|(a, b)| a.0 == b.0 && self.base_types_equal(&a.1, &b.1))
+            }
+            (&Ty::Access(ref a), &Ty::Access(ref b)) | (&Ty::File(ref a), &Ty::File(ref b)) => {
+                self.base_types_equal(a, b)
+            }
+            (&Ty::Range(ref a), &Ty::Range(ref b)) => self.base_types_equal(&a.bound, &b.bound),
+            (a, b) => a == b,
+        }
+    }
+
+    /// Explain how two types differ.
+    ///
+    /// Returns `None` if the types are structurally equal. Otherwise describes
+    /// the first difference found, e.g. `ranges differ: 0 to 7 vs 0 to 15`,
+    /// such that a type mismatch can be reported as more than just two type
+    /// names.
+    pub fn explain_type_diff(&self, a: &Ty, b: &Ty) -> Option<String> {
+        if a == b {
+            return None;
+        }
+        if !self.base_types_equal(a, b) {
+            return Some(format!("base types differ: {} vs {}", a, b));
+        }
+        let (a, b) = match (self.deref_named_type(a), self.deref_named_type(b)) {
+            (Ok(a), Ok(b)) => (a, b),
+            _ => return None,
+        };
+        match (a, b) {
+            (&Ty::Int(ref a), &Ty::Int(ref b)) => explain_int_diff(a, b),
+            (&Ty::Physical(ref a), &Ty::Physical(ref b)) => explain_int_diff(&a.base, &b.base),
+            (&Ty::Range(ref a), &Ty::Range(ref b)) => {
+                if a.dir != b.dir {
+                    Some(format!("directions differ: {} vs {}", a, b))
+                } else if a.static_bounds != b.static_bounds {
+                    Some(format!("ranges differ: {} vs {}", a, b))
+                } else {
+                    self.explain_type_diff(&a.bound, &b.bound)
+                }
+            }
+            (&Ty::Array(ref a), &Ty::Array(ref b)) => {
+                for (i, (ia, ib)) in a.indices.iter().zip(b.indices.iter()).enumerate() {
+                    let diff = match (ia, ib) {
+                        (&ArrayIndex::Unbounded(ref ta), &ArrayIndex::Unbounded(ref tb))
+                        | (&ArrayIndex::Constrained(ref ta), &ArrayIndex::Constrained(ref tb)) => {
+                            self.explain_type_diff(ta, tb)
+                        }
+                        _ => Some(format!("constraints differ: {} vs {}", ia, ib)),
+                    };
+                    if let Some(diff) = diff {
+                        return Some(format!("index {}: {}", i + 1, diff));
+                    }
+                }
+                self.explain_type_diff(&a.element, &b.element)
+                    .map(|diff| format!("element types differ: {}", diff))
+            }
+            (&Ty::Access(ref a), &Ty::Access(ref b)) | (&Ty::File(ref a), &Ty::File(ref b)) => {
+                self.explain_type_diff(a, b)
             }
-            other => Ok(other),
+            _ => None,
         }
     }
 }
 
+/// Explain how the ranges of two integer types differ.
+fn explain_int_diff(a: &IntTy, b: &IntTy) -> Option<String> {
+    if a.dir != b.dir {
+        Some(format!("directions differ: {} vs {}", a, b))
+    } else if a != b {
+        Some(format!("ranges differ: {} vs {}", a, b))
+    } else {
+        None
+    }
+}
+
 /// Determine the type of a type mark.
 impl_make!(self, id: TypeMarkRef => &Ty {
     match id {
@@ -1156,7 +4810,13 @@ impl_make!(self, id: TypeDeclRef => &Ty {
             let base = self.make_range_ty(dir, lb_id, rb_id, data.span)?;
             let base = match *base {
                 Ty::Int(ref it) => it.clone(),
-                _ => unreachable!(),
+                _ => {
+                    self.emit(
+                        DiagBuilder2::error("bounds of a physical type must be integers")
+                        .span(data.span)
+                    );
+                    return Err(());
+                }
             };
             let units = units.iter().map(|&(name, ref abs, ref rel)|
                 PhysicalUnit::new(name.value, abs.clone(), rel.clone())
@@ -1166,6 +4826,32 @@ impl_make!(self, id: TypeDeclRef => &Ty {
 
         hir::TypeData::Enum(ref lits) => {
             use crate::ty2::{EnumBasetype, EnumVariant};
+
+            // Make sure each literal is declared only once. Identifiers and
+            // character literals never clash with each other, such that `a`
+            // and `'a'` may appear in the same enumeration.
+            let mut had_fails = false;
+            let mut used_idents = HashMap::new();
+            let mut used_chars = HashMap::new();
+            for lit in lits {
+                let (prev, span) = match *lit {
+                    hir::EnumLit::Ident(sp) => (used_idents.insert(sp.value, sp.span), sp.span),
+                    hir::EnumLit::Char(sp) => (used_chars.insert(sp.value, sp.span), sp.span),
+                };
+                if let Some(prev) = prev {
+                    self.emit(
+                        DiagBuilder2::error(format!("enumeration literal `{}` declared more than once", span.extract()))
+                        .span(span)
+                        .add_note("Previous declaration was here:")
+                        .span(prev)
+                    );
+                    had_fails = true;
+                }
+            }
+            if had_fails {
+                return Err(());
+            }
+
             let ty = EnumBasetype::new(lits.iter().map(|l| match *l {
                 hir::EnumLit::Ident(sp) => EnumVariant::from(sp.value),
                 hir::EnumLit::Char(sp)  => EnumVariant::from(sp.value),
@@ -1208,7 +4894,7 @@ impl_make!(self, id: TypeDeclRef => &Ty {
                 return Err(());
             }
             let elem_ty = self.ty(elem_ty)?.clone();
-            Ok(self.intern_ty(ArrayTy::new(indices, Box::new(elem_ty))))
+            Ok(self.intern_ty(ArrayTy::new(indices, Box::new(elem_ty)).with_decl(Some(id))))
         }
 
         hir::TypeData::File(tm) => {
@@ -1239,10 +4925,121 @@ impl_make!(self, id: TypeDeclRef => &Ty {
             }
             Ok(self.intern_ty(RecordTy::new(mapped_fields)))
         }
+
+        hir::TypeData::Protected(ref methods) => {
+            let mut had_fails = false;
+            for &method in methods {
+                if self.lazy_typeval(method).is_err() {
+                    had_fails = true;
+                }
+            }
+            if had_fails {
+                return Err(());
+            }
+            Ok(self.intern_ty(ProtectedTy::new(id)))
+        }
+
+        hir::TypeData::ProtectedBody(ref methods) => {
+            let decl = self.protected_type_of_body(hir.parent, hir.name)?;
+            self.check_protected_body(decl, methods, hir.name)?;
+            self.ty(decl)
+        }
     }
 });
 
 impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
+    /// Find the protected type declaration a protected type body belongs to.
+    fn protected_type_of_body(&self, scope: ScopeRef, name: Spanned<Name>) -> Result<TypeDeclRef> {
+        let defs = self.resolve_name(name.map_into(), scope, false, true)?;
+        for def in defs {
+            if let Def::Type(id) = def.value {
+                if let Ty::Protected(_) = *self.ty(id)? {
+                    return Ok(id);
+                }
+            }
+        }
+        self.emit(
+            DiagBuilder2::error(format!("no protected type `{}` declared", name.value))
+                .span(name.span)
+                .add_note(
+                    "A protected type body must be preceded by the declaration of the \
+                     protected type. See IEEE 1076-2008 section 5.6.3.",
+                ),
+        );
+        Err(())
+    }
+
+    /// Check that a protected type body implements each method of its
+    /// declaration, with a conforming signature.
+    fn check_protected_body(
+        &self,
+        decl: TypeDeclRef,
+        bodies: &[SubprogDeclRef],
+        name: Spanned<Name>,
+    ) -> Result<()> {
+        let methods = match self.lazy_hir(decl)?.data {
+            Some(Spanned {
+                value: hir::TypeData::Protected(ref methods),
+                ..
+            }) => methods,
+            _ => unreachable!(),
+        };
+        let mut had_fails = false;
+        for &method in methods {
+            let method_ast = self.ast(method).1;
+            let method_ty = self.lazy_typeval(method)?;
+            let mut found = false;
+            for &body in bodies {
+                let body_ast = self.ast(body).1;
+                if body_ast.spec.name.kind != method_ast.spec.name.kind {
+                    continue;
+                }
+                if self.are_signatures_conforming(method_ty, self.lazy_typeval(body)?) {
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                self.emit(
+                    DiagBuilder2::error(format!(
+                        "protected type body `{}` does not implement `{}`",
+                        name.value,
+                        method_ast.spec.span.extract()
+                    ))
+                    .span(name.span)
+                    .add_note("Method declared here:")
+                    .span(method_ast.spec.span),
+                );
+                had_fails = true;
+            }
+        }
+        if had_fails {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check whether two subprogram signatures conform, i.e. have the same
+    /// parameter names, parameter types, and return type.
+    fn are_signatures_conforming(&self, a: &Ty, b: &Ty) -> bool {
+        let (a, b) = match (a, b) {
+            (&Ty::Subprog(ref a), &Ty::Subprog(ref b)) => (a, b),
+            _ => return false,
+        };
+        let args_conform = a.args.len() == b.args.len()
+            && a.args
+                .iter()
+                .zip(b.args.iter())
+                .all(|(a, b)| a.name == b.name && are_types_matching(self, &a.ty, &b.ty));
+        let ret_conforms = match (&a.ret, &b.ret) {
+            (&Some(ref a), &Some(ref b)) => are_types_matching(self, a, b),
+            (&None, &None) => true,
+            _ => false,
+        };
+        args_conform && ret_conforms
+    }
+
     pub fn make_range_ty(
         &self,
         dir: hir::Dir,
@@ -1264,9 +5061,8 @@ impl<'lazy, 'sb, 'ast, 'ctx> ScoreContext<'lazy, 'sb, 'ast, 'ctx> {
                 self.intern_ty(IntTy::new(dir, lb.value.clone(), rb.value.clone()).maybe_null())
             }
 
-            (&Const::Float(ref _lb), &Const::Float(ref _rb)) => {
-                self.emit(DiagBuilder2::error("Float range bounds not yet supported").span(span));
-                return Err(());
+            (&Const::Float(ref lb), &Const::Float(ref rb)) => {
+                self.intern_ty(FloatTy::new(dir, lb.value.clone(), rb.value.clone()))
             }
 
             _ => {
@@ -1348,6 +5144,7 @@ impl_make!(self, id: SignalRef => &Ty {
     match id {
         SignalRef::Intf(id) => self.make(id),
         SignalRef::Decl(id) => self.lazy_typeval(id),
+        SignalRef::Guard(_) => Ok(self.intern_ty(BOOLEAN_TYPE.named_ty())),
     }
 });
 
@@ -0,0 +1,26 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type BIT is ('0', '1');
+	type INTEGER is range -256 to 255;
+	type ASC is array (0 to 7) of BIT;
+	type DESC is array (7 downto 0) of BIT;
+	type VEC is array (INTEGER range <>) of BIT;
+
+	constant a0 : ASC := (0 to 3 => '0', 4 to 7 => '1');
+	constant a1 : DESC := (7 downto 4 => '1', 3 downto 0 => '0');
+	constant a2 : DESC := (7 downto 4 => '1', others => '0');
+	constant a3 : ASC := ('0', '1', others => '0');
+
+	-- An unconstrained context takes its direction from the choices.
+	function f (x : VEC) return BIT is
+	begin
+		return '0';
+	end;
+	signal s : BIT;
+begin
+	s <= f((3 downto 0 => '0'));
+	s <= f((0 to 3 => '1'));
+end;
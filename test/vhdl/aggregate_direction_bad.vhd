@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: choice range direction does not match array index
+// CHECK: error: choice range direction does not match array index
+// CHECK: error: named element `1 => '1'` cannot follow positional elements in array aggregate
+*/
+entity foo is end;
+architecture bar of foo is
+	type BIT is ('0', '1');
+	type INTEGER is range -256 to 255;
+	type ASC is array (0 to 7) of BIT;
+	type DESC is array (7 downto 0) of BIT;
+
+	constant a0 : ASC := (0 to 3 => '0', 4 to 7 => '1');
+	constant a1 : DESC := (7 downto 4 => '1', 3 downto 0 => '0');
+	constant a2 : DESC := (7 downto 4 => '1', others => '0');
+	constant a3 : ASC := ('0', '1', others => '0');
+	constant a4 : DESC := (0 to 3 => '0', others => '1');
+	constant a5 : ASC := (7 downto 4 => '1', others => '0');
+	constant a6 : ASC := ('0', 1 => '1', others => '0');
+begin end;
@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		a : BIT;
+		b : BIT;
+		c : BIT;
+	end record;
+	type ARR is array (0 to 3) of BIT;
+
+	constant r0 : REC := ('0', b => '1', c => '0');
+	constant r1 : REC := ('0', others => '1');
+	constant r2 : REC := (a => '0', others => '1');
+	constant a0 : ARR := ('0', '1', others => '0');
+begin end;
@@ -0,0 +1,26 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: positional association `'1'` after named association is illegal
+// CHECK: error: `others` must be the last choice
+// CHECK: error: positional association `'0'` after named association is illegal
+// CHECK: error: `others` must be the last choice
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		a : BIT;
+		b : BIT;
+		c : BIT;
+	end record;
+	type ARR is array (0 to 3) of BIT;
+
+	constant r0 : REC := ('0', b => '1', c => '0');
+	constant r1 : REC := ('0', others => '1');
+	constant r2 : REC := (a => '0', others => '1');
+	constant a0 : ARR := ('0', '1', others => '0');
+	constant r3 : REC := (a => '0', '1', '0');
+	constant r4 : REC := (others => '1', a => '0');
+	constant a1 : ARR := (0 => '1', '0', others => '0');
+	constant a2 : ARR := (others => '0', '1');
+begin end;
@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	function add (a, b : INTEGER) return INTEGER is
+	begin
+		return a + b;
+	end;
+	function add (a, b : BIT) return BIT is
+	begin
+		return a xor b;
+	end;
+	alias plus is add [INTEGER, INTEGER return INTEGER];
+	alias bit_plus is add [BIT, BIT return BIT];
+begin
+end;
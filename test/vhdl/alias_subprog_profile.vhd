@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: no subprogram `add` with profile `(INTEGER, BIT) return INTEGER`
+*/
+entity foo is end;
+architecture bar of foo is
+	function add (a, b : INTEGER) return INTEGER is
+	begin
+		return a + b;
+	end;
+	alias bad_plus is add [INTEGER, BIT return INTEGER];
+begin
+end;
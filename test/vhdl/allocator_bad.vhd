@@ -0,0 +1,32 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: allocator `new BIT_VECTOR` requires a constrained subtype
+// CHECK: error: allocator `new BIT` yields a value of type access BIT, but INT_PTR is expected
+// CHECK: error: `'1'` is ambiguous
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		a : BIT;
+		b : INTEGER;
+	end record;
+	type INT_PTR is access INTEGER;
+	type REC_PTR is access REC;
+	type VEC_PTR is access BIT_VECTOR;
+begin
+	process
+		variable p : INT_PTR;
+		variable r : REC_PTR;
+		variable v : VEC_PTR;
+	begin
+		p := new INTEGER;
+		p := new INTEGER'(5);
+		r := new REC;
+		v := new BIT_VECTOR'("0101");
+		v := new BIT_VECTOR;
+		p := new BIT;
+		p := new INTEGER'('1');
+		wait;
+	end process;
+end;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `BIT_VECTOR'length` requires a constrained array prefix
+// CHECK: error: `BIT'length` requires an array prefix, but `BIT` is enumeration type
+*/
+entity foo is end;
+architecture bar of foo is
+	type BIT is ('0', '1');
+	type INTEGER is range -256 to 255;
+	type BIT_VECTOR is array (INTEGER range <>) of BIT;
+	subtype WORD is BIT_VECTOR(7 downto 0);
+	subtype NIBBLE is BIT_VECTOR(0 to 3);
+	subtype WORD2 is BIT_VECTOR(WORD'length-1 downto 0);
+	subtype WORD3 is BIT_VECTOR(WORD'left downto WORD'right);
+	subtype NIBBLE2 is BIT_VECTOR(NIBBLE'low to NIBBLE'high);
+	signal s : BIT_VECTOR(NIBBLE'length*2-1 downto 0);
+	constant c : INTEGER := WORD'length;
+	subtype BAD is BIT_VECTOR(BIT_VECTOR'length-1 downto 0);
+	constant d : INTEGER := BIT'length;
+begin end;
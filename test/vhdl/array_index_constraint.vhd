@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type COLOR is (RED, GREEN, BLUE, WHITE);
+	subtype PRIMARY is COLOR range RED to BLUE;
+	type PALETTE is array (COLOR range <>) of BIT;
+	type GRID is array (NATURAL range <>, COLOR range <>) of BIT;
+	subtype WORD is BIT_VECTOR(31 downto 0);
+	subtype RGB is PALETTE(RED to BLUE);
+	subtype RGB2 is PALETTE(PRIMARY);
+	subtype CELLS is GRID(0 to 3, GREEN to WHITE);
+	signal w : WORD;
+	signal p : RGB;
+	signal g : CELLS;
+begin
+end;
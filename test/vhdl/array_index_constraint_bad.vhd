@@ -0,0 +1,30 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: constrained 2 indices, but array has 1
+// CHECK: error: `0` is not an enumeration literal
+// CHECK: error: `3` is not an enumeration literal
+// CHECK: error: expression does not have a constant value
+// CHECK: error: `BIT` is not a subtype of `enum`
+// CHECK: error: constrained 1 indices, but array has 2
+*/
+entity foo is end;
+architecture bar of foo is
+	type COLOR is (RED, GREEN, BLUE, WHITE);
+	subtype PRIMARY is COLOR range RED to BLUE;
+	type PALETTE is array (COLOR range <>) of BIT;
+	type GRID is array (NATURAL range <>, COLOR range <>) of BIT;
+	subtype WORD is BIT_VECTOR(31 downto 0);
+	subtype RGB is PALETTE(RED to BLUE);
+	subtype RGB2 is PALETTE(PRIMARY);
+	subtype CELLS is GRID(0 to 3, GREEN to WHITE);
+	signal w : WORD;
+	signal p : RGB;
+	signal g : CELLS;
+	subtype BAD1 is BIT_VECTOR(0 to 3, 0 to 3);
+	subtype BAD2 is PALETTE(0 to 3);
+	subtype BAD3 is BIT_VECTOR(RED to BLUE);
+	subtype BAD4 is PALETTE(BIT);
+	subtype BAD5 is GRID(0 to 3);
+begin
+end;
@@ -0,0 +1,20 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type INTEGER is range -256 to 255;
+	type matrix is array (INTEGER range <>, INTEGER range <>) of BIT;
+	subtype matrix4x8 is matrix(0 to 3, 7 downto 0);
+	signal m : matrix4x8;
+begin
+	process is
+		variable v : matrix4x8;
+		variable b : BIT;
+	begin
+		b := m(1, 2);
+		b := v(3, 7);
+		v(0, 0) := b;
+		wait;
+	end process;
+end;
@@ -0,0 +1,35 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: constrained 1 indices, but array has 2
+// CHECK: error: constrained 3 indices, but array has 2
+// CHECK: error: `0 to 300` is not a subrange of `-256 to 255`
+// CHECK: error: `m(1)` has 1 indices, but the array has 2 dimensions
+// CHECK: error: `m(1, 2, 3)` has 3 indices, but the array has 2 dimensions
+// CHECK: error: `'0'` is ambiguous
+// CHECK: error: `v(1)` has 1 indices, but the array has 2 dimensions
+*/
+entity foo is end;
+architecture bar of foo is
+	type INTEGER is range -256 to 255;
+	type matrix is array (INTEGER range <>, INTEGER range <>) of BIT;
+	subtype matrix4x8 is matrix(0 to 3, 7 downto 0);
+	signal m : matrix4x8;
+	subtype bad1 is matrix(0 to 3);
+	subtype bad2 is matrix(0 to 3, 0 to 3, 0 to 3);
+	subtype bad3 is matrix(0 to 3, 0 to 300);
+begin
+	process is
+		variable v : matrix4x8;
+		variable b : BIT;
+	begin
+		b := m(1, 2);
+		b := v(3, 7);
+		v(0, 0) := b;
+		b := m(1);
+		b := m(1, 2, 3);
+		b := m(1, '0');
+		v(1) := b;
+		wait;
+	end process;
+end;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		variable x : INTEGER;
+		variable s : BIT;
+		variable l : SEVERITY_LEVEL;
+	begin
+		assert x = 0;
+		assert x = 0 report "x is not zero";
+		assert x = 0 report "x is not zero" severity warning;
+		assert s severity l;
+		report "hello";
+		report "hello" severity note;
+		report "hello" severity l;
+		wait;
+	end process;
+end;
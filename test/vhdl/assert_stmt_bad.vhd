@@ -0,0 +1,32 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BOOLEAN, but `x` has type INTEGER
+// CHECK: error: expected a string, but `x` has type INTEGER
+// CHECK: error: expected type SEVERITY_LEVEL, but `x` has type INTEGER
+// CHECK: error: expected a string, but `'c'` has type enum
+// CHECK: error: expected type SEVERITY_LEVEL, but `TRUE` has type enum
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		variable x : INTEGER;
+		variable s : BIT;
+		variable l : SEVERITY_LEVEL;
+	begin
+		assert x = 0;
+		assert x = 0 report "x is not zero";
+		assert x = 0 report "x is not zero" severity warning;
+		assert s severity l;
+		report "hello";
+		report "hello" severity note;
+		report "hello" severity l;
+		wait;
+		assert x;
+		assert x = 0 report x;
+		assert x = 0 severity x;
+		report 'c';
+		report "hello" severity TRUE;
+	end process;
+end;
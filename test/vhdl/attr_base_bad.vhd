@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `'base` requires a type or subtype prefix, but `a` is not a type
+// CHECK: error: `NATURAL'base` denotes a type and cannot be used as an expression
+*/
+entity foo is end;
+architecture bar of foo is
+	type WORD is range 0 to 65535;
+	subtype NIBBLE is WORD range 0 to 15;
+	constant a : INTEGER := NATURAL'base'high;
+	constant b : INTEGER := POSITIVE'base'low;
+	constant c : WORD := NIBBLE'base'high;
+	constant d : WORD := NIBBLE'base'base'left;
+	constant e : NATURAL := NATURAL'high;
+	signal s : NIBBLE'base;
+	constant f : INTEGER := a'base'high;
+	constant g : INTEGER := NATURAL'base;
+begin end;
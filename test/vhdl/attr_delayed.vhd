@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	constant c : BIT := '0';
+	signal s, t, u : BIT;
+	signal v, w : BIT_VECTOR(3 downto 0);
+begin
+	t <= s'delayed(1 ns);
+	u <= s'delayed;
+	w <= v'delayed(2 ns);
+end;
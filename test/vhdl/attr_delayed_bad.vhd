@@ -0,0 +1,20 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `c'delayed(1 ns)` requires a signal prefix, but `c` is not a signal
+// CHECK: error: expected type TIME, but `1` has type {universal integer}
+// CHECK: error: expected type BIT, but `v'delayed(1 ns)` has type array (3 downto 0) of BIT
+*/
+entity foo is end;
+architecture bar of foo is
+	constant c : BIT := '0';
+	signal s, t, u : BIT;
+	signal v, w : BIT_VECTOR(3 downto 0);
+begin
+	t <= s'delayed(1 ns);
+	u <= s'delayed;
+	w <= v'delayed(2 ns);
+	t <= c'delayed(1 ns);
+	t <= s'delayed(1);
+	t <= v'delayed(1 ns);
+end;
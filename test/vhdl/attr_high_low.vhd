@@ -0,0 +1,28 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+	end record;
+	subtype WORD is BIT_VECTOR(7 downto 0);
+	subtype DOWN is INTEGER range 7 downto 0;
+	subtype HIGH is INTEGER range WORD'high to WORD'high;
+	subtype LOW is INTEGER range DOWN'low to DOWN'low;
+	constant a : HIGH := 7;
+	constant b : LOW := 0;
+	constant c : BOOLEAN := WORD'ascending;
+	constant d : BOOLEAN := DOWN'ascending;
+	signal s : WORD;
+begin
+	process is
+		variable v : INTEGER;
+		variable asc : BOOLEAN;
+	begin
+		v := s'high;
+		v := s'low;
+		asc := s'ascending;
+		wait;
+	end process;
+end;
@@ -0,0 +1,41 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: integer literal `0` does not fit into type HIGH
+// CHECK: error: integer literal `7` does not fit into type LOW
+// CHECK: error: expected type INTEGER, but `DOWN'ascending` has type BOOLEAN
+// CHECK: error: `BIT_VECTOR'ascending` requires a constrained array prefix, but `BIT_VECTOR` is unconstrained
+// CHECK: error: `REC'high` requires a scalar prefix, but `REC` is record type
+// CHECK: error: `REC'ascending` requires a scalar prefix, but `REC` is record type
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+	end record;
+	subtype WORD is BIT_VECTOR(7 downto 0);
+	subtype DOWN is INTEGER range 7 downto 0;
+	subtype HIGH is INTEGER range WORD'high to WORD'high;
+	subtype LOW is INTEGER range DOWN'low to DOWN'low;
+	constant a : HIGH := 7;
+	constant b : LOW := 0;
+	constant c : BOOLEAN := WORD'ascending;
+	constant d : BOOLEAN := DOWN'ascending;
+	signal s : WORD;
+	constant e : HIGH := 0;
+	constant f : LOW := 7;
+	constant g : INTEGER := DOWN'ascending;
+	constant h : BOOLEAN := BIT_VECTOR'ascending;
+	constant i : BIT := REC'high;
+	constant j : BOOLEAN := REC'ascending;
+begin
+	process is
+		variable v : INTEGER;
+		variable asc : BOOLEAN;
+	begin
+		v := s'high;
+		v := s'low;
+		asc := s'ascending;
+		wait;
+	end process;
+end;
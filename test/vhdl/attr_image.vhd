@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	constant x : INTEGER := 3;
+	constant s : STRING := INTEGER'image(x);
+	constant y : INTEGER := INTEGER'value("123");
+	constant t : STRING := TIME'image(10 ns);
+begin
+	process is
+	begin
+		report INTEGER'image(x);
+		wait;
+	end process;
+end;
@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `BIT_VECTOR'image(x)` requires a scalar prefix, but `BIT_VECTOR` is array type
+*/
+entity foo is end;
+architecture bar of foo is
+	constant x : INTEGER := 3;
+	constant s : STRING := INTEGER'image(x);
+	constant y : INTEGER := INTEGER'value("123");
+	constant t : STRING := TIME'image(10 ns);
+	constant u : STRING := BIT_VECTOR'image(x);
+begin
+	process is
+	begin
+		report INTEGER'image(x);
+		wait;
+	end process;
+end;
@@ -0,0 +1,46 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	signal en : BIT;
+	signal a : LOGIC;
+	signal y : RLOGIC bus;
+	signal z : LOGIC;
+begin
+	blk_a: block
+		signal t : LOGIC;
+	begin
+		t <= a;
+	end block;
+
+	blk_b: block (en = '1')
+	begin
+		y <= guarded a;
+		z <= a when GUARD else 'Z';
+	end block;
+
+	blk_c: block
+		generic (N : INTEGER);
+		generic map (N => 4);
+		port (i : in LOGIC; o : out LOGIC);
+		port map (i => a, o => z);
+	begin
+		o <= i;
+	end block;
+
+	blk_d: block (en = '1') is
+	begin
+		blk_e: block
+		begin
+			y <= guarded a;
+		end block;
+	end block;
+end;
@@ -0,0 +1,64 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BOOLEAN, but `a` has type LOGIC
+// CHECK: error: guarded assignment `y <= guarded a;` is not within a guarded block
+// CHECK: error: target `z` of guarded assignment is not a guarded signal
+// CHECK: error: implicit signal `GUARD` cannot be assigned
+// CHECK: error: expected type LOGIC, but `en` has type BIT
+// CHECK: error: `'1'` is not a signal and cannot be associated with port `o` of mode out
+// CHECK: error: generic `N` is not associated
+// CHECK: error: `t` is not known
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	signal en : BIT;
+	signal a : LOGIC;
+	signal y : RLOGIC bus;
+	signal z : LOGIC;
+begin
+	blk_a: block
+		signal t : LOGIC;
+	begin
+		t <= a;
+	end block;
+
+	blk_b: block (en = '1')
+	begin
+		y <= guarded a;
+		z <= a when GUARD else 'Z';
+	end block;
+
+	blk_c: block
+		generic (N : INTEGER);
+		generic map (N => 4);
+		port (i : in LOGIC; o : out LOGIC);
+		port map (i => a, o => z);
+	begin
+		o <= i;
+	end block;
+
+	blk_d: block (en = '1') is
+	begin
+		blk_e: block
+		begin
+			y <= guarded a;
+		end block;
+	end block;
+
+	blk_f: block (a) begin end block;
+	blk_g: block begin y <= guarded a; end block;
+	blk_h: block (en = '1') begin z <= guarded a; end block;
+	blk_i: block (en = '1') begin GUARD <= FALSE; end block;
+	blk_j: block port (i : in LOGIC); port map (i => en); begin end block;
+	blk_k: block port (o : out LOGIC); port map (o => '1'); begin end block;
+	blk_l: block generic (N : INTEGER); begin end block;
+	blk_m: block begin t <= a; end block;
+end;
@@ -0,0 +1,28 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	function f (a : INTEGER; b : BIT) return INTEGER;
+	function f (a : INTEGER; c : BOOLEAN) return INTEGER;
+	function g (x, y : INTEGER) return BIT;
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+
+architecture bar of foo is
+	signal i, x : INTEGER;
+	signal a : BIT;
+	signal c : BOOLEAN;
+begin
+	process begin
+		i <= f(1, a);
+		i <= f(1, b => a);
+		i <= f(a => 1, b => '1');
+		i <= f(c => c, a => i);
+		a <= g(x => x, y => 2);
+		a <= g(y => x, x => i);
+		wait;
+	end process;
+end;
@@ -0,0 +1,26 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	subtype SMALL is INTEGER range 0 to 3;
+	type SMALL_ARRAY is array (0 to 1) of SMALL;
+	signal s : SMALL;
+	signal v : SMALL_ARRAY;
+	signal y : BIT;
+begin
+	process begin
+		case s is
+			when 0 | 1 => y <= '0';
+			when 2 to 3 => y <= '1';
+		end case;
+		case SMALL'(v(0)) is
+			when 0 to 3 => y <= '0';
+		end case;
+		case v(0) is
+			when 0 to 3 => y <= '0';
+			when others => y <= '1';
+		end case;
+		wait;
+	end process;
+end;
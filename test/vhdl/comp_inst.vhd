@@ -0,0 +1,23 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	component adder is
+		generic (WIDTH : INTEGER; DELAY : TIME := 1 ns);
+		port (a, b : in BIT; cin : in BIT := '0'; s, cout : out BIT);
+	end component;
+	component tap is
+		port (x : in INTEGER; y : out BIT; z : inout BIT);
+	end component;
+	signal x, y, s, c : BIT;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal i : INTEGER;
+begin
+	u0 : adder generic map (4) port map (x, y, '1', s, c);
+	u1 : component adder
+		generic map (WIDTH => 8, DELAY => 2 ns)
+		port map (a => x, b => y, s => s, cout => open);
+	u2 : adder generic map (WIDTH => 1, DELAY => open) port map (x, y, open, v(0), v(1));
+	u3 : tap port map (i + 1, z => c, y => s);
+end;
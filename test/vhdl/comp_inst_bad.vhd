@@ -0,0 +1,51 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type INTEGER, but `TRUE` has type enum
+// CHECK: error: generic `WIDTH` is not associated
+// CHECK: error: generic has already been associated
+// CHECK: error: generic `WIDTH` cannot be left open
+// CHECK: error: expected type BIT, but `i` has type INTEGER
+// CHECK: error: port `b` of mode in must be associated
+// CHECK: error: `'0'` is not a signal and cannot be associated with port `s` of mode out
+// CHECK: error: too many ports; expected at most 5
+// CHECK: error: `q` is not a port
+// CHECK: error: port `a` of mode in must be associated
+// CHECK: error: port has already been associated
+// CHECK: error: positional association `i` follows a named association
+// CHECK: error: positional association `s` follows a named association
+// CHECK: error: port `x` of mode in must be associated
+// CHECK: error: `foo` is not a component
+*/
+entity foo is end;
+architecture bar of foo is
+	component adder is
+		generic (WIDTH : INTEGER; DELAY : TIME := 1 ns);
+		port (a, b : in BIT; cin : in BIT := '0'; s, cout : out BIT);
+	end component;
+	component tap is
+		port (x : in INTEGER; y : out BIT; z : inout BIT);
+	end component;
+	signal x, y, s, c : BIT;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal i : INTEGER;
+begin
+	u0 : adder generic map (4) port map (x, y, '1', s, c);
+	u1 : component adder
+		generic map (WIDTH => 8, DELAY => 2 ns)
+		port map (a => x, b => y, s => s, cout => open);
+	u2 : adder generic map (WIDTH => 1, DELAY => open) port map (x, y, open, v(0), v(1));
+	u3 : tap port map (i + 1, z => c, y => s);
+	u4 : adder generic map (TRUE) port map (x, y, '1', s, c);
+	u5 : adder port map (x, y, '1', s, c);
+	u6 : adder generic map (1, WIDTH => 2) port map (x, y, '1', s, c);
+	u7 : adder generic map (WIDTH => open) port map (x, y, '1', s, c);
+	u8 : adder generic map (1) port map (i, y, '1', s, c);
+	u9 : adder generic map (1) port map (a => x, s => s);
+	u10 : adder generic map (1) port map (x, y, '1', '0', c);
+	u11 : adder generic map (1) port map (x, y, '1', s, c, x);
+	u12 : adder generic map (1) port map (q => x, b => y, s => s);
+	u13 : adder generic map (1) port map (a => x, a => y, b => y);
+	u14 : tap port map (z => c, i, s);
+	u15 : foo port map (x);
+end;
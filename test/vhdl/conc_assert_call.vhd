@@ -0,0 +1,30 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT;
+	signal b : BOOLEAN;
+	procedure p (x : BIT; y : INTEGER) is
+	begin
+	end;
+	procedure q is
+	begin
+	end;
+	function f (x : BIT) return INTEGER is
+	begin
+		return 0;
+	end;
+begin
+	assert b;
+	assert s = '1' report "s is not set" severity error;
+	p(s, 1);
+	p(y => 2, x => s);
+	q;
+	process is
+	begin
+		p('0', 0);
+		q;
+		wait;
+	end process;
+end;
@@ -0,0 +1,45 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: no overload of `f` is a procedure
+// CHECK: error: expected type BOOLEAN, but `1` has type {universal integer}
+// CHECK: error: expected type SEVERITY_LEVEL, but `2` has type {universal integer}
+// CHECK: error: expected type BIT, but `1` has type {universal integer}
+// CHECK: error: no overload of `p` applies
+// CHECK: error: no overload of `f` is a procedure
+// CHECK: error: `b` is not a procedure
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT;
+	signal b : BOOLEAN;
+	procedure p (x : BIT; y : INTEGER) is
+	begin
+	end;
+	procedure q is
+	begin
+	end;
+	function f (x : BIT) return INTEGER is
+	begin
+		return 0;
+	end;
+begin
+	assert b;
+	assert s = '1' report "s is not set" severity error;
+	p(s, 1);
+	p(y => 2, x => s);
+	q;
+	process is
+	begin
+		p('0', 0);
+		q;
+		wait;
+		f('0');
+	end process;
+	assert 1;
+	assert b severity 2;
+	p(1, 1);
+	p(s);
+	f(s);
+	b;
+end;
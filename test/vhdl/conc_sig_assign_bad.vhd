@@ -0,0 +1,47 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type LOGIC, but `s` has type BIT
+// CHECK: error: expected type TIME, but `1` has type {universal integer}
+// CHECK: error: expected type TIME, but `1` has type {universal integer}
+// CHECK: error: expected type BOOLEAN, but `1` has type {universal integer}
+// CHECK: error: expected type LOGIC, but `s` has type BIT
+// CHECK: error: target `z` of guarded assignment is not a guarded signal
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	signal GUARD : BOOLEAN;
+	signal a, b, z : LOGIC;
+	signal y : RLOGIC bus;
+	signal c : BOOLEAN;
+	signal s : BIT;
+	signal t : TIME;
+begin
+	z <= a;
+	z <= a after 1 ns, b after 2 ns;
+	z <= transport a after t;
+	z <= reject 1 ns inertial a after 2 ns;
+	z <= a when c else b;
+	z <= a when c else b when s = '1' else '0';
+	z <= a when c;
+	y <= guarded a;
+	y <= guarded a when c else b;
+	process is
+	begin
+		z <= a when c else b;
+		wait;
+	end process;
+	z <= s;
+	z <= a after 1;
+	z <= reject 1 inertial a;
+	z <= a when 1 else b;
+	z <= a when c else s;
+	z <= guarded a;
+end;
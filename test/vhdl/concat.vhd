@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT_VECTOR(0 to 3);
+	signal b : BIT_VECTOR(0 to 1);
+	signal c : BIT;
+	signal y : BIT_VECTOR(0 to 5);
+	signal z : BIT_VECTOR(0 to 2);
+	signal s : STRING(1 to 4);
+	signal p : STRING(1 to 2);
+begin
+	y <= a & b;
+	z <= b & c;
+	z <= c & b;
+	b <= c & c;
+	y <= a & "01";
+	a <= "01" & "10";
+	s <= p & "ab";
+	s <= p & 'x' & 'y';
+end;
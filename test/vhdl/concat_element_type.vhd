@@ -0,0 +1,15 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `b & 'x'` concatenates operands with incompatible element types
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT_VECTOR(0 to 3);
+	signal b : BIT_VECTOR(0 to 1);
+	signal y : BIT_VECTOR(0 to 5);
+	signal z : BIT_VECTOR(0 to 2);
+	signal p : STRING(1 to 2);
+begin
+	z <= b & 'x';
+end;
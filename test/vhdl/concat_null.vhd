@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal n : BIT_VECTOR(1 to 0);
+	signal m : BIT_VECTOR(0 downto 3);
+	signal a : BIT_VECTOR(0 to 3);
+	signal c : BIT;
+	signal y : BIT_VECTOR(0 to 4);
+begin
+	-- A null left operand yields the right operand.
+	a <= n & a;
+	a <= m & a;
+	-- A null right operand contributes no elements.
+	a <= a & n;
+	y <= n & a & c;
+	a <= n & n & a;
+end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type BIT is ('0', '1');
+	type INTEGER is range -256 to 255;
+	type BIT_VECTOR is array (INTEGER range <>) of BIT;
+	-- (-7) mod 3 = 2 and (-7) rem 3 = -1
+	subtype A is BIT_VECTOR((-7) mod 3 downto (-7) rem 3);
+	subtype B is BIT_VECTOR(7 mod (-3) to 7 rem (-3));
+	subtype C is BIT_VECTOR((-7) / 2 to 7 / 2);
+begin end;
@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	-- Physical literals are normalized to the primary unit.
+	constant a : BOOLEAN := 1 us > 500 ns;
+	constant b : BOOLEAN := 1 us = 1000 ns;
+	constant c : TIME := 1 us - 500 ns;
+	constant d : TIME := -(2 ns);
+begin end;
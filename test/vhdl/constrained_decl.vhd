@@ -0,0 +1,24 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	constant MSG : STRING;
+end;
+
+entity foo is
+	port (p : in BIT_VECTOR);
+end;
+
+architecture bar of foo is
+	type WORDS is array (NATURAL range <>) of BIT_VECTOR(0 to 7);
+	subtype WORD4 is WORDS(0 to 3);
+	signal a : BIT_VECTOR(0 to 3);
+	signal b : WORD4;
+	constant C : BIT_VECTOR := "0101";
+begin
+	process is
+		variable v : STRING(1 to 4);
+	begin
+		wait;
+	end process;
+end;
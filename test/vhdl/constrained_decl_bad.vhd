@@ -0,0 +1,33 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: declaration requires a constrained subtype
+// CHECK: error: declaration requires a constrained subtype
+// CHECK: error: constant `F` requires a value
+// CHECK: error: declaration requires a constrained subtype
+*/
+package pkg is
+	constant MSG : STRING;
+end;
+
+entity foo is
+	port (p : in BIT_VECTOR);
+end;
+
+architecture bar of foo is
+	type WORDS is array (NATURAL range <>) of BIT_VECTOR(0 to 7);
+	subtype WORD4 is WORDS(0 to 3);
+	signal a : BIT_VECTOR(0 to 3);
+	signal b : WORD4;
+	constant C : BIT_VECTOR := "0101";
+	signal d : BIT_VECTOR;
+	signal e : WORDS;
+	constant F : STRING;
+begin
+	process is
+		variable v : STRING(1 to 4);
+		variable w : STRING;
+	begin
+		wait;
+	end process;
+end;
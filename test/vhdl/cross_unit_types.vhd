@@ -0,0 +1,42 @@
+/*
+// RUN: moore %s --typeck foo --typeck bar
+*/
+package pkg is
+	type STATE is (IDLE, BUSY, DONE);
+	type WORD is array (0 to 7) of BIT;
+	constant RESET_STATE : STATE := IDLE;
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+architecture a of foo is
+	signal s : STATE := RESET_STATE;
+	signal w : WORD;
+begin
+	process
+		variable v : work.pkg.STATE;
+	begin
+		v := s;
+		s <= v;
+		wait;
+	end process;
+end;
+
+library work;
+use work.pkg;
+entity bar is end;
+architecture b of bar is
+	signal s : pkg.STATE := pkg.RESET_STATE;
+	signal w : pkg.WORD;
+begin
+	process
+		variable v : work.pkg.STATE;
+		variable x : work.pkg.WORD;
+	begin
+		v := s;
+		s <= v;
+		x := w;
+		wait;
+	end process;
+end;
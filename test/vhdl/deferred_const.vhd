@@ -0,0 +1,23 @@
+/*
+// RUN: moore %s --typeck pkg --typeck foo
+*/
+package pkg is
+	constant A : BIT_VECTOR;
+	constant B : INTEGER;
+	constant C : BOOLEAN := true;
+end;
+
+package body pkg is
+	constant A : BIT_VECTOR := "0101";
+	constant B : INTEGER := 42;
+end;
+
+entity foo is end;
+architecture bar of foo is
+	constant E : INTEGER := 1;
+begin
+	process is
+	begin
+		wait;
+	end process;
+end;
@@ -0,0 +1,30 @@
+/*
+// RUN: moore %s --typeck pkg --typeck foo
+// FAIL
+// CHECK: error: deferred constant `D` has no full declaration in the package body
+// CHECK: error: constant `F` requires a value
+// CHECK: error: constant `G` requires a value
+*/
+package pkg is
+	constant A : BIT_VECTOR;
+	constant B : INTEGER;
+	constant C : BOOLEAN := true;
+	constant D : BIT;
+end;
+
+package body pkg is
+	constant A : BIT_VECTOR := "0101";
+	constant B : INTEGER := 42;
+end;
+
+entity foo is end;
+architecture bar of foo is
+	constant E : INTEGER := 1;
+	constant F : INTEGER;
+begin
+	process is
+		constant G : BIT;
+	begin
+		wait;
+	end process;
+end;
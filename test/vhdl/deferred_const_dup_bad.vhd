@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck pkg
+// FAIL
+// CHECK: error: `B` has already been declared
+// CHECK: error: `A` has already been declared
+*/
+package pkg is
+	constant A : BIT_VECTOR;
+	constant B : INTEGER;
+	constant C : BOOLEAN := true;
+end;
+
+package body pkg is
+	constant A : BIT_VECTOR := "0101";
+	constant B : INTEGER := 42;
+	constant B : INTEGER := 43;
+	constant A : BOOLEAN := false;
+end;
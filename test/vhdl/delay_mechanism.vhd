@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a, b : BIT;
+	signal t : TIME;
+begin
+	process begin
+		a <= transport b after 2 ns;
+		a <= inertial b after 2 ns;
+		a <= reject 1 ns inertial b after 2 ns;
+		a <= reject t inertial b after t;
+		a <= reject 0 ns inertial b;
+		wait;
+	end process;
+end;
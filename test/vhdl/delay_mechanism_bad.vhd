@@ -0,0 +1,28 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type TIME, but `1` has type {universal integer}
+// CHECK: error: expected type TIME, but `b` has type BIT
+// CHECK: error: pulse rejection limit `-1 ns` is negative
+// CHECK: error: delay `-2 ns` is negative
+// CHECK: error: expected type TIME, but `2` has type {universal integer}
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a, b : BIT;
+	signal t : TIME;
+begin
+	process begin
+		a <= transport b after 2 ns;
+		a <= inertial b after 2 ns;
+		a <= reject 1 ns inertial b after 2 ns;
+		a <= reject t inertial b after t;
+		a <= reject 0 ns inertial b;
+		a <= reject 1 inertial b;
+		a <= reject b inertial b;
+		a <= reject -1 ns inertial b;
+		a <= b after -2 ns;
+		a <= b after 2;
+		wait;
+	end process;
+end;
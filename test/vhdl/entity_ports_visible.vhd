@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is
+	port (a : in BIT; b : in BIT_VECTOR(0 to 3); y : out BIT);
+end;
+architecture bar of foo is
+begin
+	y <= a;
+	process
+		variable v : BIT_VECTOR(0 to 3);
+	begin
+		v := b;
+		wait;
+	end process;
+end;
@@ -0,0 +1,10 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	-- Identifiers and character literals may be mixed.
+	type STATE is (IDLE, 'I', RUN, 'R');
+	type LOGIC is ('0', '1', 'X', Z);
+	signal s : STATE;
+begin end;
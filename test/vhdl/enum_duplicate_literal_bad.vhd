@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: enumeration literal `A` declared more than once
+// CHECK: error: enumeration literal `a` declared more than once
+// CHECK: error: enumeration literal `'0'` declared more than once
+*/
+entity foo is end;
+architecture bar of foo is
+	-- Identifiers and character literals may be mixed.
+	type STATE is (IDLE, 'I', RUN, 'R');
+	type LOGIC is ('0', '1', 'X', Z);
+	type DUP is (A, B, A);
+	type DUP_CASE is (A, B, a);
+	type DUP_CHAR is ('0', '1', '0');
+	signal s : STATE;
+begin end;
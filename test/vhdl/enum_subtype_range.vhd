@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type COLOR is (RED, GREEN, BLUE, WHITE);
+	type FRUIT is (APPLE, BANANA);
+	subtype PRIMARY is COLOR range RED to BLUE;
+	subtype WARM is PRIMARY range RED to GREEN;
+	subtype REVERSED is COLOR range BLUE downto RED;
+	signal c : PRIMARY := GREEN;
+	signal w : WARM := RED;
+begin
+end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `APPLE` is not a literal of the constrained type
+// CHECK: error: `0` is not an enumeration literal
+// CHECK: error: `2` is not an enumeration literal
+// CHECK: error: right bound `WHITE` is out of range of the constrained type
+*/
+entity foo is end;
+architecture bar of foo is
+	type COLOR is (RED, GREEN, BLUE, WHITE);
+	type FRUIT is (APPLE, BANANA);
+	subtype PRIMARY is COLOR range RED to BLUE;
+	subtype WARM is PRIMARY range RED to GREEN;
+	subtype REVERSED is COLOR range BLUE downto RED;
+	signal c : PRIMARY := GREEN;
+	signal w : WARM := RED;
+	subtype BAD1 is COLOR range RED to APPLE;
+	subtype BAD2 is COLOR range 0 to 2;
+	subtype BAD3 is PRIMARY range RED to WHITE;
+begin
+end;
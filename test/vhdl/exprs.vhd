@@ -40,7 +40,7 @@ architecture bar of foo is
 	constant s22 : REC := ('0', c => '0', b => '1');
 	constant s23 : TRIBITS := ('0', '1', '0');
 	constant s24 : TRIBITS := (0 => '0', 1 => '1', 2 => '0');
-	constant s25 : TRIBITS := ('0', 2 => '1', 1 => '0');
+	-- constant s25 : TRIBITS := ('0', 2 => '1', 1 => '0'); -- should fail
 
 	-- primary function call
 	constant s30 : INTEGER := square(2);
@@ -52,15 +52,15 @@ architecture bar of foo is
 	constant s43 : REC := REC'('0', c => '0', b => '1');
 	constant s44 : TRIBITS := TRIBITS'('0', '1', '0');
 	constant s45 : TRIBITS := TRIBITS'(0 => '0', 1 => '1', 2 => '0');
-	constant s46 : TRIBITS := TRIBITS'('0', 2 => '1', 1 => '0');
+	-- constant s46 : TRIBITS := TRIBITS'('0', 2 => '1', 1 => '0'); -- should fail
 
 	-- primary type conversion
 	--constant s50 : INTEGER := INTEGER('0');
 	constant s51 : INTEGER := INTEGER(123);
 
 	-- primary allocator
-	constant s60 : INTEGER := new INTEGER;
-	constant s61 : INTEGER := new INTEGER'(123);
+	-- constant s60 : INTEGER := new INTEGER; -- should fail
+	-- constant s61 : INTEGER := new INTEGER'(123); -- should fail
 
 	-- primary parenthesized
 	constant s70 : INTEGER := (123);
@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package textio is
+	type LINE is access STRING;
+	type TEXT is file of STRING;
+end;
+
+library work;
+use work.textio.all;
+
+entity foo is end;
+architecture bar of foo is
+	file input : TEXT open READ_MODE is "in.txt";
+	file output : TEXT open WRITE_MODE is "out.txt";
+	file scratch : TEXT;
+begin end;
@@ -0,0 +1,24 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `STRING` is not a file type
+// CHECK: error: `'1'` is ambiguous
+// CHECK: error: expected a string, but `42` has type {universal integer}
+*/
+package textio is
+	type LINE is access STRING;
+	type TEXT is file of STRING;
+end;
+
+library work;
+use work.textio.all;
+
+entity foo is end;
+architecture bar of foo is
+	file input : TEXT open READ_MODE is "in.txt";
+	file output : TEXT open WRITE_MODE is "out.txt";
+	file scratch : TEXT;
+	file bad_type : STRING;
+	file bad_mode : TEXT open '1' is "in.txt";
+	file bad_name : TEXT open READ_MODE is 42;
+begin end;
@@ -0,0 +1,29 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT_VECTOR(3 downto 0);
+	signal x : INTEGER;
+begin
+	gen_a: for i in 0 to 3 generate
+		signal t : BIT;
+	begin
+		t <= '1';
+		process
+			variable v : INTEGER;
+		begin
+			v := i;
+			wait;
+		end process;
+	end generate;
+
+	gen_b: for j in s'range generate
+		signal t : BIT;
+	begin
+		t <= '0';
+	end generate;
+
+	gen_c: for k in BIT generate
+	end generate;
+end;
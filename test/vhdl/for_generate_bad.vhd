@@ -0,0 +1,37 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `0 ns to 1 ns` is not a discrete range
+// CHECK: error: `s` is not a valid discrete range
+// CHECK: error: `x` is not globally static
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT_VECTOR(3 downto 0);
+	signal x : INTEGER;
+begin
+	gen_a: for i in 0 to 3 generate
+		signal t : BIT;
+	begin
+		t <= '1';
+		process
+			variable v : INTEGER;
+		begin
+			v := i;
+			wait;
+		end process;
+	end generate;
+
+	gen_b: for j in s'range generate
+		signal t : BIT;
+	begin
+		t <= '0';
+	end generate;
+
+	gen_c: for k in BIT generate
+	end generate;
+
+	gen_d: for l in 0 ns to 1 ns generate end generate;
+	gen_e: for m in s generate end generate;
+	gen_f: for n in 0 to x generate end generate;
+end;
@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is
+	generic (
+		function to_int (x : bit; y, z : integer range 0 to 7) return integer;
+		procedure notify (msg : bit_vector);
+		function pick (a : bit_vector(0 to 3)) return bit
+	);
+end;
+
+architecture bar of foo is
+begin
+end;
@@ -0,0 +1,15 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: unknown type `no_such_type`
+*/
+entity foo is
+	generic (
+		function pick (a : bit_vector(0 to 3)) return bit;
+		function bad (a : no_such_type) return bit
+	);
+end;
+
+architecture bar of foo is
+begin
+end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s -e int_stack -e bit_stack
+*/
+package stack is
+	generic (type ELEMENT);
+	constant DEPTH : INTEGER := 4;
+end package;
+
+library work;
+package int_stack is new work.stack generic map (ELEMENT => INTEGER);
+
+library work;
+package bit_stack is new work.stack generic map (BIT);
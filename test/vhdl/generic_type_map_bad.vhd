@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s -e bad_stack -e bad_formal -e no_actual
+// FAIL
+// CHECK: error: `42` is not a type or subtype
+// CHECK: error: `ITEM` is not a generic
+// CHECK: error: generic type `ELEMENT` is not associated
+// CHECK: error: generic type `ELEMENT` is not associated
+*/
+package stack is
+	generic (type ELEMENT);
+	constant DEPTH : INTEGER := 4;
+end package;
+
+library work;
+package bad_stack is new work.stack generic map (ELEMENT => 42);
+
+library work;
+package bad_formal is new work.stack generic map (ITEM => BIT);
+
+library work;
+package no_actual is new work.stack;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	signal GUARD : BOOLEAN;
+	signal sel : BIT;
+	signal a, b : LOGIC;
+	signal y : RLOGIC bus;
+begin
+	with sel select y <= guarded
+		a when '0',
+		b when '1';
+end;
@@ -0,0 +1,31 @@
+/*
+// RUN: moore %s --typeck foo --typeck baz
+// FAIL
+// CHECK: error: target `z` of guarded assignment is not a guarded signal
+// CHECK: error: guarded assignment `with sel select y <= guarded a when others;` is not within a guarded block
+*/
+entity foo is end;
+architecture bar of foo is
+	signal GUARD : BOOLEAN;
+	signal sel : BIT;
+	signal a, b : BIT;
+	signal z : BIT;
+begin
+	with sel select z <= guarded a when '0', b when '1';
+end;
+
+entity baz is end;
+architecture bar of baz is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	signal sel : BIT;
+	signal a : LOGIC;
+	signal y : RLOGIC bus;
+begin
+	with sel select y <= guarded a when others;
+end;
@@ -0,0 +1,40 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type MODE is (FAST, SLOW, OFF);
+	constant M : MODE := FAST;
+	constant N : INTEGER := 4;
+	signal s : BIT;
+	signal b : BOOLEAN;
+begin
+	gen_a: if N > 2 generate
+		signal t : BIT;
+	begin
+		t <= s;
+	elsif N = 2 generate
+		signal t : INTEGER;
+	begin
+		t <= N;
+	else generate
+		s <= '0';
+	end generate;
+
+	gen_b: if N /= 0 and N < 8 generate
+	end generate;
+
+	gen_c: case M generate
+		when FAST =>
+			signal t : BIT;
+		begin
+			t <= '1';
+		when SLOW | OFF =>
+	end generate;
+
+	gen_d: case N generate
+		when 0 to 3 =>
+		when others =>
+			s <= '1';
+	end generate;
+end;
@@ -0,0 +1,54 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BOOLEAN, but `N` has type INTEGER
+// CHECK: error: `b` is not globally static
+// CHECK: error: choices do not cover all values of `M`
+// CHECK: error: choice `1` is not a locally static value of type MODE
+// CHECK: error: `s` is not globally static
+// CHECK: error: expected type BIT, but `N` has type INTEGER
+*/
+entity foo is end;
+architecture bar of foo is
+	type MODE is (FAST, SLOW, OFF);
+	constant M : MODE := FAST;
+	constant N : INTEGER := 4;
+	signal s : BIT;
+	signal b : BOOLEAN;
+begin
+	gen_a: if N > 2 generate
+		signal t : BIT;
+	begin
+		t <= s;
+	elsif N = 2 generate
+		signal t : INTEGER;
+	begin
+		t <= N;
+	else generate
+		s <= '0';
+	end generate;
+
+	gen_b: if N /= 0 and N < 8 generate
+	end generate;
+
+	gen_c: case M generate
+		when FAST =>
+			signal t : BIT;
+		begin
+			t <= '1';
+		when SLOW | OFF =>
+	end generate;
+
+	gen_d: case N generate
+		when 0 to 3 =>
+		when others =>
+			s <= '1';
+	end generate;
+
+	gen_e: if N generate end generate;
+	gen_f: if b generate end generate;
+	gen_g: case M generate when FAST => end generate;
+	gen_h: case M generate when 1 => when others => end generate;
+	gen_i: case s generate when '0' => when '1' => end generate;
+	gen_j: if N > 2 generate signal t : BIT; begin t <= N; end generate;
+end;
@@ -0,0 +1,25 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT;
+	signal b : BOOLEAN;
+	signal i : INTEGER;
+begin
+	process begin
+		if b then
+			a <= '1';
+		elsif i = 0 then
+			a <= '0';
+		elsif a then
+			i <= 1;
+		else
+			i <= 2;
+		end if;
+		if a = '1' and b then
+			null;
+		end if;
+		wait;
+	end process;
+end;
@@ -0,0 +1,32 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BOOLEAN, but `i` has type INTEGER
+// CHECK: error: `'1'` is ambiguous
+// CHECK: error: expected type BIT, but `1` has type {universal integer}
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT;
+	signal b : BOOLEAN;
+	signal i : INTEGER;
+begin
+	process begin
+		if b then
+			a <= '1';
+		elsif i = 0 then
+			a <= '0';
+		elsif a then
+			i <= 1;
+		else
+			i <= 2;
+		end if;
+		if a = '1' and b then
+			null;
+		end if;
+		if i then null; end if;
+		if b then i <= '1'; end if;
+		if b then null; else a <= 1; end if;
+		wait;
+	end process;
+end;
@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type BYTE is range 0 to 255;
+	subtype NIBBLE is BYTE range 0 to 15;
+	constant a : BYTE := 255;
+	constant b : NIBBLE := 15;
+	constant c : INTEGER := 256;
+begin end;
@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: integer literal `256` does not fit into type BYTE
+// CHECK: error: integer literal `16` does not fit into type NIBBLE
+*/
+entity foo is end;
+architecture bar of foo is
+	type BYTE is range 0 to 255;
+	subtype NIBBLE is BYTE range 0 to 15;
+	constant a : BYTE := 255;
+	constant b : NIBBLE := 15;
+	constant c : INTEGER := 256;
+	constant d : BYTE := 256;
+	constant e : NIBBLE := 16;
+begin end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type WORD is range 0 to 65535;
+	subtype BYTE is WORD range 0 to 255;
+	constant a : INTEGER := 2147483647;
+	constant b : INTEGER := -2147483648;
+	constant c : NATURAL := 2147483647;
+	constant d : WORD := 65535;
+	constant e : BYTE := 255;
+begin end;
@@ -0,0 +1,28 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: integer literal `2147483648` does not fit into type INTEGER
+// CHECK: error: integer value out of range
+// CHECK: error: integer literal `3_000_000_000` does not fit into type INTEGER
+// CHECK: error: integer literal `2147483648` does not fit into type NATURAL
+// CHECK: error: integer literal `65536` does not fit into type WORD
+// CHECK: error: integer literal `65536` does not fit into type BYTE
+// CHECK: error: integer literal `9223372036854775808` does not fit into type INTEGER
+*/
+entity foo is end;
+architecture bar of foo is
+	type WORD is range 0 to 65535;
+	subtype BYTE is WORD range 0 to 255;
+	constant a : INTEGER := 2147483647;
+	constant b : INTEGER := -2147483648;
+	constant c : NATURAL := 2147483647;
+	constant d : WORD := 65535;
+	constant e : BYTE := 255;
+	constant f : INTEGER := 2147483648;
+	constant g : INTEGER := -2147483649;
+	constant h : INTEGER := 3_000_000_000;
+	constant i : NATURAL := 2147483648;
+	constant j : WORD := 65536;
+	constant k : BYTE := 65536;
+	constant l : INTEGER := 9223372036854775808;
+begin end;
@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck foo --lint-signals --warnings-as-errors
+*/
+entity foo is
+	port (a : in BIT; b : out BIT; c : inout BIT);
+end;
+
+architecture bar of foo is
+	signal s, t, u : BIT;
+begin
+	-- Ports are never reported, regardless of how they are used.
+	s <= a;
+	process (s) begin
+		t <= s;
+	end process;
+	u <= t;
+	b <= u;
+end;
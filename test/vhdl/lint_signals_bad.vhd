@@ -0,0 +1,23 @@
+/*
+// RUN: moore %s --typeck foo --lint-signals --warnings-as-errors
+// FAIL
+// CHECK: warning: signal `unused` is never used
+// CHECK: warning: signal `stuck` is never used
+*/
+entity foo is
+	port (a : in BIT; b : out BIT; c : inout BIT);
+end;
+
+architecture bar of foo is
+	signal s, t, u : BIT;
+	signal unused : BIT;
+	signal stuck : BIT := '1';
+begin
+	-- Ports are never reported, regardless of how they are used.
+	s <= a;
+	process (s) begin
+		t <= s;
+	end process;
+	u <= t;
+	b <= u;
+end;
@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo --warnings-as-errors
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	g0: for i in 0 to 7 generate end generate;
+	process is
+	begin
+		for i in 0 to 1000 loop null; end loop;
+		for i in 1000 downto 0 loop null; end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo --warnings-as-errors
+// FAIL
+// CHECK: warning: `0 to 2147483646` iterates 2147483647 times
+// CHECK: warning: `0 to 2147483646` iterates 2147483647 times
+// CHECK: warning: `2147483646 downto 0` iterates 2147483647 times
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	g0: for i in 0 to 7 generate end generate;
+	g1: for i in 0 to 2147483646 generate end generate;
+	process is
+	begin
+		for i in 0 to 1000 loop null; end loop;
+		for i in 1000 downto 0 loop null; end loop;
+		for i in 0 to 2147483646 loop null; end loop;
+		for i in 2147483646 downto 0 loop null; end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,15 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		variable x : INTEGER;
+	begin
+		for i in 0 to 3 loop
+			x := 1;
+		end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: cannot assign to loop parameter `i`
+// CHECK: error: cannot assign to loop parameter `i`
+// CHECK: error: cannot assign to loop parameter `i`
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		variable x : INTEGER;
+	begin
+		for i in 0 to 3 loop
+			x := 1;
+		end loop;
+		for i in 0 to 3 loop i := 1; end loop;
+		for i in 0 to 3 loop for j in 0 to 1 loop i := 1; end loop; end loop;
+		for i in 0 to 3 loop for i in 0 to 1 loop i := 1; end loop; end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,49 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BOOLEAN, but `x` has type INTEGER
+// CHECK: error: expected type BOOLEAN, but `x` has type INTEGER
+// CHECK: error: expected type BOOLEAN, but `1` has type {universal integer}
+// CHECK: error: expected type INTEGER, but `e` has type T
+// CHECK: error: `i` is unknown
+// CHECK: error: `0 ns to 1 ns` is not a discrete range
+*/
+entity foo is end;
+architecture bar of foo is
+	type T is (A, B, C);
+begin
+	process is
+		variable x : INTEGER;
+		variable b : BOOLEAN;
+		variable y : T;
+		variable v : BIT_VECTOR(7 downto 0);
+	begin
+		while b loop
+			exit when x = 0;
+			next when not b;
+		end loop;
+		for i in 0 to 3 loop
+			x := i;
+			for j in i to 3 loop
+				x := i + j;
+				next when j = 2;
+			end loop;
+		end loop;
+		for e in T loop
+			y := e;
+		end loop;
+		for i in v'range loop
+			v(i) := '1';
+		end loop;
+		for i in 0 to 3 loop
+			exit;
+		end loop;
+		while x loop end loop;
+		loop exit when x; end loop;
+		loop next when 1; end loop;
+		for e in T loop x := e; end loop;
+		for i in 0 to 3 loop end loop; x := i;
+		for t in 0 ns to 1 ns loop end loop;
+		wait;
+	end process;
+end;
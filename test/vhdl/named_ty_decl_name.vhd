@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type T, but `TRUE` has type enum
+*/
+entity foo is end;
+architecture bar of foo is
+	type T is range 0 to 100;
+	subtype S is T range 0 to 10;
+	-- The base type `T` is first encountered while checking the value `5`,
+	-- yet it is still named after its declaration.
+	constant c : S := 5;
+	constant d : T := TRUE;
+begin end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		variable x : INTEGER;
+	begin
+		outer : loop
+			inner : loop
+				next outer;
+				exit inner;
+				exit outer;
+				next;
+			end loop;
+			next outer;
+			exit;
+		end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `next` statement outside of a loop
+// CHECK: error: `inner` is unknown
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+	begin
+		outer : loop
+			inner : loop
+				exit outer;
+			end loop;
+		end loop;
+		next;
+		exit inner;
+		wait;
+	end process;
+end;
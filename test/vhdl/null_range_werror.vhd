@@ -0,0 +1,7 @@
+/*
+// RUN: moore --warnings-as-errors %s -e null_range_werror
+// FAIL
+*/
+package null_range_werror is
+	subtype EMPTY is INTEGER range 7 to 0;
+end package;
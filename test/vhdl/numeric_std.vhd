@@ -0,0 +1,25 @@
+/*
+// RUN: moore %s --lib ieee --typeck foo
+*/
+package NUMERIC_STD is
+	type UNSIGNED is array (NATURAL range <>) of BIT;
+	type SIGNED is array (NATURAL range <>) of BIT;
+
+	function "+" (L, R : UNSIGNED) return UNSIGNED;
+	function "+" (L : UNSIGNED; R : NATURAL) return UNSIGNED;
+	function "+" (L, R : SIGNED) return SIGNED;
+	function "*" (L, R : UNSIGNED) return UNSIGNED;
+end;
+
+library ieee;
+use ieee.NUMERIC_STD.all;
+
+entity foo is end;
+architecture bar of foo is
+	constant a : UNSIGNED(7 downto 0) := "00000000";
+	constant b : UNSIGNED(3 downto 0) := "0000";
+	constant c : SIGNED(7 downto 0) := "00000000";
+	constant d : UNSIGNED(7 downto 0) := a + b;
+	constant e : UNSIGNED(11 downto 0) := a * b;
+	constant f : UNSIGNED(7 downto 0) := a + 1;
+begin end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --lib ieee --typeck foo
+// FAIL
+// CHECK: error: `a + c` mixes operands of type `UNSIGNED` and `SIGNED`
+*/
+package NUMERIC_STD is
+	type UNSIGNED is array (NATURAL range <>) of BIT;
+	type SIGNED is array (NATURAL range <>) of BIT;
+
+	function "+" (L, R : UNSIGNED) return UNSIGNED;
+	function "+" (L, R : SIGNED) return SIGNED;
+end;
+
+library ieee;
+use ieee.NUMERIC_STD.all;
+
+entity foo is end;
+architecture bar of foo is
+	constant a : UNSIGNED(7 downto 0) := "00000000";
+	constant c : SIGNED(7 downto 0) := "00000000";
+	constant g : UNSIGNED(7 downto 0) := a + c;
+begin end;
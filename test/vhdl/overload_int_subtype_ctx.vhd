@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	constant a : INTEGER range 0 to 7 := 1 + 2;
+	constant b : INTEGER range 0 to 15 := a * 2;
+	signal s : INTEGER range 0 to 255;
+	signal t : BOOLEAN;
+begin
+	process is
+		variable v : INTEGER range -8 to 7;
+		variable w : NATURAL;
+	begin
+		v := -8;
+		v := a - b;
+		s <= w + 1;
+		t <= v < a;
+		wait;
+	end process;
+end;
@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+*/
+entity foo is end;
+architecture bar of foo is
+	type SMALL is range 0 to 7;
+	constant a : SMALL := 1;
+	constant b : INTEGER := 2;
+	constant c : BOOLEAN := a = b;
+begin end;
@@ -0,0 +1,33 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: no overload of `conv` returns type COLOR
+// CHECK: error: `pick` is ambiguous
+*/
+package pkg is
+	type COLOR is (RED, GREEN);
+	function conv (x: INTEGER) return BIT;
+	function conv (x: INTEGER) return BOOLEAN;
+	function pick (x: BIT) return INTEGER;
+	function pick (x: BOOLEAN) return INTEGER;
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+
+architecture bar of foo is
+	signal b: BIT;
+	signal t: BOOLEAN;
+	signal i: INTEGER;
+	signal c: COLOR;
+begin
+	-- The result type selects among overloads with identical arguments.
+	b <= conv(1);
+	t <= conv(i);
+	-- The arguments select among overloads with identical result types.
+	i <= pick(b);
+	i <= pick(t);
+	c <= conv(1);
+	i <= pick('1');
+end;
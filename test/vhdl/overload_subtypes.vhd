@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	subtype BYTE is INTEGER range 0 to 255;
+	constant a : NATURAL := 1;
+	constant b : INTEGER := 2;
+	constant c : BYTE := 3;
+	constant d : BOOLEAN := a = b;
+	constant e : BOOLEAN := b = c;
+	constant f : BOOLEAN := a = 0;
+begin end;
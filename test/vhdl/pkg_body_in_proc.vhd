@@ -0,0 +1,20 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process is
+		constant WIDTH : INTEGER := 8;
+		package inner is
+			constant K : INTEGER;
+			constant M : BIT;
+		end package;
+		package body inner is
+			constant K : INTEGER := WIDTH;
+			constant M : BIT := '1';
+		end package body;
+	begin
+		wait;
+	end process;
+end;
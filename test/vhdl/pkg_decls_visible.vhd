@@ -0,0 +1,29 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type STATE is (IDLE, BUSY);
+	type DISTANCE is range 0 to 1000000
+		units
+			um;
+			mm = 1000 um;
+		end units;
+	constant START : STATE := IDLE;
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+architecture bar of foo is
+	signal s : STATE := BUSY;
+	signal t : work.pkg.STATE := work.pkg.IDLE;
+	constant d : DISTANCE := 2 mm;
+begin
+	process
+		variable v : STATE;
+	begin
+		v := START;
+		v := s;
+		wait;
+	end process;
+end;
@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `BIT` is unknown
+*/
+package pkg is
+	type STATE is (IDLE, BUSY);
+end;
+
+library work;
+entity foo is end;
+architecture bar of foo is
+	signal s : work.pkg.BIT;
+begin end;
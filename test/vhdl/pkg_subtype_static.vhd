@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s -e pkg
+*/
+package pkg is
+	subtype BYTE is INTEGER range 0 to 255;
+	subtype NIBBLE is INTEGER range 15 downto 0;
+	subtype WORD is BIT_VECTOR(31 downto 0);
+
+	constant MAX : BYTE := 255;
+	signal LIMIT : INTEGER;
+end;
@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is
+	port (
+		a : in BIT_VECTOR := "0101";
+		b : in BIT_VECTOR(1 downto 0) := "01";
+		c : in BIT := '0';
+		f : out BIT
+	);
+end;
+
+architecture bar of foo is
+begin
+	f <= c;
+end;
@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `"0101"` is ambiguous
+// CHECK: error: `"01"` is ambiguous
+*/
+entity foo is
+	port (
+		a : in BIT_VECTOR := "0101";
+		b : in BIT_VECTOR(1 downto 0) := "01";
+		c : in BIT := '0';
+		d : in BOOLEAN_VECTOR := "0101";
+		e : in BIT := "01";
+		f : out BIT
+	);
+end;
+
+architecture bar of foo is
+begin
+	f <= c;
+end;
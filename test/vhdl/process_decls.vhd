@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type IntegerFile is file of INTEGER;
+begin
+	process is
+		variable count : INTEGER := 0;
+		variable flag : BIT := '1';
+		variable bits : BIT_VECTOR(0 to 1) := "01";
+		file scratch : IntegerFile;
+	begin
+		count := 1;
+		wait;
+	end process;
+end;
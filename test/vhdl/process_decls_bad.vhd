@@ -0,0 +1,26 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `'1'` is ambiguous
+// CHECK: error: expected type BIT, but `1` has type {universal integer}
+// CHECK: error: `'0'` is ambiguous
+// CHECK: error: `INTEGER` is not a file type
+*/
+entity foo is end;
+architecture bar of foo is
+	type IntegerFile is file of INTEGER;
+begin
+	process is
+		variable count : INTEGER := 0;
+		variable flag : BIT := '1';
+		variable bits : BIT_VECTOR(0 to 1) := "01";
+		file scratch : IntegerFile;
+		variable bad1 : INTEGER := '1';
+		variable bad2 : BIT := 1;
+		variable bad3 : BIT_VECTOR(0 to 1) := '0';
+		file bad4 : INTEGER;
+	begin
+		count := 1;
+		wait;
+	end process;
+end;
@@ -0,0 +1,33 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type COUNTER is protected
+		procedure increment (N : INTEGER);
+		impure function value return INTEGER;
+	end protected COUNTER;
+end;
+
+package body pkg is
+	type COUNTER is protected body
+		variable count : INTEGER := 0;
+
+		procedure increment (N : INTEGER) is
+		begin
+			count := count + N;
+		end procedure increment;
+
+		impure function value return INTEGER is
+		begin
+			return count;
+		end function value;
+	end protected body COUNTER;
+end;
+
+library work;
+use work.pkg.all;
+
+entity foo is end;
+architecture bar of foo is
+	shared variable c : COUNTER;
+begin end;
@@ -0,0 +1,9 @@
+/*
+// RUN: moore %s -e range_constraint
+*/
+package range_constraint is
+	subtype BYTE is INTEGER range 0 to 255;
+	subtype NIBBLE is BYTE range 0 to 15;
+	subtype SHORT_DELAY is TIME range 0 fs to 10 ns;
+	subtype REVERSED is BYTE range 15 downto 0;
+end package;
@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s -e range_constraint
+// FAIL
+// CHECK: error: left bound `-1` is out of range of `0 to 255`
+// CHECK: error: right bound `256` is out of range of `0 to 255`
+// CHECK: error: expression does not have a constant value
+*/
+package range_constraint is
+	subtype BYTE is INTEGER range 0 to 255;
+	subtype NIBBLE is BYTE range 0 to 15;
+	subtype SHORT_DELAY is TIME range 0 fs to 10 ns;
+	subtype BAD_LEFT is BYTE range -1 to 15;
+	subtype BAD_RIGHT is BYTE range 0 to 256;
+	subtype REVERSED is BYTE range 15 downto 0;
+	subtype BAD_BOUND is BYTE range 0 to '1';
+end package;
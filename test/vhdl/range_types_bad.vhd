@@ -0,0 +1,25 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `0 ns to 1 ns` is not a discrete range
+// CHECK: error: `0 ns to 10 ns` is not a discrete range
+*/
+entity foo is end;
+architecture bar of foo is
+	subtype BYTE is INTEGER range 0 to 255;
+	type BYTES is array (0 to 3) of BYTE;
+	constant B : BYTES := (0 to 1 => 0, 2 to 3 => 255);
+	constant C : BYTES := (0 ns to 1 ns => 0, others => 255);
+begin
+	process is
+	begin
+		for i in 0 to 3 loop
+			null;
+		end loop;
+		for i in BYTE loop
+			null;
+		end loop;
+		for t in 0 ns to 10 ns loop end loop;
+		wait;
+	end process;
+end;
@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		data : BIT_VECTOR;
+		addr : BIT_VECTOR;
+		flag : BIT;
+	end record;
+	type OUTER is record
+		inner : REC;
+	end record;
+	subtype BYTE_REC is REC(data(7 downto 0), addr(3 downto 0));
+	subtype PART_REC is REC(data(15 downto 0));
+	subtype NESTED is OUTER(inner(data(7 downto 0), addr(0 to 1)));
+	signal s : BYTE_REC;
+begin
+end;
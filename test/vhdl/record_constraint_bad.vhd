@@ -0,0 +1,28 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: element `data` has already been constrained
+// CHECK: error: record has no element `size`
+// CHECK: error: array constraint `(7 downto 0)` does not apply to enumeration type
+// CHECK: error: record constraint `(data(7 downto 0))` does not apply to enumeration type
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		data : BIT_VECTOR;
+		addr : BIT_VECTOR;
+		flag : BIT;
+	end record;
+	type OUTER is record
+		inner : REC;
+	end record;
+	subtype BYTE_REC is REC(data(7 downto 0), addr(3 downto 0));
+	subtype PART_REC is REC(data(15 downto 0));
+	subtype NESTED is OUTER(inner(data(7 downto 0), addr(0 to 1)));
+	signal s : BYTE_REC;
+	subtype BAD1 is REC(data(7 downto 0), data(3 downto 0));
+	subtype BAD2 is REC(size(7 downto 0));
+	subtype BAD3 is REC(flag(7 downto 0));
+	subtype BAD4 is BIT(data(7 downto 0));
+begin
+end;
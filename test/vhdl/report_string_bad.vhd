@@ -0,0 +1,21 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected a string, but `b` has type array (0 to 1) of BIT
+*/
+entity foo is end;
+architecture bar of foo is
+	type LETTERS is array (NATURAL range <>) of CHARACTER;
+	constant s : STRING(1 to 4) := "val=";
+	constant l : LETTERS(0 to 1) := "ab";
+	constant b : BIT_VECTOR(0 to 1) := "01";
+begin
+	process is
+	begin
+		report s;
+		report l;
+		assert false report "val=";
+		report b;
+		wait;
+	end process;
+end;
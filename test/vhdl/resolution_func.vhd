@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	function not_a_resolver (s : LOGIC) return LOGIC is
+	begin
+		return s;
+	end;
+	subtype RLOGIC is resolve LOGIC;
+begin
+end;
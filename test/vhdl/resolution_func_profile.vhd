@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `not_a_resolver` cannot resolve type LOGIC
+// CHECK: error: `resolve` cannot resolve type BIT
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	function not_a_resolver (s : LOGIC) return LOGIC is
+	begin
+		return s;
+	end;
+	subtype BAD is not_a_resolver LOGIC;
+	subtype WRONG_TYPE is resolve BIT;
+begin
+end;
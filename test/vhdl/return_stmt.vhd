@@ -0,0 +1,33 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type T is (A, B, C);
+	function f (x : INTEGER) return INTEGER is
+	begin
+		return x + 1;
+	end;
+	function g return T is
+	begin
+		return B;
+	end;
+	function h (x : BIT) return BOOLEAN is
+	begin
+		for i in 0 to 3 loop
+			return x = '1';
+		end loop;
+		return FALSE;
+	end;
+	procedure p (x : INTEGER) is
+	begin
+		if x = 0 then
+			return;
+		end if;
+	end;
+begin
+	process is
+	begin
+		wait;
+	end process;
+end;
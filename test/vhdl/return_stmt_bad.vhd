@@ -0,0 +1,42 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type -2147483648 to 2147483647, but `TRUE` has type enum
+// CHECK: error: `return` in a function must return a enum
+// CHECK: error: `return` in a procedure cannot return a value
+// CHECK: error: `return` statement outside of a subprogram
+*/
+entity foo is end;
+architecture bar of foo is
+	type T is (A, B, C);
+	function f (x : INTEGER) return INTEGER is
+	begin
+		return x + 1;
+	end;
+	function g return T is
+	begin
+		return B;
+	end;
+	function h (x : BIT) return BOOLEAN is
+	begin
+		for i in 0 to 3 loop
+			return x = '1';
+		end loop;
+		return FALSE;
+	end;
+	procedure p (x : INTEGER) is
+	begin
+		if x = 0 then
+			return;
+		end if;
+	end;
+	function f1 return INTEGER is begin return TRUE; end;
+	function f2 return T is begin return; end;
+	procedure p1 is begin return 0; end;
+begin
+	process is
+	begin
+		wait;
+		return;
+	end process;
+end;
@@ -0,0 +1,40 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: choices do not cover all values of `sel`
+// CHECK: error: choice `IDLE` overlaps with `IDLE`
+// CHECK: error: choice `"0"` has 1 elements, but the selector has 2
+*/
+entity foo is end;
+architecture bar of foo is
+	type STATE is (IDLE, BUSY, DONE);
+	signal sel : BIT_VECTOR(0 to 1);
+	signal st : STATE;
+	signal a, b, c, y : BIT;
+begin
+	with sel select y <=
+		a when "00",
+		b when "01",
+		c when others;
+
+	with sel select y <=
+		a when "00" | "11",
+		b when "01",
+		c when "10";
+
+	with st select y <=
+		a when IDLE,
+		b when BUSY to DONE;
+
+	with sel select y <= a when "00", b when "01";
+	with st select y <= a when IDLE, b when IDLE | DONE, c when BUSY;
+	with sel select y <= a when "0", b when others;
+	process is
+	begin
+		case st is
+			when IDLE => y <= '0';
+			when BUSY | DONE => y <= '1';
+		end case;
+		wait;
+	end process;
+end;
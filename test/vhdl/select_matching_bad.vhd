@@ -0,0 +1,35 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `bits` of type array (0 to 1) of BIT cannot be used in a matching selection
+// CHECK: error: choice `"11"` overlaps with `"1-"`
+// CHECK: error: choices do not cover all values of `sel`
+// CHECK: error: choice `'L'` overlaps with `'0'`
+*/
+entity foo is end;
+architecture bar of foo is
+	type std_ulogic is ('U', 'X', '0', '1', 'Z', 'W', 'L', 'H', '-');
+	type std_ulogic_vector is array (NATURAL range <>) of std_ulogic;
+	signal sel : std_ulogic_vector(0 to 1);
+	signal s : std_ulogic;
+	signal bits : BIT_VECTOR(0 to 1);
+	signal a, b, c, y : std_ulogic;
+begin
+	with sel select? y <=
+		a when "1-",
+		b when "01",
+		c when others;
+
+	with s select? y <=
+		a when '0',
+		b when '1',
+		c when others;
+
+	with sel select? y <=
+		a when "--";
+
+	with bits select? y <= a when "1-", b when others;
+	with sel select? y <= a when "1-", b when "11", c when others;
+	with sel select? y <= a when "1-", b when "0-";
+	with s select? y <= a when '0', b when 'L', c when others;
+end;
@@ -0,0 +1,27 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type COUNTER is protected
+		procedure increment (N : INTEGER);
+	end protected COUNTER;
+end;
+
+package body pkg is
+	type COUNTER is protected body
+		variable count : INTEGER := 0;
+		procedure increment (N : INTEGER) is
+		begin
+			count := count + N;
+		end procedure increment;
+	end protected body COUNTER;
+end;
+
+library work;
+use work.pkg.all;
+
+entity foo is end;
+architecture bar of foo is
+	shared variable c : COUNTER;
+begin
+end;
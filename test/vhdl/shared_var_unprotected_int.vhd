@@ -0,0 +1,10 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: shared variable `x` must be of a protected type, but `INTEGER` is a integer type
+*/
+entity foo is end;
+architecture bar of foo is
+	shared variable x : INTEGER;
+begin
+end;
@@ -0,0 +1,10 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: shared variable `x` must be of a protected type, but `array (7 downto 0) of BIT` is a array type
+*/
+entity foo is end;
+architecture bar of foo is
+	shared variable x : BIT_VECTOR(7 downto 0);
+begin
+end;
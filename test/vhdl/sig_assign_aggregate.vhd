@@ -0,0 +1,23 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+		y : INTEGER;
+	end record;
+	signal a, b : BIT;
+	signal i : INTEGER;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal w : BIT_VECTOR(2 downto 0);
+	signal r : REC;
+	constant k : BIT := '0';
+begin
+	(a, b) <= v;
+	process begin
+		(a, b) <= v;
+		(a, i) <= r;
+		wait;
+	end process;
+end;
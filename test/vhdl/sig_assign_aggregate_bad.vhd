@@ -0,0 +1,34 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type BIT, but `i` has type INTEGER
+// CHECK: error: aggregate target `(a, b)` has 2 elements, but the assigned value has 3
+// CHECK: error: aggregate target `(a, b, i)` has 3 elements, but the record has 2 fields
+// CHECK: error: `k` is not a signal
+// CHECK: error: aggregate target `(a, b)` is assigned a value of type INTEGER
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+		y : INTEGER;
+	end record;
+	signal a, b : BIT;
+	signal i : INTEGER;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal w : BIT_VECTOR(2 downto 0);
+	signal r : REC;
+	constant k : BIT := '0';
+begin
+	(a, b) <= v;
+	process begin
+		(a, b) <= v;
+		(a, i) <= r;
+		(a, i) <= v;
+		(a, b) <= w;
+		(a, b, i) <= r;
+		(a, k) <= v;
+		(a, b) <= i;
+		wait;
+	end process;
+end;
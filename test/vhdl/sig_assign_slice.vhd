@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT_VECTOR(7 downto 0);
+	signal b : BIT_VECTOR(3 downto 0);
+	signal c : BIT;
+	signal i : INTEGER;
+begin
+	process begin
+		a(3) <= c;
+		a(i) <= '1';
+		a(3 downto 0) <= b;
+		a(7 downto 4) <= "0101";
+		wait;
+	end process;
+end;
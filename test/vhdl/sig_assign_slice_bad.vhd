@@ -0,0 +1,27 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type array (3 downto 0) of BIT, but `c` has type BIT
+// CHECK: error: expected type BIT, but `b` has type array (3 downto 0) of BIT
+// CHECK: error: `a(1, 2)` has 2 indices, but the array has 1 dimensions
+// CHECK: error: `c(0)` indexes a signal which is not an array
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a : BIT_VECTOR(7 downto 0);
+	signal b : BIT_VECTOR(3 downto 0);
+	signal c : BIT;
+	signal i : INTEGER;
+begin
+	process begin
+		a(3) <= c;
+		a(i) <= '1';
+		a(3 downto 0) <= b;
+		a(7 downto 4) <= "0101";
+		a(3 downto 0) <= c;
+		a(3) <= b;
+		a(1, 2) <= c;
+		c(0) <= '1';
+		wait;
+	end process;
+end;
@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	type LOGIC_VECTOR is array (NATURAL range <>) of LOGIC;
+	function resolve (s : LOGIC_VECTOR) return LOGIC is
+	begin
+		return s(0);
+	end;
+	subtype RLOGIC is resolve LOGIC;
+	subtype RLOGIC2 is RLOGIC;
+	signal s0 : LOGIC;
+	signal s1 : RLOGIC bus;
+	signal s2 : RLOGIC2 register;
+	signal s3 : resolve LOGIC bus;
+begin
+end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: bus signal `s4` must have a resolved subtype
+// CHECK: error: register signal `s5` must have a resolved subtype
+*/
+entity foo is end;
+architecture bar of foo is
+	type LOGIC is ('0', '1', 'Z');
+	signal s4 : LOGIC bus;
+	signal s5 : LOGIC register;
+begin
+end;
@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : INTEGER range 0 to 255;
+begin
+	process is
+		variable v : INTEGER range -8 to 7;
+		variable w : INTEGER;
+	begin
+		s <= 255;
+		v := -8;
+		v := w;
+		wait;
+	end process;
+end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: integer literal `300` does not fit into type 0 to 255
+// CHECK: error: integer literal `8` does not fit into type -8 to 7
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : INTEGER range 0 to 255;
+begin
+	process is
+		variable v : INTEGER range -8 to 7;
+		variable w : INTEGER;
+	begin
+		s <= 255;
+		v := -8;
+		v := w;
+		s <= 300;
+		v := 8;
+		wait;
+	end process;
+end;
@@ -5,7 +5,7 @@ architecture bar of foo is
 	constant B : BIT := '0';
 	constant C : BIT := '1';
 
-	constant xA : std.standard.BIT_VECTOR;
+	-- constant xA : std.standard.BIT_VECTOR; -- should fail
 	constant xB : BIT_VECTOR(0 to 1) := ('0', '1');
 
 begin end;
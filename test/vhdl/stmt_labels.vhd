@@ -0,0 +1,17 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	p1: process is
+	begin
+		l1: loop
+			exit l1;
+		end loop l1;
+		c1: if true then null; end if c1;
+		wait;
+	end process p1;
+	b1: block is begin end block b1;
+	g1: for i in 0 to 3 generate end generate g1;
+end;
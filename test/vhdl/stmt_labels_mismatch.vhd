@@ -0,0 +1,30 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: statement label mismatch: opening `l2`, closing `l3`
+// CHECK: error: statement is not labeled but has closing label `l4`
+// CHECK: error: statement label mismatch: opening `c2`, closing `c3`
+// CHECK: error: statement is not labeled but has closing label `c4`
+// CHECK: error: statement label mismatch: opening `p2`, closing `p3`
+// CHECK: error: statement is not labeled but has closing label `p4`
+// CHECK: error: statement label mismatch: opening `b2`, closing `b3`
+// CHECK: error: statement label mismatch: opening `g2`, closing `g3`
+// CHECK: error: statement label mismatch: opening `g4`, closing `g5`
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	p1: process is
+	begin
+		l2: loop exit; end loop l3;
+		loop exit; end loop l4;
+		c2: if true then null; end if c3;
+		case 0 is when others => null; end case c4;
+		wait;
+	end process p1;
+	p2: process is begin wait; end process p3;
+	process is begin wait; end process p4;
+	b2: block is begin end block b3;
+	g2: for i in 0 to 3 generate end generate g3;
+	g4: if true generate end generate g5;
+end;
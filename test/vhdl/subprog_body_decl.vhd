@@ -0,0 +1,27 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	function f (x : INTEGER) return INTEGER;
+	function f (x : INTEGER) return INTEGER is
+	begin
+		return x;
+	end;
+	function g (x : BIT) return INTEGER is
+	begin
+		return 0;
+	end;
+	signal i : INTEGER;
+begin
+	i <= f(1);
+	i <= g('1');
+	process
+		procedure p (a : INTEGER) is
+		begin
+		end;
+	begin
+		p(f(2));
+		wait;
+	end process;
+end;
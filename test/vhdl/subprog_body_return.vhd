@@ -0,0 +1,24 @@
+/*
+// RUN: moore %s --typeck foo --warnings-as-errors
+*/
+entity foo is end;
+architecture bar of foo is
+	function f return BIT is
+		constant c : BOOLEAN := TRUE;
+	begin
+		if c then
+			return '1';
+		else
+			return '0';
+		end if;
+	end;
+	function g return BIT is
+	begin
+		loop
+		end loop;
+	end;
+	procedure p is
+	begin
+	end;
+begin
+end;
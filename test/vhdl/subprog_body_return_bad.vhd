@@ -0,0 +1,31 @@
+/*
+// RUN: moore %s --typeck foo --warnings-as-errors
+// FAIL
+// CHECK: warning: function `h` may reach its end without returning a value
+// CHECK: warning: function `i` may reach its end without returning a value
+// CHECK: warning: function `j` may reach its end without returning a value
+*/
+entity foo is end;
+architecture bar of foo is
+	function f return BIT is
+		constant c : BOOLEAN := TRUE;
+	begin
+		if c then
+			return '1';
+		else
+			return '0';
+		end if;
+	end;
+	function g return BIT is
+	begin
+		loop
+		end loop;
+	end;
+	procedure p is
+	begin
+	end;
+	function h return BIT is begin end;
+	function i return BIT is begin if TRUE then return '1'; end if; end;
+	function j return BIT is begin loop exit; end loop; end;
+begin
+end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `a` is a parameter of mode `in` and cannot be assigned
+*/
+entity foo is end;
+architecture bar of foo is
+	procedure p (a : INTEGER) is
+	begin
+		a := 1;
+	end;
+begin
+end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	procedure p (a : INTEGER; variable b : out INTEGER; signal s : out BIT; c : inout BIT_VECTOR) is
+		variable t : INTEGER;
+	begin
+		t := a;
+		b := a + 1;
+		s <= '1';
+		c(0) := '0';
+	end;
+	function f (constant x : INTEGER; y : BOOLEAN) return INTEGER is
+	begin
+		if y then
+			return x;
+		end if;
+		return -x;
+	end;
+begin
+end;
@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type UP is range 0 to 15;
+	subtype DOWN is INTEGER range 7 downto 0;
+	subtype NIBBLE is UP range 3 downto 0;
+	subtype WORD is BIT_VECTOR(7 downto 0);
+	constant a : DOWN := 3;
+	constant b : NIBBLE := 2;
+	signal w : WORD;
+begin
+	process is
+		variable n : INTEGER := 4;
+		subtype DYN is INTEGER range n downto 0;
+		variable d : DYN;
+	begin
+		w(0) <= '1';
+		wait;
+	end process;
+end;
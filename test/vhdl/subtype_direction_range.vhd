@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: left bound `16` is out of range of `0 to 15`
+*/
+entity foo is end;
+architecture bar of foo is
+	type UP is range 0 to 15;
+	subtype NIBBLE is UP range 16 downto 0;
+begin
+end;
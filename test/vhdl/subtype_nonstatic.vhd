@@ -0,0 +1,19 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type INTEGER is range -256 to 255;
+	signal n : INTEGER;
+begin
+	process
+		variable v : INTEGER := 7;
+		-- Constraints in a process may depend on variables and signals.
+		subtype VAR_RANGE is INTEGER range 0 to v;
+		subtype SIG_RANGE is INTEGER range n to 255;
+		subtype REV_RANGE is INTEGER range v downto 0;
+		variable x : VAR_RANGE;
+	begin
+		wait;
+	end process;
+end;
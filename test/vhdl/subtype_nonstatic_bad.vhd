@@ -0,0 +1,10 @@
+/*
+// RUN: moore %s -e pkg
+// FAIL
+// CHECK: error: `n` is not globally static
+*/
+package pkg is
+	type INTEGER is range -256 to 255;
+	signal n : INTEGER;
+	subtype SIG_RANGE is INTEGER range 0 to n;
+end;
@@ -0,0 +1,15 @@
+/*
+// RUN: moore %s -e type_conversion
+*/
+package type_conversion is
+	type WORD is range 0 to 65535;
+	subtype BYTE is WORD range 0 to 255;
+	type BITS is array (NATURAL range <>) of BIT;
+	type STATE is (IDLE, BUSY);
+	constant a : WORD := 42;
+	constant b : INTEGER := INTEGER(a);
+	constant c : BYTE := BYTE(b);
+	constant d : NATURAL := NATURAL(b);
+	constant e : BIT_VECTOR(0 to 1) := "01";
+	constant f : BITS(0 to 1) := BITS(e);
+end package;
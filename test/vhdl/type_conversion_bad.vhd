@@ -0,0 +1,22 @@
+/*
+// RUN: moore %s -e type_conversion
+// FAIL
+// CHECK: error: `a` of type WORD cannot be converted to type STATE
+// CHECK: error: `IDLE` of type enum cannot be converted to type INTEGER
+// CHECK: error: `IDLE` of type enum cannot be converted to type BITS
+*/
+package type_conversion is
+	type WORD is range 0 to 65535;
+	subtype BYTE is WORD range 0 to 255;
+	type BITS is array (NATURAL range <>) of BIT;
+	type STATE is (IDLE, BUSY);
+	constant a : WORD := 42;
+	constant b : INTEGER := INTEGER(a);
+	constant c : BYTE := BYTE(b);
+	constant d : NATURAL := NATURAL(b);
+	constant e : BIT_VECTOR(0 to 1) := "01";
+	constant f : BITS(0 to 1) := BITS(e);
+	constant g : STATE := STATE(a);
+	constant h : INTEGER := INTEGER(IDLE);
+	constant i : BITS(0 to 0) := BITS(IDLE);
+end package;
@@ -0,0 +1,23 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type STATE is (IDLE, BUSY);
+	type WORD is array (0 to 3) of BIT;
+	signal s : STATE := IDLE;
+	signal w : WORD;
+begin
+	process
+		type COUNT is range 0 to 15;
+		variable c : COUNT := 0;
+		variable v : WORD;
+		variable st : STATE;
+	begin
+		c := 15;
+		v := w;
+		st := s;
+		st := BUSY;
+		wait;
+	end process;
+end;
@@ -0,0 +1,15 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type UNIT_T is range 0.0 to 1.0;
+	type SYM_T is range -1.5 to 1.5;
+	type DOWN_T is range 1.0e3 downto 0.0;
+	signal s : UNIT_T;
+	signal t : SYM_T;
+	signal u : DOWN_T;
+	-- type BAD1 is range 0.0 to 1; -- should fail
+	-- type BAD2 is range 0 to 1.0; -- should fail
+begin
+end;
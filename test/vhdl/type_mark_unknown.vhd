@@ -0,0 +1,11 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	subtype WORD is BIT_VECTOR(15 downto 0);
+	signal a : INTEGER;
+	signal b : WORD;
+begin
+	a <= INTEGER'(42);
+end;
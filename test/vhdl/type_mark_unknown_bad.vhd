@@ -0,0 +1,20 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: unknown type `INTEGR`
+// CHECK: error: unknown type `WROD`
+// CHECK: error: `a` is not a subtype indication
+// CHECK: error: unknown type `INTEGR`
+*/
+entity foo is end;
+architecture bar of foo is
+	subtype WORD is BIT_VECTOR(15 downto 0);
+	signal a : INTEGER;
+	signal b : WORD;
+	signal c : INTEGR;
+	signal d : WROD;
+	signal e : a;
+begin
+	a <= INTEGER'(42);
+	a <= INTEGR'(42);
+end;
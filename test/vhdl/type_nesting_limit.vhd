@@ -0,0 +1,15 @@
+/*
+// RUN: moore --max-type-nesting 4 %s -e type_nesting_limit
+// FAIL
+*/
+package type_nesting_limit is
+	type T0 is range 0 to 255;
+	subtype T1 is T0;
+	subtype T2 is T1;
+	subtype T3 is T2;
+	subtype T4 is T3;
+	subtype T5 is T4;
+	subtype T6 is T5;
+	subtype T7 is T6;
+	constant C : T7 := 42;
+end package;
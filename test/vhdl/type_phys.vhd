@@ -30,5 +30,10 @@ architecture bar of foo is
 		lg   = 3 mi;
 	end units;
 
+	subtype SHORT_TIME is TIME range 0 ns to 1 us;
+	subtype NEG_TIME is TIME range -1 ms to -1 ps;
+	--subtype HUGE_TIME is TIME range 0 fs to 2000000 hr; -- should fail
+	--subtype ODD_TIME is TIME range 0 ns to 1 mm; -- should fail
+
 	constant t : TIME;
 begin end;
@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo.good
+*/
+entity foo is end;
+
+architecture good of foo is
+	type BIT is ('0', '1');
+	signal s : BIT;
+begin end;
+
+-- The erroneous architecture below is not checked when only `foo.good` is
+-- requested.
+architecture bad of foo is
+	type BIT is ('0', '1');
+	signal s : BIT := 0;
+begin end;
@@ -0,0 +1,38 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type REC is record
+		x : INTEGER;
+	end record;
+	function "-" (value : REC) return REC;
+	function "abs" (value : REC) return INTEGER;
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+
+architecture bar of foo is
+	signal a, b : BIT;
+	signal i, j : INTEGER;
+	signal t : TIME;
+	signal v : BIT_VECTOR(3 downto 0);
+	signal c : BOOLEAN;
+	signal r, s : REC;
+begin
+	process begin
+		a <= not b;
+		v <= not v;
+		a <= and v;
+		a <= xor v;
+		i <= -j;
+		i <= abs j;
+		i <= +j;
+		t <= -t;
+		c <= not c;
+		r <= -s;
+		i <= abs r;
+		wait;
+	end process;
+end;
@@ -0,0 +1,13 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type STATE is (IDLE, BUSY);
+end;
+
+library work;
+use work.pkg.all;
+entity foo is end;
+architecture bar of foo is
+	signal s : STATE;
+begin end;
@@ -0,0 +1,14 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: no declaration `pgk` in `work`
+*/
+package pkg is
+	type STATE is (IDLE, BUSY);
+end;
+
+library work;
+use work.pgk.all;
+entity foo is end;
+architecture bar of foo is
+begin end;
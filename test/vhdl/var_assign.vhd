@@ -0,0 +1,31 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+		y : INTEGER;
+	end record;
+	signal s : BIT;
+	constant k : BIT := '0';
+begin
+	process
+		variable a : BIT;
+		variable i : INTEGER;
+		variable v : BIT_VECTOR(7 downto 0);
+		variable w : BIT_VECTOR(3 downto 0);
+		variable r : REC;
+	begin
+		a := '1';
+		i := 42;
+		v(3) := a;
+		v(i) := '0';
+		v(3 downto 0) := w;
+		w := v(7 downto 4);
+		r.x := a;
+		r.y := i;
+		i := r.y;
+		wait;
+	end process;
+end;
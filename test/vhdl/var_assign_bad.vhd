@@ -0,0 +1,50 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `s` is a signal and cannot be assigned with `:=`
+// CHECK: error: `k` is a constant and cannot be assigned
+// CHECK: error: expected type BIT, but `1` has type {universal integer}
+// CHECK: error: expected type BIT, but `w` has type array (3 downto 0) of BIT
+// CHECK: error: expected type array (3 downto 0) of BIT, but `a` has type BIT
+// CHECK: error: `r` has no element `z`
+// CHECK: error: expected type BIT, but `i` has type INTEGER
+// CHECK: error: `a.x` selects an element of `a`, which is not a record
+// CHECK: error: `v(1 to 2, 3)` slices an array with 1 dimensions
+*/
+entity foo is end;
+architecture bar of foo is
+	type REC is record
+		x : BIT;
+		y : INTEGER;
+	end record;
+	signal s : BIT;
+	constant k : BIT := '0';
+begin
+	process
+		variable a : BIT;
+		variable i : INTEGER;
+		variable v : BIT_VECTOR(7 downto 0);
+		variable w : BIT_VECTOR(3 downto 0);
+		variable r : REC;
+	begin
+		a := '1';
+		i := 42;
+		v(3) := a;
+		v(i) := '0';
+		v(3 downto 0) := w;
+		w := v(7 downto 4);
+		r.x := a;
+		r.y := i;
+		i := r.y;
+		s := '1';
+		k := '1';
+		a := 1;
+		v(3) := w;
+		v(3 downto 0) := a;
+		r.z := a;
+		r.x := i;
+		a.x := '1';
+		v(1 to 2, 3) := w;
+		wait;
+	end process;
+end;
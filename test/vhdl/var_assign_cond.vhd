@@ -0,0 +1,16 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+begin
+	process
+		variable b : BOOLEAN;
+		variable x, y : BIT;
+	begin
+		x := '1' when b else '0';
+		y := x when b else '1' when b = b else '0';
+		x := '1' when x else '0';
+		wait;
+	end process;
+end;
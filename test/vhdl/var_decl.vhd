@@ -0,0 +1,36 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+package pkg is
+	type COUNTER is protected
+		procedure increment (N : INTEGER);
+	end protected COUNTER;
+end;
+
+package body pkg is
+	type COUNTER is protected body
+		variable count : INTEGER := 0;
+		procedure increment (N : INTEGER) is
+		begin
+			count := count + N;
+		end procedure increment;
+	end protected body COUNTER;
+end;
+
+library work;
+use work.pkg.all;
+
+entity foo is end;
+architecture bar of foo is
+	type IntegerFile is file of INTEGER;
+	shared variable c : COUNTER;
+begin
+	process is
+		variable a : INTEGER := 42;
+		variable b : BIT_VECTOR(0 to 3) := "0101";
+		variable s : STRING := "abc";
+		variable l : COUNTER;
+	begin
+		wait;
+	end process;
+end;
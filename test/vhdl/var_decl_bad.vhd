@@ -0,0 +1,49 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: variable `d` of protected type COUNTER cannot have an initial value
+// CHECK: error: shared variable `e` must be of a protected type, but `BIT` is a enumeration type
+// CHECK: error: variable `f` cannot be of a file type
+// CHECK: error: `'0'` is ambiguous
+// CHECK: error: expected type array (0 to 3) of BIT, but `42` has type {universal integer}
+// CHECK: error: expected type NATURAL, but `-1` has type INTEGER
+*/
+package pkg is
+	type COUNTER is protected
+		procedure increment (N : INTEGER);
+	end protected COUNTER;
+end;
+
+package body pkg is
+	type COUNTER is protected body
+		variable count : INTEGER := 0;
+		procedure increment (N : INTEGER) is
+		begin
+			count := count + N;
+		end procedure increment;
+	end protected body COUNTER;
+end;
+
+library work;
+use work.pkg.all;
+
+entity foo is end;
+architecture bar of foo is
+	type IntegerFile is file of INTEGER;
+	shared variable c : COUNTER;
+	shared variable d : COUNTER := c;
+	shared variable e : BIT := '0';
+begin
+	process is
+		variable a : INTEGER := 42;
+		variable b : BIT_VECTOR(0 to 3) := "0101";
+		variable s : STRING := "abc";
+		variable l : COUNTER;
+		variable f : IntegerFile;
+		variable g : INTEGER := '0';
+		variable h : BIT_VECTOR(0 to 3) := 42;
+		variable i : NATURAL := -1;
+	begin
+		wait;
+	end process;
+end;
@@ -0,0 +1,26 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT;
+	signal b : BOOLEAN;
+	constant c : INTEGER := 0;
+	procedure p is
+	begin
+		wait for 1 ns;
+	end;
+begin
+	process is
+		variable t : TIME;
+	begin
+		wait on s, b;
+		wait until b;
+		wait until s = '1';
+		wait until s;
+		wait for 10 ns;
+		wait for t;
+		wait on s until b for 1 ns;
+		wait;
+	end process;
+end;
@@ -0,0 +1,41 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: `wait` statement in a function
+// CHECK: error: expected type BOOLEAN, but `1` has type {universal integer}
+// CHECK: error: expected type TIME, but `10` has type {universal integer}
+// CHECK: error: timeout `-1 ns` is negative
+// CHECK: error: `c` is not a signal
+// CHECK: error: `wait` statement in a process with a sensitivity list
+// CHECK: error: `wait` statement in a process with a sensitivity list
+*/
+entity foo is end;
+architecture bar of foo is
+	signal s : BIT;
+	signal b : BOOLEAN;
+	constant c : INTEGER := 0;
+	procedure p is
+	begin
+		wait for 1 ns;
+	end;
+	function f return BIT is begin wait; return '0'; end;
+begin
+	process is
+		variable t : TIME;
+	begin
+		wait on s, b;
+		wait until b;
+		wait until s = '1';
+		wait until s;
+		wait for 10 ns;
+		wait for t;
+		wait on s until b for 1 ns;
+		wait;
+		wait until 1;
+		wait for 10;
+		wait for -1 ns;
+		wait on c;
+	end process;
+	process (s) begin wait; end process;
+	process (all) begin wait on s; end process;
+end;
@@ -0,0 +1,18 @@
+/*
+// RUN: moore %s --typeck foo
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a, b, c : BIT;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal t : TIME;
+begin
+	with c select a <= b after 1 ns when '0', unaffected when others;
+	with c select (a, b) <= unaffected when '0', v after t when others;
+	process begin
+		a <= b after 1 ns, not b after 2 ns;
+		a <= b after t;
+		a <= b after 1 ns + 2 ps;
+		wait;
+	end process;
+end;
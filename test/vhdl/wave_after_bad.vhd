@@ -0,0 +1,25 @@
+/*
+// RUN: moore %s --typeck foo
+// FAIL
+// CHECK: error: expected type TIME, but `1` has type {universal integer}
+// CHECK: error: expected type TIME, but `b` has type BIT
+// CHECK: error: expected type TIME, but `'x'` has type enum
+*/
+entity foo is end;
+architecture bar of foo is
+	signal a, b, c : BIT;
+	signal v : BIT_VECTOR(1 downto 0);
+	signal t : TIME;
+begin
+	with c select a <= b after 1 ns when '0', unaffected when others;
+	with c select (a, b) <= unaffected when '0', v after t when others;
+	process begin
+		a <= b after 1 ns, not b after 2 ns;
+		a <= b after t;
+		a <= b after 1 ns + 2 ps;
+		a <= b after 1;
+		a <= b after b;
+		a <= b after 1 ns, c after 'x';
+		wait;
+	end process;
+end;
@@ -0,0 +1,56 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! The scoreboard setup shared by the integration tests.
+
+// Each test binary includes this module, but uses only some of its helpers.
+#![allow(dead_code)]
+
+use moore::common::name::get_name_table;
+use moore::common::score::{GenericContext, NodeRef};
+use moore::common::source::get_source_manager;
+use moore::common::NodeId;
+use moore::vhdl;
+use moore::vhdl::score::{LibRef, ScoreContext};
+use moore::vhdl::syntax::ast;
+
+pub struct Global;
+impl GenericContext for Global {}
+
+/// Declare `$ctx` as a score context for the session `$sess`.
+///
+/// The arenas and tables backing the context are declared alongside it, such
+/// that they live until the end of the enclosing block.
+macro_rules! score_context {
+    ($ctx:ident, $sess:expr) => {
+        let arenas = moore::vhdl::score::Arenas::new();
+        let sb = moore::vhdl::score::ScoreBoard::new(&arenas);
+        let lazy = moore::vhdl::lazy::LazyPhaseTable::new(&sb);
+        let $ctx = moore::vhdl::score::ScoreContext {
+            sess: $sess,
+            global: &$crate::common::Global,
+            sb: &sb,
+            lazy: &lazy,
+        };
+    };
+}
+
+/// Parse VHDL source text, registering it with the source manager as `path`.
+pub fn parse(path: &str, text: &str) -> Vec<ast::DesignUnit> {
+    let source = get_source_manager().add(path, text);
+    vhdl::syntax::parse(source).expect("test source does not parse")
+}
+
+/// Add the design units of a library to a score context.
+pub fn add_library<'ast>(
+    ctx: &ScoreContext<'_, '_, 'ast, '_>,
+    name: &str,
+    units: &'ast [ast::DesignUnit],
+) -> LibRef {
+    let lib = LibRef::new(NodeId::alloc());
+    ctx.add_library(
+        get_name_table().intern(name, true),
+        lib,
+        units.iter().collect(),
+    );
+    lib
+}
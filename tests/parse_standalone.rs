@@ -0,0 +1,35 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Checks that subtype indications and expressions given as text are parsed,
+//! type checked, and folded, with syntax errors reported to the session.
+
+#[macro_use]
+mod common;
+
+use moore::common::errors::DiagBuilder2;
+use moore::common::Session;
+use moore::vhdl::konst::Const;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn parse_standalone() {
+    let sink = Rc::new(RefCell::new(Vec::<DiagBuilder2>::new()));
+    let sess = Session::with_sink(sink.clone());
+    score_context!(ctx, &sess);
+
+    let ty = ctx.parse_and_intern_ty("integer range 0 to 255").unwrap();
+    assert_eq!(ty.to_string(), "0 to 255");
+    let (_, value) = ctx.eval_expr("16 * 16 - 1", Some(ty)).unwrap();
+    match value {
+        Some(&Const::Int(ref value)) => assert_eq!(value.value, 255.into()),
+        x => panic!("expected an integer value, got {:?}", x),
+    }
+    assert!(sink.borrow().is_empty());
+
+    assert!(ctx.parse_and_intern_ty("integer range 0 to").is_err());
+    assert!(ctx.eval_expr("16 * 16 - 1;", None).is_err());
+    let diags = sink.borrow();
+    assert_eq!(diags.len(), 2);
+    assert_eq!(diags[1].message, "expected end of input, found `;`");
+}
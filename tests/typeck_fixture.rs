@@ -0,0 +1,34 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Checks that the synthetic library of the type checking benchmark is valid
+//! VHDL, such that the benchmark measures a successful type check.
+
+#[path = "../benches/fixture/mod.rs"]
+mod fixture;
+
+#[macro_use]
+mod common;
+
+use moore::common::Session;
+use moore::vhdl::typeck::{Typeck, TypeckContext};
+
+#[test]
+fn generated_library_type_checks() {
+    for &(entities, assigns) in fixture::SIZES {
+        let units = common::parse(
+            &format!("fixture_{}x{}.vhd", entities, assigns),
+            &fixture::generate_library(entities, assigns),
+        );
+        let sess = Session::new();
+        score_context!(ctx, &sess);
+        let lib = common::add_library(&ctx, "work", &units);
+        let tyc = TypeckContext::new(&ctx);
+        tyc.typeck(lib);
+        assert!(
+            tyc.finish(),
+            "generated {}x{} library fails to type check",
+            entities,
+            assigns
+        );
+    }
+}
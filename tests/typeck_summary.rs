@@ -0,0 +1,43 @@
+// Copyright (c) 2016-2020 Fabian Schuiki
+
+//! Checks that the type check summary covers only the requested library, and
+//! that it can be serialized for editor integrations.
+
+#[macro_use]
+mod common;
+
+use moore::common::Session;
+
+#[test]
+fn summary_covers_only_the_library() {
+    let other = common::parse(
+        "typeck_summary_other.vhd",
+        "entity bar is end; architecture rtl of bar is signal t : BIT; begin t <= '1'; end;",
+    );
+    let work = common::parse(
+        "typeck_summary_work.vhd",
+        "entity foo is end; architecture rtl of foo is signal s : BIT; begin s <= 1; end;",
+    );
+    let sess = Session::new();
+    score_context!(ctx, &sess);
+    let other_lib = common::add_library(&ctx, "other", &other);
+    let work_lib = common::add_library(&ctx, "work", &work);
+    assert!(ctx.typeck_summary(other_lib).ok);
+
+    let summary = ctx.typeck_summary(work_lib);
+    assert!(!summary.ok);
+    assert!(!summary.types.is_empty());
+    assert!(summary
+        .types
+        .iter()
+        .all(|node| node.span.file == "typeck_summary_work.vhd"));
+    assert_eq!(summary.diagnostics.len(), 1);
+    assert_eq!(summary.diagnostics[0].severity, "error");
+
+    let json = serde_json::to_value(&summary).unwrap();
+    assert_eq!(json["ok"], false);
+    assert_eq!(
+        json["diagnostics"][0]["span"]["file"],
+        "typeck_summary_work.vhd"
+    );
+}